@@ -1,7 +1,8 @@
 //! Demonstrates full-duplex, async SPI I/O.
 //!
-//! Connect SDI to SDO to enable loopback transfers. If there's no loopback,
-//! the example panics. Use this to interrupt an otherwise working example.
+//! The LPSPI loops back its own output, so you don't need to connect SDI
+//! to SDO. If the received data doesn't match the transmitted data, the
+//! example panics.
 
 #![no_std]
 #![no_main]
@@ -9,6 +10,7 @@
 #[imxrt_rt::entry]
 fn main() -> ! {
     let (board::Common { mut dma, .. }, board::Specifics { mut spi, .. }) = board::new();
+    spi.set_loopback(true);
 
     let mut chan_a = dma[board::BOARD_DMA_A_INDEX].take().unwrap();
     chan_a.set_disable_on_completion(true);
@@ -8,9 +8,10 @@
 #![no_std]
 #![no_main]
 
+use core::time::Duration;
 use imxrt_hal as hal;
 
-const PIT_DELAY_MS: u32 = board::PIT_FREQUENCY / 1_000 * 250;
+const PIT_DELAY: Duration = Duration::from_millis(250);
 
 const _: () = assert!(board::PWM_FREQUENCY / 1000 < i16::MAX as u32);
 const SWITCHING_FREQ: i16 = (board::PWM_FREQUENCY / 1000) as i16;
@@ -20,9 +21,10 @@ const PWM_B_DUTY: u32 = PWM_A_DUTY / 2;
 
 #[imxrt_rt::entry]
 fn main() -> ! {
-    let (board::Common { mut pit, .. }, board::Specifics { led, pwm, .. }) = board::new();
-    pit.0.set_load_timer_value(PIT_DELAY_MS);
-    pit.0.enable();
+    let (board::Common { pit, .. }, board::Specifics { led, pwm, .. }) = board::new();
+    let mut pit = hal::timer::DurationPit::<0, { board::PIT_FREQUENCY }>::from_pit(pit.0);
+    pit.set_period(PIT_DELAY);
+    pit.pit_mut().enable();
 
     let board::Pwm {
         mut module,
@@ -30,18 +32,12 @@ fn main() -> ! {
         outputs: (out_a, out_b),
     } = pwm;
 
-    submodule.set_debug_enable(true);
-    submodule.set_wait_enable(true);
-    submodule.set_clock_select(hal::flexpwm::ClockSelect::Ipg);
-    submodule.set_prescaler(board::PWM_PRESCALER);
-    submodule.set_pair_operation(hal::flexpwm::PairOperation::Independent);
-    submodule.set_load_mode(hal::flexpwm::LoadMode::reload_full());
-    submodule.set_load_frequency(1);
-    submodule.set_initial_count(&module, SWITCHING_FREQ / -2i16);
-    submodule.set_value(
-        hal::flexpwm::FULL_RELOAD_VALUE_REGISTER,
-        SWITCHING_FREQ / 2i16,
-    );
+    const CONFIG: hal::flexpwm::SubmoduleConfig =
+        hal::flexpwm::SubmoduleConfig::new().prescaler(board::PWM_PRESCALER);
+    module.configure(&mut submodule, &CONFIG).unwrap();
+    module
+        .set_center_aligned(&mut submodule, SWITCHING_FREQ)
+        .unwrap();
 
     out_a.set_turn_on(&submodule, PWM_A_DUTY as i16 / -2i16);
     out_a.set_turn_off(&submodule, PWM_A_DUTY as i16 / 2i16);
@@ -51,12 +47,12 @@ fn main() -> ! {
 
     out_a.set_output_enable(&mut module, true);
     out_b.set_output_enable(&mut module, true);
-    submodule.set_load_ok(&mut module);
+    submodule.set_load_ok(&module);
     submodule.set_running(&mut module, true);
 
     loop {
-        while !pit.0.is_elapsed() {}
-        pit.0.clear_elapsed();
+        while !pit.pit().is_elapsed() {}
+        pit.pit().clear_elapsed();
         led.toggle();
 
         let enabled = out_a.output_enable(&module);
@@ -41,17 +41,12 @@ mod app {
             },
         ) = board::new();
 
-        submodule.set_debug_enable(true);
-        submodule.set_wait_enable(true);
-        submodule.set_clock_select(hal::flexpwm::ClockSelect::Ipg);
-        submodule.set_prescaler(board::PWM_PRESCALER);
-        submodule.set_pair_operation(hal::flexpwm::PairOperation::Independent);
-        submodule.set_load_mode(hal::flexpwm::LoadMode::reload_full());
-        submodule.set_load_frequency(1);
-        submodule.set_initial_count(&module, i16::MIN);
-        submodule.set_value(hal::flexpwm::ValueRegister::Val1, i16::MIN + SWITCHING_FREQ);
-        submodule.set_interrupts(hal::flexpwm::Interrupts::COMPARE_VAL1);
-        submodule.set_load_ok(&mut module);
+        const CONFIG: hal::flexpwm::SubmoduleConfig = hal::flexpwm::SubmoduleConfig::new()
+            .prescaler(board::PWM_PRESCALER)
+            .period(i16::MIN, i16::MIN + SWITCHING_FREQ);
+        module.configure(&mut submodule, &CONFIG).unwrap();
+        module.enable_period_interrupt(&submodule);
+        submodule.set_load_ok(&module);
         submodule.set_running(&mut module, true);
 
         (Shared {}, Local { led, submodule })
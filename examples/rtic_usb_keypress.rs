@@ -8,12 +8,12 @@
 
 #[rtic::app(device = board, peripherals = false)]
 mod app {
-    use hal::usbd::{BusAdapter, EndpointMemory, EndpointState, Speed};
+    use hal::usbd::{BusAdapter, DeviceDescriptorConfig, EndpointMemory, EndpointState, Speed};
     use imxrt_hal as hal;
 
     use usb_device::{
         bus::UsbBusAllocator,
-        device::{UsbDevice, UsbDeviceBuilder, UsbDeviceState, UsbVidPid},
+        device::{UsbDevice, UsbDeviceState},
     };
     use usbd_hid::{
         descriptor::{KeyboardReport, SerializedDescriptor as _},
@@ -22,9 +22,10 @@ mod app {
 
     /// Change me if you want to play with a full-speed USB device.
     const SPEED: Speed = Speed::High;
-    /// Matches whatever is in imxrt-log.
-    const VID_PID: UsbVidPid = UsbVidPid(0x5824, 0x27dd);
-    const PRODUCT: &str = "imxrt-hal-example";
+    /// VID and PID match whatever is in imxrt-log.
+    const DEVICE: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, SPEED)
+        .product("imxrt-hal-example")
+        .device_class(usbd_serial::USB_CLASS_CDC);
     /// How frequently should we poll the logger?
     const LPUART_POLL_INTERVAL_MS: u32 = board::PIT_FREQUENCY / 1_000 * 100;
     /// Change me to change how log messages are serialized.
@@ -107,13 +108,7 @@ mod app {
         // Note that "4" correlates to a 1ms polling interval. Since this is a high speed
         // device, bInterval is computed differently.
         let class = HIDClass::new(bus, KeyboardReport::desc(), 4);
-        let device = UsbDeviceBuilder::new(bus, VID_PID)
-            .strings(&[usb_device::device::StringDescriptors::default().product(PRODUCT)])
-            .unwrap()
-            .device_class(usbd_serial::USB_CLASS_CDC)
-            .max_packet_size_0(64)
-            .unwrap()
-            .build();
+        let device = DEVICE.build(bus).unwrap();
 
         (
             Shared {},
@@ -28,20 +28,21 @@
 
 #[rtic::app(device = board, peripherals = false)]
 mod app {
-    use hal::usbd::{BusAdapter, EndpointMemory, EndpointState, Speed};
+    use hal::usbd::{BusAdapter, DeviceDescriptorConfig, EndpointMemory, EndpointState, Speed};
     use imxrt_hal as hal;
 
     use usb_device::{
         bus::UsbBusAllocator,
-        device::{UsbDevice, UsbDeviceBuilder, UsbDeviceState, UsbVidPid},
+        device::{UsbDevice, UsbDeviceState},
     };
     use usbd_serial::SerialPort;
 
     /// Change me if you want to play with a full-speed USB device.
     const SPEED: Speed = Speed::High;
-    /// Matches whatever is in imxrt-log.
-    const VID_PID: UsbVidPid = UsbVidPid(0x5824, 0x27dd);
-    const PRODUCT: &str = "imxrt-hal-example";
+    /// VID and PID match whatever is in imxrt-log.
+    const DEVICE: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, SPEED)
+        .product("imxrt-hal-example")
+        .device_class(usbd_serial::USB_CLASS_CDC);
     /// How frequently should we poll the logger?
     const LPUART_POLL_INTERVAL_MS: u32 = board::PIT_FREQUENCY / 1_000 * 100;
     /// Change me to change how log messages are serialized.
@@ -104,13 +105,7 @@ mod app {
 
         let bus = ctx.local.bus.insert(UsbBusAllocator::new(bus));
         let class = SerialPort::new(bus);
-        let device = UsbDeviceBuilder::new(bus, VID_PID)
-            .strings(&[usb_device::device::StringDescriptors::default().product(PRODUCT)])
-            .unwrap()
-            .device_class(usbd_serial::USB_CLASS_CDC)
-            .max_packet_size_0(64)
-            .unwrap()
-            .build();
+        let device = DEVICE.build(bus).unwrap();
 
         (
             Shared {},
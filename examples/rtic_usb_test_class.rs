@@ -15,6 +15,7 @@
 //!
 //! If you're playing the benchmark game and optimizing for bulk read / write
 //! throughput, configure your runtime to place as much as possible into TCM.
+//! To place only the endpoint memory, see `imxrt_hal::endpoint_memory!`.
 
 #![no_std]
 #![no_main]
@@ -0,0 +1,120 @@
+//! Driver constructors that enable their own clock gates.
+//!
+//! The common drivers don't know about the CCM, so they assume that their
+//! clock gates are already enabled. The constructors in this module take the
+//! CCM, and they enable the driver's clock gate before they touch any
+//! driver registers.
+//!
+//! These constructors only enable the clock gate. You're still responsible
+//! for selecting and dividing the peripheral's clock root.
+
+use crate::ccm::clock_gate;
+use crate::common::{lpi2c, lpspi, lpuart};
+use crate::iomuxc::{self, consts};
+use crate::ral::{self, ccm::CCM};
+
+impl<TX, RX, const N: u8> lpuart::Lpuart<lpuart::Pins<TX, RX>, N>
+where
+    TX: iomuxc::lpuart::Pin<Module = consts::Const<N>, Direction = iomuxc::lpuart::Tx>,
+    RX: iomuxc::lpuart::Pin<Module = consts::Const<N>, Direction = iomuxc::lpuart::Rx>,
+    ral::lpuart::Instance<N>: ral::Valid,
+{
+    /// Enable the LPUART clock gate, then create the LPUART driver.
+    ///
+    /// This is the same as [`new()`](Self::new), except that it enables the
+    /// clock gate for this LPUART instance.
+    pub fn new_with_ccm(
+        lpuart: ral::lpuart::Instance<N>,
+        pins: lpuart::Pins<TX, RX>,
+        ccm: &mut CCM,
+    ) -> Self {
+        clock_gate::lpuart::<N>().set(ccm, clock_gate::ON);
+        Self::new(lpuart, pins)
+    }
+}
+
+impl<SDO, SDI, SCK, PCS0, const N: u8> lpspi::Lpspi<lpspi::Pins<SDO, SDI, SCK, PCS0>, N>
+where
+    SDO: iomuxc::lpspi::Pin<Module = consts::Const<N>, Signal = iomuxc::lpspi::Sdo>,
+    SDI: iomuxc::lpspi::Pin<Module = consts::Const<N>, Signal = iomuxc::lpspi::Sdi>,
+    SCK: iomuxc::lpspi::Pin<Module = consts::Const<N>, Signal = iomuxc::lpspi::Sck>,
+    PCS0: iomuxc::lpspi::Pin<Module = consts::Const<N>, Signal = iomuxc::lpspi::Pcs0>,
+    ral::lpspi::Instance<N>: ral::Valid,
+{
+    /// Enable the LPSPI clock gate, then create the LPSPI driver.
+    ///
+    /// This is the same as [`new()`](Self::new), except that it enables the
+    /// clock gate for this LPSPI instance.
+    pub fn new_with_ccm(
+        lpspi: ral::lpspi::Instance<N>,
+        pins: lpspi::Pins<SDO, SDI, SCK, PCS0>,
+        ccm: &mut CCM,
+    ) -> Self {
+        clock_gate::lpspi::<N>().set(ccm, clock_gate::ON);
+        Self::new(lpspi, pins)
+    }
+}
+
+impl<SCL, SDA, const N: u8> lpi2c::Lpi2c<lpi2c::Pins<SCL, SDA>, N>
+where
+    SCL: iomuxc::lpi2c::Pin<Signal = iomuxc::lpi2c::Scl, Module = consts::Const<N>>,
+    SDA: iomuxc::lpi2c::Pin<Signal = iomuxc::lpi2c::Sda, Module = consts::Const<N>>,
+    ral::lpi2c::Instance<N>: ral::Valid,
+{
+    /// Enable the LPI2C clock gate, then create the LPI2C driver.
+    ///
+    /// This is the same as [`new()`](Self::new), except that it enables the
+    /// clock gate for this LPI2C instance.
+    pub fn new_with_ccm(
+        lpi2c: ral::lpi2c::Instance<N>,
+        pins: lpi2c::Pins<SCL, SDA>,
+        timings: &lpi2c::Timing,
+        ccm: &mut CCM,
+    ) -> Self {
+        clock_gate::lpi2c::<N>().set(ccm, clock_gate::ON);
+        Self::new(lpi2c, pins, timings)
+    }
+}
+
+impl<const N: u8> lpuart::Lpuart<(), N>
+where
+    ral::lpuart::Instance<N>: ral::Valid,
+{
+    /// Enable the LPUART clock gate, then create the LPUART driver without pins.
+    ///
+    /// See [`without_pins()`](Self::without_pins) for more information.
+    pub fn without_pins_with_ccm(lpuart: ral::lpuart::Instance<N>, ccm: &mut CCM) -> Self {
+        clock_gate::lpuart::<N>().set(ccm, clock_gate::ON);
+        Self::without_pins(lpuart)
+    }
+}
+
+impl<const N: u8> lpspi::Lpspi<(), N>
+where
+    ral::lpspi::Instance<N>: ral::Valid,
+{
+    /// Enable the LPSPI clock gate, then create the LPSPI driver without pins.
+    ///
+    /// See [`without_pins()`](Self::without_pins) for more information.
+    pub fn without_pins_with_ccm(lpspi: ral::lpspi::Instance<N>, ccm: &mut CCM) -> Self {
+        clock_gate::lpspi::<N>().set(ccm, clock_gate::ON);
+        Self::without_pins(lpspi)
+    }
+}
+
+impl<const N: u8> lpi2c::Lpi2c<(), N>
+where
+    ral::lpi2c::Instance<N>: ral::Valid,
+{
+    /// Enable the LPI2C clock gate, then create the LPI2C driver without pins.
+    ///
+    /// See [`without_pins()`](Self::without_pins) for more information.
+    pub fn without_pins_with_ccm(
+        lpi2c: ral::lpi2c::Instance<N>,
+        timings: &lpi2c::Timing,
+        ccm: &mut CCM,
+    ) -> Self {
+        clock_gate::lpi2c::<N>().set(ccm, clock_gate::ON);
+        Self::without_pins(lpi2c, timings)
+    }
+}
@@ -67,6 +67,8 @@ mod mappings {
     pub(super) const LPSPI_DMA_TX_MAPPING: [u32; 4] = [14, 78, 16, 80];
 
     pub(super) const ADC_DMA_RX_MAPPING: [u32; 2] = [24, 88];
+
+    pub(super) const ADC_ETC_DMA_RX_MAPPING: u32 = 115;
 }
 #[cfg(family = "imxrt11xx")]
 mod mappings {
@@ -117,29 +119,105 @@ impl<P, const N: u8> lpuart::Lpuart<P, N> {
     /// Use a DMA channel to write data to the UART peripheral
     ///
     /// Completes when all data in `buffer` has been written to the UART
-    /// peripheral.
+    /// peripheral. To write a buffer that's longer than a single DMA transfer,
+    /// use [`chunked_write`](crate::dma::chunked_write).
+    ///
+    /// When this completes, the DMA has moved the last byte into the transmit
+    /// FIFO, but the transmitter may still be sending it, and any other bytes
+    /// in the FIFO. The line isn't idle yet. If you need an idle line, like
+    /// before you release an RS-485 driver enable, reconfigure a pin, or disable
+    /// the transmitter, use [`dma_write_flush`](Self::dma_write_flush).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is longer than
+    /// [`MAX_TRANSFER_ELEMENTS`](crate::dma::MAX_TRANSFER_ELEMENTS).
     pub fn dma_write<'a>(
         &'a mut self,
         channel: &'a mut Channel,
         buffer: &'a [u8],
     ) -> peripheral::Write<'a, Self, u8> {
+        assert!(buffer.len() <= crate::dma::MAX_TRANSFER_ELEMENTS);
+        crate::dma::reset_tcd(channel);
         peripheral::write(channel, buffer, self)
     }
 
+    /// Use a DMA channel to write data to the UART peripheral, then wait
+    /// for the transmitter to go idle.
+    ///
+    /// This is [`dma_write`](Self::dma_write), followed by a wait for transmit
+    /// complete. When this completes, the last stop bit left the shift register,
+    /// and the line is idle. See [`is_transmit_complete`](lpuart::Lpuart::is_transmit_complete).
+    ///
+    /// The wait polls the transmit complete flag. The future wakes itself while it
+    /// waits, so your executor keeps polling it until the transmitter is idle.
+    /// The wait lasts, at most, the time to send the transmit FIFO.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is longer than
+    /// [`MAX_TRANSFER_ELEMENTS`](crate::dma::MAX_TRANSFER_ELEMENTS).
+    pub async fn dma_write_flush(
+        &mut self,
+        channel: &mut Channel,
+        buffer: &[u8],
+    ) -> crate::dma::Result<()> {
+        self.dma_write(channel, buffer).await?;
+        core::future::poll_fn(|cx| {
+            if self.is_transmit_complete() {
+                core::task::Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                core::task::Poll::Pending
+            }
+        })
+        .await;
+        Ok(())
+    }
+
     /// Use a DMA channel to read data from the UART peripheral
     ///
-    /// Completes when `buffer` is filled.
+    /// Completes when `buffer` is filled. To read a buffer that's longer
+    /// than a single DMA transfer, use [`chunked_read`](crate::dma::chunked_read).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is longer than
+    /// [`MAX_TRANSFER_ELEMENTS`](crate::dma::MAX_TRANSFER_ELEMENTS).
     pub fn dma_read<'a>(
         &'a mut self,
         channel: &'a mut Channel,
         buffer: &'a mut [u8],
     ) -> peripheral::Read<'a, Self, u8> {
+        assert!(buffer.len() <= crate::dma::MAX_TRANSFER_ELEMENTS);
+        crate::dma::reset_tcd(channel);
         peripheral::read(channel, self, buffer)
     }
+
+    /// Use a DMA channel to continuously receive data into a circular buffer.
+    ///
+    /// The transfer runs until you drop the returned reader. Use
+    /// [`RingReader::read`](crate::dma::RingReader::read) to drain the data
+    /// received since your last read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length isn't a power of two, if the buffer isn't
+    /// aligned to its length, or if the buffer is larger than
+    /// [`MAX_RING_ELEMENTS`](crate::dma::MAX_RING_ELEMENTS).
+    pub fn dma_receive_ring<'a>(
+        &'a mut self,
+        channel: &'a mut Channel,
+        buffer: &'a mut [u8],
+    ) -> crate::dma::RingReader<'a, Self, u8> {
+        crate::dma::RingReader::new(channel, self, buffer)
+    }
 }
 
 // LPSPI
+use crate::common::dma::channel;
 use crate::lpspi;
+use core::{future::Future, task::Poll};
 
 unsafe impl<P, const N: u8> peripheral::Source<u32> for lpspi::Lpspi<P, N> {
     fn source_signal(&self) -> u32 {
@@ -176,6 +254,9 @@ unsafe impl<P, const N: u8> peripheral::Bidirectional<u32> for lpspi::Lpspi<P, N
 impl<P, const N: u8> lpspi::Lpspi<P, N> {
     /// Use a DMA channel to write data to the LPSPI peripheral.
     ///
+    /// The LPSPI discards the data that it receives during the write, so
+    /// you don't need a receive buffer.
+    ///
     /// The future completes when all data in `buffer` has been written to the
     /// peripheral. This call may block until space is available in the
     /// command queue. An error indicates that there was an issue preparing the
@@ -192,6 +273,7 @@ impl<P, const N: u8> lpspi::Lpspi<P, N> {
         transaction.receive_data_mask = true;
         self.wait_for_transmit_fifo_space()?;
         self.enqueue_transaction(&transaction);
+        crate::dma::reset_tcd(channel);
         Ok(peripheral::write(channel, buffer, self))
     }
 
@@ -212,9 +294,99 @@ impl<P, const N: u8> lpspi::Lpspi<P, N> {
         transaction.transmit_data_mask = true;
         self.wait_for_transmit_fifo_space()?;
         self.enqueue_transaction(&transaction);
+        crate::dma::reset_tcd(channel);
         Ok(peripheral::read(channel, self, buffer))
     }
 
+    /// Use DMA channels to read data from the LPSPI peripheral while sending
+    /// a constant.
+    ///
+    /// The `tx` channel sends `fill` for every word that the `rx` channel
+    /// receives into `buffer`, so you don't need a transmit buffer. Use this
+    /// for devices that expect a specific word, like `0x00` or `0xFF`, while
+    /// they respond. To receive without driving the output, use
+    /// [`dma_read`](Self::dma_read).
+    ///
+    /// The future completes when `buffer` is filled. This call may block until
+    /// space is available in the command queue. An error indicates that there was
+    /// an issue preparing the transaction, or there was an issue waiting for space
+    /// in the command queue.
+    pub fn dma_read_sending<'a>(
+        &'a mut self,
+        rx: &'a mut Channel,
+        tx: &'a mut Channel,
+        buffer: &'a mut [u32],
+        fill: u32,
+    ) -> Result<impl Future<Output = crate::dma::Result<()>> + 'a, lpspi::LpspiError> {
+        let mut transaction = lpspi::Transaction::new_u32s(buffer)?;
+        transaction.bit_order = self.bit_order();
+
+        self.wait_for_transmit_fifo_space()?;
+        self.enqueue_transaction(&transaction);
+        Ok(async move {
+            let fill = fill;
+            let len = buffer.len() as u16;
+            let (rx_signal, tx_signal) = (
+                peripheral::Source::source_signal(self),
+                peripheral::Destination::destination_signal(self),
+            );
+
+            for (channel, signal) in [(&mut *rx, rx_signal), (&mut *tx, tx_signal)] {
+                channel.disable();
+                crate::dma::reset_tcd(channel);
+                channel.set_disable_on_completion(true);
+                channel
+                    .set_channel_configuration(crate::dma::channel::Configuration::enable(signal));
+            }
+            // Safety: the receive transfer fills the buffer, and the future
+            // borrows the buffer for the duration of the transfer. The transmit
+            // transfer reads the same fill word for every iteration. The fill
+            // word lives in the future, which is pinned while the transfer runs.
+            unsafe {
+                channel::set_source_hardware(rx, self.rdr().cast::<u32>());
+                channel::set_destination_linear_buffer(rx, buffer);
+                channel::set_source_hardware(tx, &fill);
+                channel::set_destination_hardware(tx, self.tdr().cast::<u32>());
+                for channel in [&mut *rx, &mut *tx] {
+                    channel.set_minor_loop_bytes(core::mem::size_of::<u32>() as u32);
+                    channel.set_transfer_iterations(len);
+                }
+            }
+
+            // Safety: transfers are described above.
+            let mut rx_transfer = core::pin::pin!(unsafe { crate::dma::Transfer::new(rx) });
+            let mut tx_transfer = core::pin::pin!(unsafe { crate::dma::Transfer::new(tx) });
+
+            struct DisableOnDrop<'a, P, const N: u8>(&'a mut lpspi::Lpspi<P, N>);
+            impl<P, const N: u8> Drop for DisableOnDrop<'_, P, N> {
+                fn drop(&mut self) {
+                    self.0.disable_dma_transmit();
+                    self.0.disable_dma_receive();
+                }
+            }
+
+            self.enable_dma_receive();
+            self.enable_dma_transmit();
+            let _guard = DisableOnDrop(self);
+
+            let (mut rx_done, mut tx_done) = (false, false);
+            core::future::poll_fn(|cx| {
+                if !rx_done {
+                    rx_done = rx_transfer.as_mut().poll(cx)?.is_ready();
+                }
+                if !tx_done {
+                    tx_done = tx_transfer.as_mut().poll(cx)?.is_ready();
+                }
+                if rx_done && tx_done {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await
+        })
+    }
+
     /// Use a DMA channel to simultaneously read and write from a buffer
     /// and the LPSPI peripheral.
     ///
@@ -233,6 +405,8 @@ impl<P, const N: u8> lpspi::Lpspi<P, N> {
 
         self.wait_for_transmit_fifo_space()?;
         self.enqueue_transaction(&transaction);
+        crate::dma::reset_tcd(rx);
+        crate::dma::reset_tcd(tx);
         Ok(peripheral::full_duplex(rx, tx, self, buffer))
     }
 }
@@ -241,14 +415,19 @@ impl<P, const N: u8> lpspi::Lpspi<P, N> {
 #[cfg(family = "imxrt10xx")]
 use crate::adc;
 
+#[cfg(family = "imxrt10xx")]
+const fn adc_dma_signal(n: u8) -> u32 {
+    ADC_DMA_RX_MAPPING[if n == ral::SOLE_INSTANCE {
+        n as usize
+    } else {
+        n as usize - 1
+    }]
+}
+
 #[cfg(family = "imxrt10xx")]
 unsafe impl<P, const N: u8> peripheral::Source<u16> for adc::DmaSource<P, N> {
     fn source_signal(&self) -> u32 {
-        ADC_DMA_RX_MAPPING[if N == ral::SOLE_INSTANCE {
-            N as usize
-        } else {
-            N as usize - 1
-        }]
+        adc_dma_signal(N)
     }
     fn source_address(&self) -> *const u16 {
         self.r0().cast()
@@ -260,3 +439,148 @@ unsafe impl<P, const N: u8> peripheral::Source<u16> for adc::DmaSource<P, N> {
         self.disable_dma();
     }
 }
+
+#[cfg(family = "imxrt10xx")]
+impl<P, const N: u8> adc::DmaSource<P, N> {
+    /// Use a DMA channel to capture `buffer.len()` samples, then decimate them
+    /// into one higher resolution result.
+    ///
+    /// The DMA channel fills `buffer` with consecutive conversions, then
+    /// this returns [`oversample(buffer)`](adc::oversample). A buffer of `4^k`
+    /// samples adds `k` bits of resolution. The buffer keeps the raw samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is empty.
+    ///
+    /// # Example
+    ///
+    /// Measure a slowly-changing signal with 14 bits of resolution from a
+    /// 12-bit conversion.
+    ///
+    /// ```no_run
+    /// use imxrt_hal as hal;
+    /// use imxrt_ral as ral;
+    /// use hal::adc;
+    ///
+    /// # async fn f() -> Result<(), hal::dma::Error> {
+    /// let inst = unsafe { ral::adc::ADC1::instance() };
+    /// let mut adc1 = adc::Adc::new(inst, adc::ClockSelect::ADACK, adc::ClockDivision::Div2);
+    /// adc1.set_resolution(adc::ResolutionBits::Res12);
+    /// let mut channel = // DMA channel 7...
+    ///     # unsafe { hal::dma::DMA.channel(7) };
+    ///
+    /// let mut source = adc::DmaSource::without_pin(adc1, 3);
+    /// let mut samples = [0u16; 16];
+    /// let reading: u16 = source.dma_oversample(&mut channel, &mut samples).await?;
+    /// assert!(reading < 1 << 14);
+    /// # Ok(()) }
+    /// ```
+    pub async fn dma_oversample(
+        &mut self,
+        channel: &mut Channel,
+        buffer: &mut [u16],
+    ) -> Result<u16, crate::dma::Error> {
+        assert!(!buffer.is_empty());
+        crate::dma::chunked_read(channel, self, buffer).await?;
+        Ok(adc::oversample(buffer))
+    }
+}
+
+#[cfg(family = "imxrt10xx")]
+impl<const N: u8> adc::DmaSequence<N> {
+    /// Use a DMA channel to capture one result from every channel in the sequence.
+    ///
+    /// `buffer[n]` receives the result of chain segment `n`. The future
+    /// resolves after the ADC_ETC finishes the next chain. Call this once per
+    /// snapshot, such as once per control cycle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length doesn't match the sequence length.
+    pub async fn dma_read_sequence(
+        &mut self,
+        channel: &mut Channel,
+        buffer: &mut [u16],
+    ) -> Result<(), crate::dma::Error> {
+        assert_eq!(buffer.len(), self.len());
+        let bytes = core::mem::size_of_val(buffer);
+
+        channel.disable();
+        crate::dma::reset_tcd(channel);
+        channel.set_disable_on_completion(true);
+        channel.set_channel_configuration(crate::dma::channel::Configuration::enable(
+            ADC_ETC_DMA_RX_MAPPING,
+        ));
+
+        // Safety: the trigger's result registers are adjacent, 32-bit registers,
+        // and each holds two results, one per half-word. The ADC_ETC requests
+        // one transfer at the end of the chain, so one minor loop reads every
+        // result, in segment order. The sequence length bounds the source
+        // addresses. The buffer length matches the sequence length, and the
+        // future borrows the buffer for the duration of the transfer.
+        unsafe {
+            channel.set_source_address(self.results().cast::<u16>());
+            channel.set_source_offset(core::mem::size_of::<u16>() as i16);
+            channel.set_source_attributes::<u16>(0);
+            channel.set_source_last_address_adjustment(-(bytes as i32));
+
+            channel.set_destination_address(buffer.as_mut_ptr());
+            channel.set_destination_offset(core::mem::size_of::<u16>() as i16);
+            channel.set_destination_attributes::<u16>(0);
+            channel.set_destination_last_address_adjustment(-(bytes as i32));
+
+            channel.set_minor_loop_bytes(bytes as u32);
+            channel.set_transfer_iterations(1);
+        }
+
+        struct DisableOnDrop<'a, const N: u8>(&'a mut adc::DmaSequence<N>);
+        impl<const N: u8> Drop for DisableOnDrop<'_, N> {
+            fn drop(&mut self) {
+                self.0.disable_dma();
+            }
+        }
+
+        self.enable_dma();
+        let _guard = DisableOnDrop(self);
+        // Safety: transfer is described above.
+        unsafe { crate::dma::Transfer::new(channel) }.await
+    }
+}
+
+// DAC
+#[cfg(family = "imxrt11xx")]
+use crate::dac;
+
+#[cfg(family = "imxrt11xx")]
+unsafe impl peripheral::Destination<u16> for dac::DmaDestination {
+    fn destination_signal(&self) -> u32 {
+        self.request()
+    }
+    fn destination_address(&self) -> *const u16 {
+        self.data().cast()
+    }
+    fn enable_destination(&mut self) {
+        self.dac_mut().set_dma_enable(true);
+    }
+    fn disable_destination(&mut self) {
+        self.dac_mut().set_dma_enable(false);
+    }
+}
+
+#[cfg(family = "imxrt11xx")]
+impl dac::DmaDestination {
+    /// Use a DMA channel to write samples to the DAC.
+    ///
+    /// Completes when all samples in `buffer` are in the DAC's FIFO. The
+    /// DAC's trigger sets the rate at which samples leave the FIFO. `buffer`
+    /// may be longer than a single DMA transfer; see
+    /// [`chunked_write`](crate::dma::chunked_write) for more information.
+    pub async fn dma_write(
+        &mut self,
+        channel: &mut Channel,
+        buffer: &[u16],
+    ) -> crate::dma::Result<()> {
+        crate::dma::chunked_write(channel, buffer, self).await
+    }
+}
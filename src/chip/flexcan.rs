@@ -0,0 +1,683 @@
+//! Flexible controller area network.
+//!
+//! [`Can`] is a polled, classic CAN driver for the FlexCAN peripheral. It
+//! sends and receives [`Frame`]s with standard or extended IDs, up to eight
+//! data bytes. It doesn't support CAN FD.
+//!
+//! Use [`Timing::from_bitrate`] to compute bit timing from the FlexCAN clock
+//! frequency. You're responsible for configuring the FlexCAN clock root, the
+//! clock gates, and the pads.
+//!
+//! # Mailboxes
+//!
+//! The driver uses 16 message buffers, or mailboxes. It receives standard
+//! frames in four mailboxes, extended frames in another four, and transmits
+//! from the remaining eight. The receive mailboxes accept all IDs. When more
+//! than one frame is waiting, [`receive`](Can::receive) returns the oldest
+//! frame. Frames waiting to transmit go out in CAN priority order, lowest ID
+//! first.
+//!
+//! # Errors
+//!
+//! Check [`error_status`](Can::error_status) for the error state, the error
+//! counters, and the bus errors observed since the last call. The FlexCAN
+//! recovers from bus off automatically, after it observes 128 occurrences
+//! of 11 recessive bits.
+//!
+//! # Example
+//!
+//! Send a frame at 500 kbit/s, given a 24MHz FlexCAN clock. Then wait
+//! for a response.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::flexcan::{Can, Frame, StandardId, Timing};
+//!
+//! let timing = Timing::from_bitrate(24_000_000, 500_000).unwrap();
+//! let mut can = Can::new(unsafe { ral::can::CAN1::instance() }, &timing);
+//!
+//! let frame = Frame::new(StandardId::new(0x123).unwrap(), &[0xDE, 0xAD]).unwrap();
+//! nb::block!(can.transmit(&frame)).unwrap();
+//!
+//! let response = loop {
+//!     if let Some(frame) = can.receive() {
+//!         break frame;
+//!     }
+//! };
+//! ```
+
+use crate::ral;
+
+pub use embedded_can::{ExtendedId, Id, StandardId};
+
+/// Offset of the first message buffer from the FlexCAN base address.
+const MB_OFFSET: usize = 0x80;
+/// Total number of message buffers used by the driver.
+const MAILBOXES: usize = 16;
+/// Receive mailboxes for standard frames.
+const RX_STANDARD: core::ops::Range<usize> = 0..4;
+/// Receive mailboxes for extended frames.
+const RX_EXTENDED: core::ops::Range<usize> = 4..8;
+/// Transmit mailboxes.
+const TX: core::ops::Range<usize> = 8..16;
+/// IFLAG1 bits for all receive mailboxes.
+const RX_FLAGS: u32 = 0xFF;
+
+const CODE_RX_EMPTY: u32 = 0b0100;
+const CODE_RX_OVERRUN: u32 = 0b0110;
+const CODE_TX_INACTIVE: u32 = 0b1000;
+const CODE_TX_DATA: u32 = 0b1100;
+
+const CS_CODE_SHIFT: u32 = 24;
+const CS_SRR: u32 = 1 << 22;
+const CS_IDE: u32 = 1 << 21;
+const CS_RTR: u32 = 1 << 20;
+const CS_DLC_SHIFT: u32 = 16;
+const ID_STD_SHIFT: u32 = 18;
+
+/// CAN bit timing.
+///
+/// A bit is divided into time quanta. Each quantum is `prescaler` FlexCAN
+/// clock cycles. A bit has one synchronization quantum, then the propagation
+/// segment and phase segment 1, which end at the sample point. Phase segment
+/// 2 follows the sample point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    prescaler: u16,
+    propagation: u8,
+    phase1: u8,
+    phase2: u8,
+    jump_width: u8,
+}
+
+impl Timing {
+    /// Create a bit timing from its segments.
+    ///
+    /// `prescaler` is between 1 and 256. The propagation segment and phase
+    /// segment 1 are between 1 and 8 quanta. Phase segment 2 is between 2
+    /// and 8 quanta. The resynchronization jump width is between 1 and 4
+    /// quanta, and no more than phase segment 2. Returns `None` if any value
+    /// is out of range.
+    pub const fn new(
+        prescaler: u16,
+        propagation: u8,
+        phase1: u8,
+        phase2: u8,
+        jump_width: u8,
+    ) -> Option<Self> {
+        if prescaler < 1
+            || prescaler > 256
+            || propagation < 1
+            || propagation > 8
+            || phase1 < 1
+            || phase1 > 8
+            || phase2 < 2
+            || phase2 > 8
+            || jump_width < 1
+            || jump_width > 4
+            || jump_width > phase2
+        {
+            return None;
+        }
+        Some(Self {
+            prescaler,
+            propagation,
+            phase1,
+            phase2,
+            jump_width,
+        })
+    }
+
+    /// Compute a bit timing for `bitrate`, given the FlexCAN clock frequency.
+    ///
+    /// The timing uses the most quanta that evenly divide the clock, and
+    /// places the sample point near 87.5% of the bit. Returns `None` if no
+    /// timing produces the exact bitrate.
+    pub const fn from_bitrate(clock_hz: u32, bitrate: u32) -> Option<Self> {
+        if bitrate == 0 {
+            return None;
+        }
+        let mut quanta: u8 = 25;
+        while quanta >= 8 {
+            let quanta_hz = bitrate as u64 * quanta as u64;
+            let prescaler = clock_hz as u64 / quanta_hz;
+            if prescaler * quanta_hz == clock_hz as u64 && prescaler <= 256 {
+                let prescaler = prescaler as u16;
+                let sample = (quanta * 7 + 4) / 8;
+                let phase2 = if quanta - sample < 2 {
+                    2
+                } else {
+                    quanta - sample
+                };
+                let rest = quanta - 1 - phase2;
+                let phase1 = if rest > 8 + phase2 { rest - 8 } else { phase2 };
+                let propagation = rest - phase1;
+                let jump_width = if phase2 < 4 { phase2 } else { 4 };
+                if let Some(timing) = Self::new(prescaler, propagation, phase1, phase2, jump_width)
+                {
+                    return Some(timing);
+                }
+            }
+            quanta -= 1;
+        }
+        None
+    }
+
+    /// Returns the number of time quanta in a bit.
+    pub const fn quanta(&self) -> u32 {
+        1 + self.propagation as u32 + self.phase1 as u32 + self.phase2 as u32
+    }
+
+    /// Returns the bitrate for this timing, given the FlexCAN clock frequency.
+    pub const fn bitrate(&self, clock_hz: u32) -> u32 {
+        clock_hz / (self.prescaler as u32 * self.quanta())
+    }
+}
+
+/// A classic CAN frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    id: Id,
+    remote: bool,
+    dlc: u8,
+    data: [u8; 8],
+}
+
+impl Frame {
+    /// Create a data frame.
+    ///
+    /// Returns `None` if `data` is longer than eight bytes.
+    pub fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        if data.len() > 8 {
+            return None;
+        }
+        let mut frame = Self {
+            id: id.into(),
+            remote: false,
+            dlc: data.len() as u8,
+            data: [0; 8],
+        };
+        frame.data[..data.len()].copy_from_slice(data);
+        Some(frame)
+    }
+
+    /// Create a remote frame, requesting `dlc` bytes.
+    ///
+    /// Returns `None` if `dlc` is greater than eight.
+    pub fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        if dlc > 8 {
+            return None;
+        }
+        Some(Self {
+            id: id.into(),
+            remote: true,
+            dlc: dlc as u8,
+            data: [0; 8],
+        })
+    }
+
+    /// Returns the frame ID.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Returns `true` if the frame has an extended ID.
+    pub fn is_extended(&self) -> bool {
+        matches!(self.id, Id::Extended(_))
+    }
+
+    /// Returns `true` if this is a remote frame.
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+
+    /// Returns the data length code.
+    pub fn dlc(&self) -> usize {
+        self.dlc as usize
+    }
+
+    /// Returns the frame data.
+    ///
+    /// The data is empty for remote frames.
+    pub fn data(&self) -> &[u8] {
+        if self.remote {
+            &[]
+        } else {
+            &self.data[..self.dlc as usize]
+        }
+    }
+
+    /// Encode the frame into the mailbox CS, ID, and data words.
+    ///
+    /// The CS word doesn't include a code.
+    fn encode(&self) -> (u32, u32, [u32; 2]) {
+        let mut cs = (self.dlc as u32) << CS_DLC_SHIFT;
+        if self.remote {
+            cs |= CS_RTR;
+        }
+        let id = match self.id {
+            Id::Standard(id) => (id.as_raw() as u32) << ID_STD_SHIFT,
+            Id::Extended(id) => {
+                cs |= CS_SRR | CS_IDE;
+                id.as_raw()
+            }
+        };
+        let data = [
+            u32::from_be_bytes([self.data[0], self.data[1], self.data[2], self.data[3]]),
+            u32::from_be_bytes([self.data[4], self.data[5], self.data[6], self.data[7]]),
+        ];
+        (cs, id, data)
+    }
+
+    /// Decode a frame from the mailbox CS, ID, and data words.
+    fn decode(cs: u32, id: u32, data: [u32; 2]) -> Self {
+        let id = if cs & CS_IDE != 0 {
+            Id::Extended(ExtendedId::new(id & ExtendedId::MAX.as_raw()).unwrap())
+        } else {
+            Id::Standard(
+                StandardId::new((id >> ID_STD_SHIFT) as u16 & StandardId::MAX.as_raw()).unwrap(),
+            )
+        };
+        let [a, b, c, d] = data[0].to_be_bytes();
+        let [e, f, g, h] = data[1].to_be_bytes();
+        Self {
+            id,
+            remote: cs & CS_RTR != 0,
+            dlc: (((cs >> CS_DLC_SHIFT) & 0xF) as u8).min(8),
+            data: [a, b, c, d, e, f, g, h],
+        }
+    }
+}
+
+impl embedded_can::Frame for Frame {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        Frame::new(id, data)
+    }
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        Frame::new_remote(id, dlc)
+    }
+    fn is_extended(&self) -> bool {
+        Frame::is_extended(self)
+    }
+    fn is_remote_frame(&self) -> bool {
+        Frame::is_remote(self)
+    }
+    fn id(&self) -> Id {
+        Frame::id(self)
+    }
+    fn dlc(&self) -> usize {
+        Frame::dlc(self)
+    }
+    fn data(&self) -> &[u8] {
+        Frame::data(self)
+    }
+}
+
+/// The fault confinement state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorState {
+    /// The node takes part in bus communication, and signals errors with
+    /// active error frames.
+    Active,
+    /// An error counter exceeded 127. The node still communicates, but
+    /// signals errors with passive error frames.
+    Passive,
+    /// The transmit error counter exceeded 255. The node doesn't take part
+    /// in bus communication.
+    BusOff,
+}
+
+bitflags::bitflags! {
+    /// Bus errors and error events.
+    pub struct ErrorFlags : u32 {
+        /// An error counter reached 96.
+        const WARNING = (1 << 16) | (1 << 17);
+        /// The node entered bus off.
+        const BUS_OFF = 1 << 2;
+        /// A stuffing error.
+        const STUFF = 1 << 10;
+        /// A form error.
+        const FORM = 1 << 11;
+        /// A CRC error.
+        const CRC = 1 << 12;
+        /// A transmitted frame wasn't acknowledged.
+        const ACK = 1 << 13;
+        /// The node transmitted a dominant bit, but read a recessive bit.
+        const BIT0 = 1 << 14;
+        /// The node transmitted a recessive bit, but read a dominant bit.
+        const BIT1 = 1 << 15;
+    }
+}
+
+/// Error state, counters, and bus errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorStatus {
+    /// The fault confinement state.
+    pub state: ErrorState,
+    /// The bus errors and error events observed since the last status.
+    pub flags: ErrorFlags,
+    /// The transmit error counter.
+    pub transmit_errors: u8,
+    /// The receive error counter.
+    pub receive_errors: u8,
+}
+
+/// A FlexCAN error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The node is bus off, and can't transmit.
+    BusOff,
+    /// A receive mailbox lost a frame before it was read.
+    Overrun,
+}
+
+impl embedded_can::Error for Error {
+    fn kind(&self) -> embedded_can::ErrorKind {
+        match self {
+            Error::BusOff => embedded_can::ErrorKind::Other,
+            Error::Overrun => embedded_can::ErrorKind::Overrun,
+        }
+    }
+}
+
+/// ESR1 interrupt flags that are cleared by writing one.
+const ESR1_W1C: u32 = (1 << 1) | (1 << 2) | (1 << 16) | (1 << 17);
+
+/// A FlexCAN peripheral.
+pub struct Can<const N: u8> {
+    can: ral::can::Instance<N>,
+    errors: ErrorFlags,
+    overrun: bool,
+}
+
+impl<const N: u8> Can<N> {
+    /// Take ownership of a FlexCAN peripheral, and join the bus.
+    ///
+    /// This resets the FlexCAN, configures the mailboxes, and sets the bit
+    /// timing. Make sure that the FlexCAN clocks are enabled.
+    pub fn new(can: ral::can::Instance<N>, timing: &Timing) -> Self {
+        ral::modify_reg!(ral::can, can, MCR, MDIS: 0);
+        while ral::read_reg!(ral::can, can, MCR, LPMACK == 1) {}
+        ral::modify_reg!(ral::can, can, MCR, SOFTRST: 1);
+        while ral::read_reg!(ral::can, can, MCR, SOFTRST == 1) {}
+
+        let mut this = Self {
+            can,
+            errors: ErrorFlags::empty(),
+            overrun: false,
+        };
+        this.enter_freeze();
+        ral::modify_reg!(ral::can, this.can, MCR,
+            MAXMB: MAILBOXES as u32 - 1,
+            IRMQ: 1,
+            SRXDIS: 1,
+            WRNEN: 1,
+            RFEN: 0,
+            AEN: 0,
+            LPRIOEN: 0
+        );
+        ral::write_reg!(ral::can, this.can, CTRL2, 0);
+        this.write_timing(timing);
+        for mailbox in 0..MAILBOXES {
+            this.mb_write(mailbox, 1, 0);
+            this.mb_write(mailbox, 2, 0);
+            this.mb_write(mailbox, 3, 0);
+            ral::write_reg!(ral::can, this.can, RXIMR[mailbox], 0);
+        }
+        for mailbox in RX_STANDARD {
+            this.mb_write(mailbox, 0, CODE_RX_EMPTY << CS_CODE_SHIFT);
+        }
+        for mailbox in RX_EXTENDED {
+            this.mb_write(mailbox, 0, CODE_RX_EMPTY << CS_CODE_SHIFT | CS_IDE);
+        }
+        for mailbox in TX {
+            this.mb_write(mailbox, 0, CODE_TX_INACTIVE << CS_CODE_SHIFT);
+        }
+        ral::write_reg!(ral::can, this.can, IMASK1, 0);
+        ral::write_reg!(ral::can, this.can, IFLAG1, u32::MAX);
+        ral::write_reg!(ral::can, this.can, ESR1, ESR1_W1C);
+        this.exit_freeze();
+        this
+    }
+
+    /// Release the FlexCAN instance.
+    ///
+    /// This doesn't change the FlexCAN.
+    pub fn release(self) -> ral::can::Instance<N> {
+        self.can
+    }
+
+    /// Change the bit timing.
+    ///
+    /// The FlexCAN briefly leaves the bus while it changes the timing.
+    pub fn set_timing(&mut self, timing: &Timing) {
+        self.enter_freeze();
+        self.write_timing(timing);
+        self.exit_freeze();
+    }
+
+    /// Queue a frame for transmit.
+    ///
+    /// Returns [`WouldBlock`](nb::Error::WouldBlock) if all transmit
+    /// mailboxes are busy. Returns [`Error::BusOff`] if the node is
+    /// bus off.
+    pub fn transmit(&mut self, frame: &Frame) -> nb::Result<(), Error> {
+        if self.read_esr1() & (0b10 << 4) != 0 {
+            return Err(nb::Error::Other(Error::BusOff));
+        }
+        let mailbox = TX
+            .clone()
+            .find(|&mailbox| self.mb_read(mailbox, 0) >> CS_CODE_SHIFT & 0xF == CODE_TX_INACTIVE)
+            .ok_or(nb::Error::WouldBlock)?;
+        ral::write_reg!(ral::can, self.can, IFLAG1, 1 << mailbox);
+        let (cs, id, data) = frame.encode();
+        self.mb_write(mailbox, 1, id);
+        self.mb_write(mailbox, 2, data[0]);
+        self.mb_write(mailbox, 3, data[1]);
+        self.mb_write(mailbox, 0, CODE_TX_DATA << CS_CODE_SHIFT | cs);
+        Ok(())
+    }
+
+    /// Returns `true` if no frames are waiting to transmit.
+    pub fn is_transmit_idle(&self) -> bool {
+        TX.clone()
+            .all(|mailbox| self.mb_read(mailbox, 0) >> CS_CODE_SHIFT & 0xF == CODE_TX_INACTIVE)
+    }
+
+    /// Receive the oldest waiting frame.
+    ///
+    /// Returns `None` if there's no frame. If a mailbox lost a frame before
+    /// it was read, this records an overrun; see [`take_overrun`](Self::take_overrun).
+    pub fn receive(&mut self) -> Option<Frame> {
+        let flags = ral::read_reg!(ral::can, self.can, IFLAG1) & RX_FLAGS;
+        if flags == 0 {
+            return None;
+        }
+        let now = ral::read_reg!(ral::can, self.can, TIMER) as u16;
+        let mailbox = (0..MAILBOXES)
+            .filter(|mailbox| flags & (1 << mailbox) != 0)
+            .max_by_key(|&mailbox| now.wrapping_sub(self.mb_read(mailbox, 0) as u16))?;
+
+        // Reading CS locks the mailbox, and reading the timer unlocks it.
+        let cs = self.mb_read(mailbox, 0);
+        let id = self.mb_read(mailbox, 1);
+        let data = [self.mb_read(mailbox, 2), self.mb_read(mailbox, 3)];
+        ral::write_reg!(ral::can, self.can, IFLAG1, 1 << mailbox);
+        ral::read_reg!(ral::can, self.can, TIMER);
+
+        if cs >> CS_CODE_SHIFT & 0xF == CODE_RX_OVERRUN {
+            self.overrun = true;
+        }
+        Some(Frame::decode(cs, id, data))
+    }
+
+    /// Returns `true` if a receive mailbox lost a frame since the last call.
+    pub fn take_overrun(&mut self) -> bool {
+        core::mem::take(&mut self.overrun)
+    }
+
+    /// Returns the fault confinement state.
+    pub fn error_state(&mut self) -> ErrorState {
+        match (self.read_esr1() >> 4) & 0b11 {
+            0 => ErrorState::Active,
+            1 => ErrorState::Passive,
+            _ => ErrorState::BusOff,
+        }
+    }
+
+    /// Returns the error state, the error counters, and the bus errors
+    /// observed since the last call.
+    pub fn error_status(&mut self) -> ErrorStatus {
+        let state = self.error_state();
+        let ecr = ral::read_reg!(ral::can, self.can, ECR);
+        ErrorStatus {
+            state,
+            flags: core::mem::replace(&mut self.errors, ErrorFlags::empty()),
+            transmit_errors: ecr as u8,
+            receive_errors: (ecr >> 8) as u8,
+        }
+    }
+
+    /// Read ESR1, and accumulate its error flags.
+    ///
+    /// Reading ESR1 clears the bus error flags, so all reads go through
+    /// this method.
+    fn read_esr1(&mut self) -> u32 {
+        let esr1 = ral::read_reg!(ral::can, self.can, ESR1);
+        ral::write_reg!(ral::can, self.can, ESR1, esr1 & ESR1_W1C);
+        self.errors |= ErrorFlags::from_bits_truncate(esr1);
+        esr1
+    }
+
+    fn write_timing(&mut self, timing: &Timing) {
+        ral::modify_reg!(ral::can, self.can, CTRL1,
+            PRESDIV: timing.prescaler as u32 - 1,
+            RJW: timing.jump_width as u32 - 1,
+            PSEG1: timing.phase1 as u32 - 1,
+            PSEG2: timing.phase2 as u32 - 1,
+            PROPSEG: timing.propagation as u32 - 1,
+            SMP: 0,
+            LBUF: 0,
+            LOM: 0,
+            LPB: 0
+        );
+    }
+
+    fn enter_freeze(&mut self) {
+        ral::modify_reg!(ral::can, self.can, MCR, FRZ: 1, HALT: 1);
+        while ral::read_reg!(ral::can, self.can, MCR, FRZACK == 0) {}
+    }
+
+    fn exit_freeze(&mut self) {
+        ral::modify_reg!(ral::can, self.can, MCR, FRZ: 0, HALT: 0);
+        while ral::read_reg!(ral::can, self.can, MCR, FRZACK == 1) {}
+    }
+
+    /// Returns a pointer to a word of a message buffer.
+    ///
+    /// Message buffers aren't part of the RAL register block.
+    fn mb_word(&self, mailbox: usize, word: usize) -> *mut u32 {
+        debug_assert!(mailbox < MAILBOXES && word < 4);
+        let base: *const ral::can::RegisterBlock = &*self.can;
+        // Safety: every FlexCAN has at least MAILBOXES message buffers of
+        // four words, starting at MB_OFFSET.
+        unsafe { base.cast::<u8>().add(MB_OFFSET + mailbox * 16 + word * 4) as *mut u32 }
+    }
+
+    fn mb_read(&self, mailbox: usize, word: usize) -> u32 {
+        // Safety: see mb_word. Message buffer words are always readable.
+        unsafe { self.mb_word(mailbox, word).read_volatile() }
+    }
+
+    fn mb_write(&mut self, mailbox: usize, word: usize, value: u32) {
+        // Safety: see mb_word. We have exclusive access to the FlexCAN.
+        unsafe { self.mb_word(mailbox, word).write_volatile(value) }
+    }
+}
+
+impl<const N: u8> embedded_can::nb::Can for Can<N> {
+    type Frame = Frame;
+    type Error = Error;
+
+    fn transmit(&mut self, frame: &Frame) -> nb::Result<Option<Frame>, Error> {
+        Can::transmit(self, frame).map(|()| None)
+    }
+
+    fn receive(&mut self) -> nb::Result<Frame, Error> {
+        if self.take_overrun() {
+            return Err(nb::Error::Other(Error::Overrun));
+        }
+        Can::receive(self).ok_or(nb::Error::WouldBlock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtendedId, Frame, StandardId, Timing};
+
+    #[test]
+    fn timing_from_bitrate() {
+        for (clock_hz, bitrate) in [
+            (24_000_000, 125_000),
+            (24_000_000, 250_000),
+            (24_000_000, 500_000),
+            (24_000_000, 1_000_000),
+            (40_000_000, 500_000),
+            (80_000_000, 1_000_000),
+        ] {
+            let timing = Timing::from_bitrate(clock_hz, bitrate).unwrap();
+            assert_eq!(timing.bitrate(clock_hz), bitrate);
+            // Sample point stays within 75% to 90%.
+            let sample = 100 * (timing.quanta() - timing.phase2 as u32) / timing.quanta();
+            assert!((75..=90).contains(&sample), "{sample}");
+        }
+        assert_eq!(
+            Timing::from_bitrate(24_000_000, 500_000),
+            Timing::new(3, 8, 5, 2, 2)
+        );
+        assert_eq!(Timing::from_bitrate(24_000_000, 0), None);
+        assert_eq!(Timing::from_bitrate(24_000_000, 7), None);
+    }
+
+    #[test]
+    fn timing_limits() {
+        assert!(Timing::new(0, 1, 1, 2, 1).is_none());
+        assert!(Timing::new(257, 1, 1, 2, 1).is_none());
+        assert!(Timing::new(1, 1, 1, 1, 1).is_none());
+        assert!(Timing::new(1, 1, 1, 2, 3).is_none());
+        assert!(Timing::new(256, 8, 8, 8, 4).is_some());
+    }
+
+    #[test]
+    fn frame_encoding() {
+        let standard = Frame::new(StandardId::new(0x7FF).unwrap(), &[1, 2, 3]).unwrap();
+        let (cs, id, data) = standard.encode();
+        assert_eq!(cs, 3 << 16);
+        assert_eq!(id, 0x7FF << 18);
+        assert_eq!(data, [0x0102_0300, 0]);
+        assert_eq!(Frame::decode(cs, id, data), standard);
+
+        let extended = Frame::new(
+            ExtendedId::new(0x1ABC_DEF0).unwrap(),
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+        )
+        .unwrap();
+        let (cs, id, data) = extended.encode();
+        assert_eq!(cs, 8 << 16 | 1 << 22 | 1 << 21);
+        assert_eq!(id, 0x1ABC_DEF0);
+        assert_eq!(data, [0x0102_0304, 0x0506_0708]);
+        assert_eq!(Frame::decode(cs, id, data), extended);
+
+        let remote = Frame::new_remote(StandardId::new(0x10).unwrap(), 4).unwrap();
+        let (cs, id, data) = remote.encode();
+        assert_eq!(cs, 4 << 16 | 1 << 20);
+        assert_eq!(Frame::decode(cs, id, data), remote);
+        assert!(remote.data().is_empty());
+
+        assert!(Frame::new(StandardId::ZERO, &[0; 9]).is_none());
+        assert!(Frame::new_remote(StandardId::ZERO, 9).is_none());
+    }
+}
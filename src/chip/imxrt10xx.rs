@@ -2,12 +2,26 @@
 //!
 //! Shared modules may rely on configurations from the `config` module.
 
+#[cfg(not(chip = "imxrt1010"))]
+pub mod acmp;
 pub mod adc;
 #[macro_use]
 pub mod ccm;
+#[path = "clock_gated.rs"]
+mod clock_gated;
+#[cfg(any(chip = "imxrt1060", chip = "imxrt1064"))]
+pub mod control_loop;
 pub mod dcdc;
 #[path = "dma.rs"]
 pub mod dma;
+#[cfg(not(chip = "imxrt1010"))]
+#[path = "flexcan.rs"]
+pub mod flexcan;
+pub mod gpc;
+#[path = "smartled.rs"]
+pub mod smartled;
+#[path = "src.rs"]
+pub mod src;
 pub mod tempmon;
 pub mod trng;
 
@@ -25,7 +39,12 @@ cfg_if::cfg_if! {
 }
 
 pub(crate) mod reexports {
-    pub use super::{adc, dcdc, tempmon, trng};
+    #[cfg(any(chip = "imxrt1060", chip = "imxrt1064"))]
+    pub use super::control_loop;
+    pub use super::src::reset_to_bootloader;
+    #[cfg(not(chip = "imxrt1010"))]
+    pub use super::{acmp, flexcan};
+    pub use super::{adc, dcdc, gpc, smartled, src, tempmon, trng};
 }
 
 pub(crate) mod iomuxc {
@@ -0,0 +1,328 @@
+//! Analog comparator.
+//!
+//! An [`Acmp`] compares the voltages on its plus and minus inputs. Its output
+//! is high when the plus input is above the minus input. Select each input from
+//! the comparator's analog mux, which includes an internal 6-bit DAC. Use the
+//! DAC as a programmable threshold.
+//!
+//! The comparator output is always an input to the crossbar switch, XBARA.
+//! To use the comparator as a PWM fault, select the comparator's
+//! [`xbar_input`](Acmp::xbar_input) for the PWM fault's XBARA output. Then
+//! the comparator can disable PWM outputs within a PWM cycle, without
+//! software.
+//!
+//! This driver is available on chips with comparators. It's not available
+//! on the 1010 chips.
+//!
+//! # Example
+//!
+//! Compare input 0 to half of the DAC's reference voltage. Interrupt
+//! when the input rises above the threshold.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::acmp::{Acmp, DacReference, Input};
+//!
+//! let mut acmp = Acmp::new(unsafe { ral::cmp::CMP1::instance() });
+//! acmp.set_dac(Some((DacReference::Vin1, 31)));
+//! acmp.set_inputs(Input::In0, Input::Dac);
+//! acmp.set_interrupt_enable(hal::acmp::Edge::Rising, true);
+//! acmp.set_enable(true);
+//!
+//! // In the CMP1 interrupt...
+//! if acmp.is_edge(hal::acmp::Edge::Rising) {
+//!     acmp.clear_edge(hal::acmp::Edge::Rising);
+//! }
+//! ```
+
+use crate::ral;
+
+/// An analog comparator.
+pub struct Acmp<const N: u8> {
+    cmp: ral::cmp::Instance<N>,
+}
+
+/// A comparator input, selected by the analog mux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Input {
+    /// Input 0.
+    In0,
+    /// Input 1.
+    In1,
+    /// Input 2.
+    In2,
+    /// Input 3.
+    In3,
+    /// Input 4.
+    In4,
+    /// Input 5.
+    In5,
+    /// Input 6.
+    In6,
+    /// The internal 6-bit DAC.
+    ///
+    /// Enable the DAC with [`set_dac`](Acmp::set_dac).
+    Dac,
+}
+
+impl Input {
+    const fn from_raw(raw: u8) -> Self {
+        match raw & 0x7 {
+            0 => Self::In0,
+            1 => Self::In1,
+            2 => Self::In2,
+            3 => Self::In3,
+            4 => Self::In4,
+            5 => Self::In5,
+            6 => Self::In6,
+            _ => Self::Dac,
+        }
+    }
+}
+
+/// The internal DAC's reference voltage.
+///
+/// See your chip's reference manual for the voltage that each input
+/// selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DacReference {
+    /// `Vin1`.
+    Vin1,
+    /// `Vin2`.
+    Vin2,
+}
+
+/// Comparator hysteresis.
+///
+/// Higher levels have more hysteresis. See your chip's datasheet
+/// for each level's voltage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Hysteresis {
+    /// Level 0, the least hysteresis.
+    Level0,
+    /// Level 1.
+    Level1,
+    /// Level 2.
+    Level2,
+    /// Level 3, the most hysteresis.
+    Level3,
+}
+
+/// A comparator output edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// The output went from low to high.
+    Rising,
+    /// The output went from high to low.
+    Falling,
+}
+
+/// The largest DAC output level.
+pub const MAX_DAC_LEVEL: u8 = 63;
+
+impl<const N: u8> Acmp<N> {
+    /// Take ownership of a comparator.
+    ///
+    /// This disables the comparator, resets its settings, and clears
+    /// its edge flags. Make sure that the comparator's clock gate is
+    /// enabled; see [`clock_gate::acmp`](crate::ccm::clock_gate::acmp).
+    pub fn new(cmp: ral::cmp::Instance<N>) -> Self {
+        ral::write_reg!(ral::cmp, cmp, CR1, 0);
+        ral::write_reg!(ral::cmp, cmp, CR0, 0);
+        ral::write_reg!(ral::cmp, cmp, FPR, 0);
+        ral::write_reg!(ral::cmp, cmp, DACCR, 0);
+        ral::write_reg!(ral::cmp, cmp, MUXCR, 0);
+        ral::write_reg!(ral::cmp, cmp, SCR, CFR: 1, CFF: 1);
+        Self { cmp }
+    }
+
+    /// Release the comparator instance.
+    ///
+    /// This doesn't change the comparator.
+    pub fn release(self) -> ral::cmp::Instance<N> {
+        self.cmp
+    }
+
+    /// Returns the XBARA input for this comparator's output.
+    ///
+    /// Select this input for an XBARA output, like a PWM fault, to route
+    /// the comparator output to that peripheral.
+    pub const fn xbar_input(&self) -> u16 {
+        26 + N as u16 - 1
+    }
+
+    /// Enable (`true`) or disable (`false`) the comparator.
+    pub fn set_enable(&mut self, enable: bool) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR1, EN: enable as u8);
+    }
+
+    /// Returns `true` if the comparator is enabled.
+    pub fn is_enabled(&self) -> bool {
+        ral::read_reg!(ral::cmp, self.cmp, CR1, EN == 1)
+    }
+
+    /// Select the plus and minus inputs.
+    pub fn set_inputs(&mut self, plus: Input, minus: Input) {
+        ral::write_reg!(ral::cmp, self.cmp, MUXCR, PSEL: plus as u8, MSEL: minus as u8);
+    }
+
+    /// Returns the plus and minus inputs, in that order.
+    pub fn inputs(&self) -> (Input, Input) {
+        let (psel, msel) = ral::read_reg!(ral::cmp, self.cmp, MUXCR, PSEL, MSEL);
+        (Input::from_raw(psel), Input::from_raw(msel))
+    }
+
+    /// Enable the internal DAC, or disable it with `None`.
+    ///
+    /// The DAC outputs `reference * (level + 1) / 64`. Select the DAC
+    /// as an input with [`Input::Dac`]. `level` is clamped to
+    /// [`MAX_DAC_LEVEL`].
+    pub fn set_dac(&mut self, dac: Option<(DacReference, u8)>) {
+        match dac {
+            Some((reference, level)) => ral::write_reg!(
+                ral::cmp,
+                self.cmp,
+                DACCR,
+                DACEN: 1,
+                VRSEL: (reference == DacReference::Vin2) as u8,
+                VOSEL: level.min(MAX_DAC_LEVEL)
+            ),
+            None => ral::write_reg!(ral::cmp, self.cmp, DACCR, 0),
+        }
+    }
+
+    /// Returns the DAC's reference and level, or `None` if the DAC is disabled.
+    pub fn dac(&self) -> Option<(DacReference, u8)> {
+        let (dacen, vrsel, vosel) = ral::read_reg!(ral::cmp, self.cmp, DACCR, DACEN, VRSEL, VOSEL);
+        (dacen != 0).then_some((
+            if vrsel != 0 {
+                DacReference::Vin2
+            } else {
+                DacReference::Vin1
+            },
+            vosel,
+        ))
+    }
+
+    /// Set the comparator's hysteresis.
+    pub fn set_hysteresis(&mut self, hysteresis: Hysteresis) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR0, HYSTCTR: hysteresis as u8);
+    }
+
+    /// Returns the comparator's hysteresis.
+    pub fn hysteresis(&self) -> Hysteresis {
+        match ral::read_reg!(ral::cmp, self.cmp, CR0, HYSTCTR) {
+            0 => Hysteresis::Level0,
+            1 => Hysteresis::Level1,
+            2 => Hysteresis::Level2,
+            _ => Hysteresis::Level3,
+        }
+    }
+
+    /// Invert (`true`) the comparator output, or don't invert it (`false`).
+    ///
+    /// When inverted, the output is high when the plus input is below
+    /// the minus input. Inversion affects the output pin, the XBARA
+    /// input, and the edge flags.
+    pub fn set_output_inverted(&mut self, inverted: bool) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR1, INV: inverted as u8);
+    }
+
+    /// Returns `true` if the comparator output is inverted.
+    pub fn is_output_inverted(&self) -> bool {
+        ral::read_reg!(ral::cmp, self.cmp, CR1, INV == 1)
+    }
+
+    /// Drive (`true`) the comparator output pin, or not (`false`).
+    ///
+    /// You're responsible for configuring the pin. The XBARA input
+    /// doesn't need the output pin.
+    pub fn set_output_pin_enable(&mut self, enable: bool) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR1, OPE: enable as u8);
+    }
+
+    /// Select the high speed (`true`) or low speed (`false`) comparison mode.
+    ///
+    /// High speed mode has less propagation delay, and uses more power.
+    pub fn set_high_speed(&mut self, high_speed: bool) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR1, PMODE: high_speed as u8);
+    }
+
+    /// Filter the comparator output.
+    ///
+    /// The output changes only after `count` consecutive samples agree. The
+    /// comparator samples every `period` bus clock cycles. A `count` of zero
+    /// disables the filter. `count` is clamped to 7.
+    pub fn set_filter(&mut self, count: u8, period: u8) {
+        ral::modify_reg!(ral::cmp, self.cmp, CR0, FILTER_CNT: count.min(7));
+        ral::write_reg!(ral::cmp, self.cmp, FPR, period);
+    }
+
+    /// Returns the comparator output.
+    ///
+    /// The output includes the filter and inversion.
+    pub fn output(&self) -> bool {
+        ral::read_reg!(ral::cmp, self.cmp, SCR, COUT == 1)
+    }
+
+    /// Returns `true` if the output had this edge.
+    pub fn is_edge(&self, edge: Edge) -> bool {
+        match edge {
+            Edge::Rising => ral::read_reg!(ral::cmp, self.cmp, SCR, CFR == 1),
+            Edge::Falling => ral::read_reg!(ral::cmp, self.cmp, SCR, CFF == 1),
+        }
+    }
+
+    /// Clear the edge flag.
+    pub fn clear_edge(&mut self, edge: Edge) {
+        match edge {
+            Edge::Rising => ral::modify_reg!(ral::cmp, self.cmp, SCR, CFR: 1, CFF: 0),
+            Edge::Falling => ral::modify_reg!(ral::cmp, self.cmp, SCR, CFR: 0, CFF: 1),
+        }
+    }
+
+    /// Enable (`true`) or disable (`false`) the interrupt for an output edge.
+    pub fn set_interrupt_enable(&mut self, edge: Edge, enable: bool) {
+        match edge {
+            Edge::Rising => {
+                ral::modify_reg!(ral::cmp, self.cmp, SCR, IER: enable as u8, CFR: 0, CFF: 0)
+            }
+            Edge::Falling => {
+                ral::modify_reg!(ral::cmp, self.cmp, SCR, IEF: enable as u8, CFR: 0, CFF: 0)
+            }
+        }
+    }
+
+    /// Returns `true` if the interrupt for an output edge is enabled.
+    pub fn is_interrupt_enabled(&self, edge: Edge) -> bool {
+        match edge {
+            Edge::Rising => ral::read_reg!(ral::cmp, self.cmp, SCR, IER == 1),
+            Edge::Falling => ral::read_reg!(ral::cmp, self.cmp, SCR, IEF == 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Input;
+
+    #[test]
+    fn inputs() {
+        for input in [
+            Input::In0,
+            Input::In1,
+            Input::In2,
+            Input::In3,
+            Input::In4,
+            Input::In5,
+            Input::In6,
+            Input::Dac,
+        ] {
+            assert_eq!(Input::from_raw(input as u8), input);
+        }
+    }
+}
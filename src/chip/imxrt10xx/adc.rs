@@ -35,6 +35,25 @@ use crate::ral;
 #[cfg(feature = "eh02-unproven")]
 use eh02::adc::{Channel, OneShot};
 
+/// Internal ADC channels.
+///
+/// Use [`Adc::read_internal`] to sample these channels.
+///
+/// On all 10xx chips, the ADC routes the high reference voltage, VREFH, to
+/// channel 25. The 10xx ADC does not provide an internal bandgap channel;
+/// see the temperature monitor ([`tempmon`](crate::tempmon)) to measure the
+/// die temperature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Internal {
+    /// The high reference voltage, VREFH (VREFSH in the reference manual).
+    ///
+    /// Since this samples the high reference, the result should be the
+    /// full scale value for the selected resolution. Use this to check the
+    /// ADC's operation.
+    VrefHigh = 25,
+}
+
 /// The clock input for an ADC
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +94,72 @@ pub enum ConversionSpeed {
     VeryFast,
 }
 
+/// The time that the ADC samples its input, in ADC clock cycles.
+///
+/// Longer sample times let the sample and hold capacitor settle when the
+/// source has a high impedance, like a resistor divider. Shorter sample
+/// times increase throughput. The cycle counts are one fewer on imxrt102x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleTime {
+    /// 3 ADC clock cycles.
+    Cycles3,
+    /// 5 ADC clock cycles.
+    Cycles5,
+    /// 7 ADC clock cycles.
+    Cycles7,
+    /// 9 ADC clock cycles.
+    Cycles9,
+    /// 13 ADC clock cycles.
+    Cycles13,
+    /// 17 ADC clock cycles.
+    Cycles17,
+    /// 21 ADC clock cycles.
+    Cycles21,
+    /// 25 ADC clock cycles.
+    Cycles25,
+}
+
+impl SampleTime {
+    /// Returns the `(ADLSMP, ADSTS)` field values.
+    const fn fields(self) -> (u32, u32) {
+        match self {
+            SampleTime::Cycles3 => (0, 0),
+            SampleTime::Cycles5 => (0, 1),
+            SampleTime::Cycles7 => (0, 2),
+            SampleTime::Cycles9 => (0, 3),
+            SampleTime::Cycles13 => (1, 0),
+            SampleTime::Cycles17 => (1, 1),
+            SampleTime::Cycles21 => (1, 2),
+            SampleTime::Cycles25 => (1, 3),
+        }
+    }
+
+    /// Decode the `ADLSMP` and `ADSTS` field values.
+    const fn from_fields(adlsmp: u32, adsts: u32) -> Self {
+        match (adlsmp, adsts) {
+            (0, 0) => SampleTime::Cycles3,
+            (0, 1) => SampleTime::Cycles5,
+            (0, 2) => SampleTime::Cycles7,
+            (0, _) => SampleTime::Cycles9,
+            (_, 0) => SampleTime::Cycles13,
+            (_, 1) => SampleTime::Cycles17,
+            (_, 2) => SampleTime::Cycles21,
+            (_, _) => SampleTime::Cycles25,
+        }
+    }
+}
+
+impl From<ConversionSpeed> for SampleTime {
+    fn from(conversion_speed: ConversionSpeed) -> Self {
+        match conversion_speed {
+            ConversionSpeed::Slow => SampleTime::Cycles25,
+            ConversionSpeed::Medium => SampleTime::Cycles17,
+            ConversionSpeed::Fast => SampleTime::Cycles9,
+            ConversionSpeed::VeryFast => SampleTime::Cycles3,
+        }
+    }
+}
+
 /// Denotes how much hardware averaging to do
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AveragingCount {
@@ -210,21 +295,40 @@ impl<const N: u8> Adc<N> {
     }
 
     /// Sets the conversion speed for this ADC, see ConversionSpeed for clock cycle counts.
+    ///
+    /// The conversion speed selects a sample time. For more sample times, use
+    /// [`set_sample_time`](Self::set_sample_time).
     pub fn set_conversion_speed(&mut self, conversion_speed: ConversionSpeed) {
-        ral::modify_reg!(ral::adc, self.reg, CFG,
-            ADSTS: match conversion_speed {
-                ConversionSpeed::Slow => ADSTS_3,
-                ConversionSpeed::Medium => ADSTS_1,
-                ConversionSpeed::Fast => ADSTS_3,
-                ConversionSpeed::VeryFast => ADSTS_0
-            },
-            ADLSMP: match conversion_speed {
-                ConversionSpeed::Slow => ADLSMP_1,
-                ConversionSpeed::Medium => ADLSMP_1,
-                ConversionSpeed::Fast => ADLSMP_0,
-                ConversionSpeed::VeryFast => ADLSMP_0
-            }
-        );
+        self.set_sample_time(conversion_speed.into());
+    }
+
+    /// Set the time that the ADC samples its input.
+    ///
+    /// If readings from a high impedance source are wrong, increase the
+    /// sample time.
+    pub fn set_sample_time(&mut self, sample_time: SampleTime) {
+        let (adlsmp, adsts) = sample_time.fields();
+        ral::modify_reg!(ral::adc, self.reg, CFG, ADLSMP: adlsmp, ADSTS: adsts);
+    }
+
+    /// Returns the sample time.
+    pub fn sample_time(&self) -> SampleTime {
+        let (adlsmp, adsts) = ral::read_reg!(ral::adc, self.reg, CFG, ADLSMP, ADSTS);
+        SampleTime::from_fields(adlsmp, adsts)
+    }
+
+    /// Enable or disable the high speed configuration.
+    ///
+    /// The high speed configuration supports faster ADC clocks. It adds two
+    /// ADC clock cycles to each conversion. Enable it when the ADC clock
+    /// exceeds the normal configuration's limit; see your chip's datasheet.
+    pub fn set_high_speed(&mut self, enable: bool) {
+        ral::modify_reg!(ral::adc, self.reg, CFG, ADHSC: enable as u32);
+    }
+
+    /// Returns `true` if the high speed configuration is enabled.
+    pub fn is_high_speed(&self) -> bool {
+        ral::read_reg!(ral::adc, self.reg, CFG, ADHSC == ADHSC_1)
     }
 
     /// Enables or disables the low power configuration in the ADC. This does limit the
@@ -233,10 +337,38 @@ impl<const N: u8> Adc<N> {
         ral::modify_reg!(ral::adc, self.reg, CFG, ADLPC: if state { ADLPC_1 } else { ADLPC_0 });
     }
 
+    /// Enable or disable the hardware trigger.
+    ///
+    /// When enabled, a hardware trigger, not software, starts each conversion.
+    /// Selecting a channel arms the conversion; the conversion starts on the
+    /// next hardware trigger. Blocking reads wait for that trigger. A
+    /// [`DmaSource`] converts once per hardware trigger, so each DMA transfer
+    /// element is one hardware triggered result.
+    ///
+    /// On 10xx chips, the ADC_ETC drives the ADC's hardware triggers. To lock a
+    /// conversion to an event, like a PWM submodule's output trigger, route the
+    /// event through XBARA to an ADC_ETC trigger input (`ADC_ETC_TRIG00`
+    /// through `ADC_ETC_TRIG03` for ADC1, `ADC_ETC_TRIG10` through
+    /// `ADC_ETC_TRIG13` for ADC2). Then, configure that ADC_ETC trigger with a
+    /// chain of length one that selects hardware trigger 0 (`HC0`), and enable
+    /// the trigger. You're responsible for configuring XBARA and ADC_ETC.
+    pub fn set_hardware_trigger(&mut self, enable: bool) {
+        ral::modify_reg!(ral::adc, self.reg, CFG, ADTRG: enable as u32);
+    }
+
+    /// Returns `true` if the hardware trigger is enabled.
+    pub fn is_hardware_trigger(&self) -> bool {
+        ral::read_reg!(ral::adc, self.reg, CFG, ADTRG == ADTRG_1)
+    }
+
     /// Calibrates the ADC, will wait for finish
     pub fn calibrate(&mut self) {
+        // Calibration requires the software trigger.
+        let hardware_trigger = self.is_hardware_trigger();
+        self.set_hardware_trigger(false);
         ral::modify_reg!(ral::adc, self.reg, GC, CAL: 0b1);
         while (ral::read_reg!(ral::adc, self.reg, CAL, CAL_CODE) != 0) {}
+        self.set_hardware_trigger(hardware_trigger);
     }
 
     /// Perform a blocking read for an ADC sample.
@@ -262,6 +394,19 @@ impl<const N: u8> Adc<N> {
         // Nevertheless, the HC0 register documents that you can
         // pass in channel 15.
         assert!(channel < 16);
+        self.convert(channel)
+    }
+
+    /// Perform a blocking read of an internal ADC channel.
+    ///
+    /// The internal channels don't need a pin, and you don't need to configure
+    /// any pads. See [`Internal`] for the available channels.
+    pub fn read_internal(&mut self, internal: Internal) -> u16 {
+        self.convert(internal as u32)
+    }
+
+    /// Start a conversion on `channel`, then wait for the result.
+    fn convert(&mut self, channel: u32) -> u16 {
         ral::modify_reg!(ral::adc, self.reg, HC0, |_| channel);
         while (ral::read_reg!(ral::adc, self.reg, HS, COCO0) == 0) {}
 
@@ -274,6 +419,17 @@ impl<const N: u8> Adc<N> {
     pub fn release(self) -> ral::adc::Instance<N> {
         self.reg
     }
+
+    /// Stop all conversions, then release the ADC's register block.
+    ///
+    /// This disables continuous conversions, DMA, and the conversion complete
+    /// interrupt. The other configurations, like the resolution and averaging,
+    /// don't change.
+    pub fn release_disabled(self) -> ral::adc::Instance<N> {
+        ral::modify_reg!(ral::adc, self.reg, GC, ADCO: 0, DMAEN: 0);
+        ral::write_reg!(ral::adc, self.reg, HC0, HC_CONVERSION_DISABLED);
+        self.reg
+    }
 }
 
 #[cfg(feature = "eh02-unproven")]
@@ -340,8 +496,13 @@ impl<P, const N: u8> DmaSource<P, N> {
     ///
     /// This is necessary to start a transfer. However, this in itself
     /// does not start a DMA transfer.
+    ///
+    /// With a software trigger, this starts continuous conversions. With
+    /// a [hardware trigger](Adc::set_hardware_trigger), the ADC converts
+    /// once for each trigger.
     pub fn enable_dma(&mut self) {
-        ral::modify_reg!(ral::adc, self.adc.reg, GC, ADCO: 1, DMAEN: 1);
+        let continuous = !self.adc.is_hardware_trigger();
+        ral::modify_reg!(ral::adc, self.adc.reg, GC, ADCO: continuous as u32, DMAEN: 1);
         ral::modify_reg!(ral::adc, self.adc.reg, HC0, |_| self.channel);
     }
 
@@ -354,6 +515,325 @@ impl<P, const N: u8> DmaSource<P, N> {
     }
 }
 
+/// Returns the number of resolution bits gained by oversampling `samples`
+/// samples.
+///
+/// Each additional bit requires four times as many samples, so this is
+/// `floor(log4(samples))`. See [`oversample`] for more information.
+pub const fn oversampled_bits(samples: usize) -> u32 {
+    if samples == 0 {
+        0
+    } else {
+        samples.ilog2() / 2
+    }
+}
+
+/// Decimate oversampled ADC results into a single, higher resolution result.
+///
+/// Oversampling by `4^k` samples, then decimating by `2^k`, adds `k` bits
+/// of resolution. For example, oversample a 12-bit ADC by 16 samples for a
+/// 14-bit result, or by 64 samples for a 15-bit result. This sums the first
+/// `4^k` samples, where `k` is [`oversampled_bits(samples.len())`](oversampled_bits),
+/// then shifts the sum right by `k`. Samples beyond `4^k` are ignored, so
+/// prefer a power of four.
+///
+/// The result has the ADC's [resolution](Adc::set_resolution) plus `k` bits.
+/// The extra bits are only meaningful if the signal changes slower than the
+/// sampling period, and if it has at least one LSB of noise to dither the
+/// quantization. Hardware [averaging](Adc::set_averaging) reduces that noise,
+/// so consider disabling it when you oversample.
+///
+/// Use [`DmaSource::dma_oversample`] to capture and decimate samples with
+/// a DMA channel.
+///
+/// # Panics
+///
+/// Panics if `samples` is empty.
+pub fn oversample(samples: &[u16]) -> u16 {
+    assert!(!samples.is_empty());
+    let bits = oversampled_bits(samples.len());
+    let sum: u64 = samples[..1 << (2 * bits)]
+        .iter()
+        .map(|&sample| u64::from(sample))
+        .sum();
+    (sum >> bits).min(u16::MAX.into()) as u16
+}
+
+/// The largest number of channels in a [`DmaSequence`].
+///
+/// This is the number of segments in an ADC_ETC trigger chain.
+pub const MAX_SEQUENCE_LEN: usize = 8;
+
+/// Adapter for using a sequence of ADC channels as a DMA source.
+///
+/// The sequence uses the ADC External Trigger Control (ADC_ETC) to pace the
+/// ADC. When a hardware trigger reaches the ADC's first ADC_ETC trigger, the
+/// ADC_ETC runs a chain that converts each channel, back to back. Chain
+/// segment `n` converts `channels[n]` with the ADC's hardware trigger control
+/// register `HCn`, and the ADC_ETC stores the result in the trigger's result
+/// registers. After the last conversion, the ADC_ETC signals the DMA channel
+/// once. One DMA transfer copies every result into your buffer, in sequence
+/// order.
+///
+/// You're responsible for routing a hardware trigger, like a FlexPWM output
+/// trigger, through XBARA to the ADC_ETC trigger returned by
+/// [`trigger`](Self::trigger).
+///
+/// The sequence takes the ADC_ETC, so only one ADC can use it. The ADC
+/// operates in hardware trigger mode. The software trigger used by
+/// [`Adc::read_blocking`] does not work while the sequence exists; use
+/// [`release`](Self::release) to get the ADC and the ADC_ETC back.
+///
+/// Use [`dma_read_sequence`](Self::dma_read_sequence) to perform a transfer.
+///
+/// # Example
+///
+/// Sample three phase currents, and the bus voltage, into one buffer.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+/// use hal::adc;
+///
+/// # async fn f() -> Result<(), hal::dma::Error> {
+/// let inst = unsafe { ral::adc::ADC1::instance() };
+/// let adc1 = adc::Adc::new(inst, adc::ClockSelect::ADACK, adc::ClockDivision::Div2);
+/// let adc_etc = unsafe { ral::adc_etc::ADC_ETC::instance() };
+/// let mut channel = // Acquire your DMA channel...
+///     # unsafe { hal::dma::DMA.channel(7) };
+///
+/// let mut sequence = adc::DmaSequence::new(adc1, adc_etc, &[1, 2, 3, 4]);
+/// // Route a hardware trigger to ADC_ETC trigger sequence.trigger()...
+///
+/// let mut snapshot = [0u16; 4];
+/// sequence.dma_read_sequence(&mut channel, &mut snapshot).await?;
+/// let [ia, ib, ic, vbus] = snapshot;
+/// # Ok(()) }
+/// ```
+pub struct DmaSequence<const N: u8> {
+    adc: Adc<N>,
+    adc_etc: ral::adc_etc::ADC_ETC,
+    len: usize,
+}
+
+impl<const N: u8> DmaSequence<N> {
+    /// Create a DMA source that converts each of `channels`.
+    ///
+    /// This configures the ADC's first ADC_ETC trigger to convert each of
+    /// `channels` when it's triggered by hardware, and enables that trigger.
+    /// You're responsible for configuring the channels' pins as ADC inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is empty, if there are more than [`MAX_SEQUENCE_LEN`]
+    /// channels, or if any channel is greater than 15.
+    pub fn new(adc: Adc<N>, adc_etc: ral::adc_etc::ADC_ETC, channels: &[u8]) -> Self {
+        assert!(!channels.is_empty() && channels.len() <= MAX_SEQUENCE_LEN);
+        assert!(channels.iter().all(|&channel| channel < 16));
+
+        // HCn converts the channel selected by chain segment n.
+        ral::modify_reg!(ral::adc, adc.reg, CFG, ADTRG: ADTRG_1);
+        for idx in 0..channels.len() {
+            hc(&adc.reg, idx).write(HC_ADC_ETC);
+        }
+
+        let n = first_trigger(N);
+        if N == 2 {
+            // TSC_BYPASS resets to 1, which reserves ADC2's triggers for
+            // the touch screen controller.
+            ral::modify_reg!(ral::adc_etc, adc_etc, CTRL, SOFTRST: 0, TSC_BYPASS: 0);
+        } else {
+            ral::modify_reg!(ral::adc_etc, adc_etc, CTRL, SOFTRST: 0);
+        }
+        let trigger = etc_trigger(&adc_etc, n);
+        trigger.counter.write(0);
+        for (register, value) in trigger.chain.iter().zip(chain(channels)) {
+            register.write(value);
+        }
+        trigger.ctrl.write(trigger_control(channels.len()));
+        let enable = ral::read_reg!(ral::adc_etc, adc_etc, CTRL, TRIG_ENABLE);
+        ral::modify_reg!(ral::adc_etc, adc_etc, CTRL, TRIG_ENABLE: enable | (1 << n));
+
+        Self {
+            adc,
+            adc_etc,
+            len: channels.len(),
+        }
+    }
+
+    /// Returns the ADC_ETC trigger that converts the sequence.
+    ///
+    /// ADC_ETC triggers 0 through 3 control ADC1, and triggers 4 through 7
+    /// control ADC2. The sequence uses the ADC's first trigger.
+    pub fn trigger(&self) -> u8 {
+        first_trigger(N)
+    }
+
+    /// Returns the number of channels in the sequence.
+    ///
+    /// This is also the number of elements in each DMA transfer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence has no channels.
+    ///
+    /// This is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a pointer to the trigger's first result register.
+    ///
+    /// The trigger's result registers are adjacent, and each holds the
+    /// results of two chain segments, one per half-word. You should use
+    /// this pointer when coordinating a DMA transfer. You're not expected to
+    /// explicitly read from this pointer in software.
+    pub fn results(&self) -> *const ral::RORegister<u32> {
+        etc_trigger(&self.adc_etc, self.trigger()).result
+    }
+
+    /// Enable the ADC_ETC's DMA request for the trigger.
+    ///
+    /// This clears a pending request, so that the next DMA transfer captures
+    /// the next set of results. This does not start a DMA transfer.
+    pub fn enable_dma(&mut self) {
+        let (enable, request) = dma_request(self.trigger());
+        // The request bits are write-one-to-clear. The sequence owns the
+        // ADC_ETC, so no other trigger's request is pending.
+        ral::modify_reg!(ral::adc_etc, self.adc_etc, DMA_CTRL, |dma_ctrl| dma_ctrl
+            | enable
+            | request);
+    }
+
+    /// Disable the ADC_ETC's DMA request for the trigger.
+    pub fn disable_dma(&mut self) {
+        let (enable, _) = dma_request(self.trigger());
+        ral::modify_reg!(ral::adc_etc, self.adc_etc, DMA_CTRL, |dma_ctrl| dma_ctrl
+            & !enable);
+    }
+
+    /// Release the ADC and the ADC_ETC.
+    ///
+    /// This disables the ADC_ETC trigger, and returns the ADC to software
+    /// trigger mode.
+    pub fn release(mut self) -> (Adc<N>, ral::adc_etc::ADC_ETC) {
+        self.disable_dma();
+        let n = self.trigger();
+        let enable = ral::read_reg!(ral::adc_etc, self.adc_etc, CTRL, TRIG_ENABLE);
+        ral::modify_reg!(ral::adc_etc, self.adc_etc, CTRL, TRIG_ENABLE: enable & !(1 << n));
+        for idx in 0..self.len {
+            hc(&self.adc.reg, idx).write(HC_CONVERSION_DISABLED);
+        }
+        ral::modify_reg!(ral::adc, self.adc.reg, CFG, ADTRG: ADTRG_0);
+        (self.adc, self.adc_etc)
+    }
+}
+
+/// The `ADCH` value that disables hardware triggered conversions.
+const HC_CONVERSION_DISABLED: u32 = 0x1F;
+
+/// The `ADCH` value that converts the channel selected by the ADC_ETC.
+const HC_ADC_ETC: u32 = 0x10;
+
+/// Returns the hardware trigger control register `HCn`.
+fn hc<const N: u8>(reg: &ral::adc::Instance<N>, n: usize) -> &ral::RWRegister<u32> {
+    match n {
+        0 => &reg.HC0,
+        n => &reg.HC[n - 1],
+    }
+}
+
+/// The first ADC_ETC trigger that controls ADC `adc`.
+const fn first_trigger(adc: u8) -> u8 {
+    if adc == ral::SOLE_INSTANCE {
+        0
+    } else {
+        (adc - 1) * 4
+    }
+}
+
+/// The registers of one ADC_ETC trigger.
+struct EtcTrigger<'a> {
+    ctrl: &'a ral::RWRegister<u32>,
+    counter: &'a ral::RWRegister<u32>,
+    chain: [&'a ral::RWRegister<u32>; 4],
+    result: *const ral::RORegister<u32>,
+}
+
+/// Returns the registers of ADC_ETC trigger `n`.
+///
+/// # Panics
+///
+/// Panics if `n` isn't the first trigger of an ADC.
+fn etc_trigger(adc_etc: &ral::adc_etc::ADC_ETC, n: u8) -> EtcTrigger<'_> {
+    match n {
+        0 => EtcTrigger {
+            ctrl: &adc_etc.TRIG0_CTRL,
+            counter: &adc_etc.TRIG0_COUNTER,
+            chain: [
+                &adc_etc.TRIG0_CHAIN_1_0,
+                &adc_etc.TRIG0_CHAIN_3_2,
+                &adc_etc.TRIG0_CHAIN_5_4,
+                &adc_etc.TRIG0_CHAIN_7_6,
+            ],
+            result: &adc_etc.TRIG0_RESULT_1_0,
+        },
+        #[cfg(not(chip = "imxrt1010"))]
+        4 => EtcTrigger {
+            ctrl: &adc_etc.TRIG4_CTRL,
+            counter: &adc_etc.TRIG4_COUNTER,
+            chain: [
+                &adc_etc.TRIG4_CHAIN_1_0,
+                &adc_etc.TRIG4_CHAIN_3_2,
+                &adc_etc.TRIG4_CHAIN_5_4,
+                &adc_etc.TRIG4_CHAIN_7_6,
+            ],
+            result: &adc_etc.TRIG4_RESULT_1_0,
+        },
+        _ => unreachable!("ADC_ETC trigger {n} isn't an ADC's first trigger"),
+    }
+}
+
+/// Encode the chain registers that convert each of `channels`.
+///
+/// Segment `n` converts `channels[n]` with the ADC's `HCn`, then starts the
+/// next segment back to back. Each register holds two segments.
+fn chain(channels: &[u8]) -> [u32; 4] {
+    use ral::adc_etc::TRIG0_CHAIN_1_0 as CHAIN;
+    let mut chain = [0; 4];
+    for (idx, &channel) in channels.iter().enumerate() {
+        let segment = (u32::from(channel) << CHAIN::CSEL0::offset)
+            | (1 << (CHAIN::HWTS0::offset + idx as u32))
+            | CHAIN::B2B0::mask;
+        let shift = if idx % 2 == 0 {
+            CHAIN::CSEL0::offset
+        } else {
+            CHAIN::CSEL1::offset
+        };
+        chain[idx / 2] |= segment << shift;
+    }
+    chain
+}
+
+/// Encode the control register of a trigger that runs a chain of `len` segments.
+///
+/// The trigger is in hardware trigger mode, and it has the highest priority.
+fn trigger_control(len: usize) -> u32 {
+    use ral::adc_etc::TRIG0_CTRL as CTRL;
+    // A zero TRIG_MODE selects the hardware trigger.
+    ((len as u32 - 1) << CTRL::TRIG_CHAIN::offset) | CTRL::TRIG_PRIORITY::mask
+}
+
+/// Returns the `DMA_CTRL` enable and request bits for ADC_ETC trigger `n`.
+const fn dma_request(n: u8) -> (u32, u32) {
+    use ral::adc_etc::DMA_CTRL;
+    (
+        DMA_CTRL::TRIG0_ENABLE::mask << n,
+        DMA_CTRL::TRIG0_REQ::mask << n,
+    )
+}
+
 /// ```compile_fail
 /// use imxrt_hal as hal;
 /// use imxrt_ral as ral;
@@ -401,3 +881,101 @@ struct Adc2Pin2;
 /// ```
 #[cfg(doctest)]
 struct Adc2Pin1Mismatch;
+
+#[cfg(test)]
+mod tests {
+    use super::{chain, dma_request, first_trigger, oversample, oversampled_bits, trigger_control};
+    use crate::ral::adc_etc::{TRIG0_CHAIN_1_0 as CHAIN, TRIG0_CTRL as CTRL};
+
+    #[test]
+    fn oversampled_bit_growth() {
+        assert_eq!(oversampled_bits(0), 0);
+        assert_eq!(oversampled_bits(1), 0);
+        assert_eq!(oversampled_bits(3), 0);
+        assert_eq!(oversampled_bits(4), 1);
+        assert_eq!(oversampled_bits(15), 1);
+        assert_eq!(oversampled_bits(16), 2);
+        assert_eq!(oversampled_bits(64), 3);
+    }
+
+    #[test]
+    fn decimation() {
+        assert_eq!(oversample(&[4095]), 4095);
+        // 12 bits to 13 bits.
+        assert_eq!(oversample(&[4095; 4]), 8190);
+        assert_eq!(oversample(&[100, 100, 101, 101]), 201);
+        // 12 bits to 14 bits.
+        assert_eq!(oversample(&[4095; 16]), 16380);
+        // The sixth sample is ignored.
+        assert_eq!(oversample(&[1, 1, 1, 1, 1, 4095]), 2);
+        // Saturates.
+        assert_eq!(oversample(&[u16::MAX; 4]), u16::MAX);
+    }
+
+    #[test]
+    fn sequence_chain() {
+        let channels = [3, 5, 7];
+        let chain = chain(&channels);
+        let segments = [
+            chain[0] & ((1 << CHAIN::CSEL1::offset) - 1),
+            chain[0] >> CHAIN::CSEL1::offset,
+            chain[1],
+        ];
+        // Segment n converts channels[n] with HCn, then starts the next
+        // segment. No segment interrupts.
+        for (idx, (segment, channel)) in segments.into_iter().zip(channels).enumerate() {
+            assert_eq!(segment & CHAIN::CSEL0::mask, u32::from(channel));
+            assert_eq!(
+                (segment & CHAIN::HWTS0::mask) >> CHAIN::HWTS0::offset,
+                1 << idx
+            );
+            assert_ne!(segment & CHAIN::B2B0::mask, 0);
+            assert_eq!(segment & CHAIN::IE0::mask, 0);
+        }
+        assert_eq!(chain[2..], [0, 0]);
+
+        let chain = super::chain(&[15; 8]);
+        assert_eq!(chain[3], 0x140F | 0x180F << 16);
+    }
+
+    #[test]
+    fn sequence_request() {
+        // A hardware trigger runs the whole chain.
+        let ctrl = trigger_control(3);
+        assert_eq!(
+            (ctrl & CTRL::TRIG_CHAIN::mask) >> CTRL::TRIG_CHAIN::offset,
+            2
+        );
+        assert_eq!(ctrl & CTRL::TRIG_MODE::mask, 0);
+        assert_eq!(ctrl & CTRL::SW_TRIG::mask, 0);
+        assert_eq!(
+            trigger_control(8) & CTRL::TRIG_CHAIN::mask,
+            CTRL::TRIG_CHAIN::mask
+        );
+
+        // Each ADC's first trigger has its own DMA request.
+        assert_eq!(first_trigger(crate::ral::SOLE_INSTANCE), 0);
+        assert_eq!(dma_request(first_trigger(1)), (1 << 0, 1 << 16));
+        assert_eq!(dma_request(first_trigger(2)), (1 << 4, 1 << 20));
+    }
+
+    #[test]
+    fn sequence_results() {
+        use crate::ral::adc_etc::{RegisterBlock, TRIG0_RESULT_1_0};
+        use core::mem::offset_of;
+
+        // Each result register holds two segments, one per half-word. One
+        // minor loop reads the results of all eight segments, in order.
+        assert_eq!(TRIG0_RESULT_1_0::DATA0::offset, 0);
+        assert_eq!(TRIG0_RESULT_1_0::DATA1::offset, 16);
+        let first = offset_of!(RegisterBlock, TRIG0_RESULT_1_0);
+        assert_eq!(offset_of!(RegisterBlock, TRIG0_RESULT_3_2), first + 4);
+        assert_eq!(offset_of!(RegisterBlock, TRIG0_RESULT_5_4), first + 8);
+        assert_eq!(offset_of!(RegisterBlock, TRIG0_RESULT_7_6), first + 12);
+        #[cfg(not(chip = "imxrt1010"))]
+        {
+            let first = offset_of!(RegisterBlock, TRIG4_RESULT_1_0);
+            assert_eq!(offset_of!(RegisterBlock, TRIG4_RESULT_7_6), first + 12);
+        }
+    }
+}
@@ -47,7 +47,7 @@ pub use crate::common::ccm::XTAL_OSCILLATOR_HZ;
 ///     .for_each(|clock_gate| clock_gate.set(&mut ccm, clock_gate::ON));
 /// ```
 pub mod perclk_clk {
-    use crate::ral::{self, ccm::CCM};
+    use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
     /// PERCLK clock selection.
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -94,13 +94,25 @@ pub mod perclk_clk {
     pub fn divider(ccm: &CCM) -> u32 {
         ral::read_reg!(ral::ccm, ccm, CSCMR1, PERCLK_PODF) + 1
     }
+
+    /// Returns the PERCLK clock frequency (Hz).
+    ///
+    /// See [`ipg_clk::frequency`](super::ipg_clk::frequency) for
+    /// the IPG selection's requirements.
+    pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+        let source_hz = match selection(ccm) {
+            Selection::Ipg => super::ipg_clk::frequency(ccm, ccm_analog),
+            Selection::Oscillator => super::XTAL_OSCILLATOR_HZ,
+        };
+        source_hz / divider(ccm)
+    }
 }
 
 /// IPG clock.
 ///
 /// The IPG clock is divided from the core clock.
 pub mod ipg_clk {
-    use crate::ral::{self, ccm::CCM};
+    use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
     /// Returns the IPG clock divider.
     #[inline(always)]
@@ -121,6 +133,13 @@ pub mod ipg_clk {
         let podf = divider.clamp(MIN_DIVIDER, MAX_DIVIDER) - 1;
         ral::modify_reg!(ral::ccm, ccm, CBCDR, IPG_PODF: podf);
     }
+
+    /// Returns the IPG clock frequency (Hz).
+    ///
+    /// This divides the [`ahb_clk::frequency`](super::ahb_clk::frequency).
+    pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+        super::ahb_clk::frequency(ccm, ccm_analog) / divider(ccm)
+    }
 }
 
 /// Wait for all handshake bits to deassert.
@@ -162,6 +181,62 @@ pub fn low_power_mode(ccm: &ral::ccm::CCM) -> LowPowerMode {
     }
 }
 
+/// Clock frequencies, read from the clock tree.
+///
+/// Use [`report`] to read the frequencies. The report reflects the
+/// CCM and analog register settings when you call `report`, regardless
+/// of what configured the clocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ClockReport {
+    /// The AHB clock, which also clocks the ARM core (Hz).
+    pub ahb_hz: u32,
+    /// The IPG clock, which clocks the peripheral buses (Hz).
+    pub ipg_hz: u32,
+    /// The PERCLK clock, which clocks the PIT and GPT timers (Hz).
+    pub perclk_hz: u32,
+    /// The UART clock root (Hz).
+    pub uart_hz: u32,
+    /// The LPI2C clock root (Hz).
+    pub lpi2c_hz: u32,
+    /// The LPSPI clock root (Hz).
+    pub lpspi_hz: u32,
+    /// The system PLL, PLL2 (Hz).
+    pub pll2_hz: u32,
+    /// The USB PLL, PLL3 (Hz).
+    pub pll3_hz: u32,
+}
+
+/// Read the clock frequencies from the clock tree.
+///
+/// # Example
+///
+/// Log the clock tree after the board configures the clocks.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// let ccm = unsafe { ral::ccm::CCM::instance() };
+/// let ccm_analog = unsafe { ral::ccm_analog::CCM_ANALOG::instance() };
+///
+/// let report = hal::ccm::report(&ccm, &ccm_analog);
+/// // log::info!("{report:?}");
+/// # let _ = report;
+/// ```
+pub fn report(ccm: &ral::ccm::CCM, ccm_analog: &ral::ccm_analog::CCM_ANALOG) -> ClockReport {
+    ClockReport {
+        ahb_hz: ahb_clk::frequency(ccm, ccm_analog),
+        ipg_hz: ipg_clk::frequency(ccm, ccm_analog),
+        perclk_hz: perclk_clk::frequency(ccm, ccm_analog),
+        uart_hz: uart_clk::frequency(ccm),
+        lpi2c_hz: lpi2c_clk::frequency(ccm),
+        lpspi_hz: lpspi_clk::frequency(ccm, ccm_analog),
+        pll2_hz: analog::pll2::frequency(ccm_analog),
+        pll3_hz: analog::pll3::frequency(ccm_analog),
+    }
+}
+
 /// UART clock root.
 ///
 /// `uart_clk` provides the clock source for all LPUART peripherals.
@@ -242,6 +317,27 @@ pub mod uart_clk {
     pub fn set_selection(ccm: &mut CCM, selection: Selection) {
         ral::modify_reg!(ral::ccm, ccm, CSCDR1, UART_CLK_SEL: selection as u32);
     }
+
+    /// Returns the UART clock frequency (Hz).
+    ///
+    /// This assumes that PLL3 runs at [`pll3::FREQUENCY`](super::analog::pll3::FREQUENCY).
+    pub fn frequency(ccm: &CCM) -> u32 {
+        let source_hz = match selection(ccm) {
+            Selection::Pll3Div6 => super::analog::pll3::FREQUENCY / 6,
+            Selection::Oscillator => super::XTAL_OSCILLATOR_HZ,
+        };
+        source_hz / divider(ccm)
+    }
+
+    /// Set the UART clock selection and divider, returning the UART clock
+    /// frequency (Hz).
+    ///
+    /// You must disable the LPUART clock gates before calling this function.
+    pub fn set(ccm: &mut CCM, selection: Selection, divider: u32) -> u32 {
+        set_selection(ccm, selection);
+        set_divider(ccm, divider);
+        frequency(ccm)
+    }
 }
 
 /// LPI2C clock root.
@@ -317,6 +413,27 @@ pub mod lpi2c_clk {
     pub fn set_selection(ccm: &mut CCM, selection: Selection) {
         ral::modify_reg!(ral::ccm, ccm, CSCDR2, LPI2C_CLK_SEL: selection as u32);
     }
+
+    /// Returns the LPI2C clock frequency (Hz).
+    ///
+    /// This assumes that PLL3 runs at [`pll3::FREQUENCY`](super::analog::pll3::FREQUENCY).
+    pub fn frequency(ccm: &CCM) -> u32 {
+        let source_hz = match selection(ccm) {
+            Selection::Pll3Div8 => super::analog::pll3::FREQUENCY / 8,
+            Selection::Oscillator => super::XTAL_OSCILLATOR_HZ,
+        };
+        source_hz / divider(ccm)
+    }
+
+    /// Set the LPI2C clock selection and divider, returning the LPI2C clock
+    /// frequency (Hz).
+    ///
+    /// You must disable the LPI2C clock gates before calling this function.
+    pub fn set(ccm: &mut CCM, selection: Selection, divider: u32) -> u32 {
+        set_selection(ccm, selection);
+        set_divider(ccm, divider);
+        frequency(ccm)
+    }
 }
 
 /// LPSPI clock root.
@@ -347,7 +464,7 @@ pub mod lpi2c_clk {
 /// # Some(()) }
 /// ```
 pub mod lpspi_clk {
-    use crate::ral::{self, ccm::CCM};
+    use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
     /// Returns the LPSPI clock divider.
     #[inline(always)]
@@ -402,6 +519,40 @@ pub mod lpspi_clk {
     pub fn set_selection(ccm: &mut CCM, selection: Selection) {
         ral::modify_reg!(ral::ccm, ccm, CBCMR, LPSPI_CLK_SEL: selection as u32);
     }
+
+    /// Returns the LPSPI clock frequency (Hz).
+    ///
+    /// The PFD selections read their fractional dividers from `ccm_analog`.
+    /// This assumes that PLL2 and PLL3 run at their documented frequencies.
+    pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+        use super::analog::{pfd_frequency, pll2, pll3};
+        let source_hz = match selection(ccm) {
+            Selection::Pll3Pfd1 => pfd_frequency(
+                pll3::FREQUENCY,
+                ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_480, PFD1_FRAC),
+            ),
+            Selection::Pll3Pfd0 => pfd_frequency(
+                pll3::FREQUENCY,
+                ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_480, PFD0_FRAC),
+            ),
+            Selection::Pll2 => pll2::FREQUENCY,
+            Selection::Pll2Pfd2 => pfd_frequency(
+                pll2::FREQUENCY,
+                ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_528, PFD2_FRAC),
+            ),
+        };
+        source_hz / divider(ccm)
+    }
+
+    /// Set the LPSPI clock selection and divider, returning the LPSPI clock
+    /// frequency (Hz).
+    ///
+    /// You must disable the LPSPI clock gates before calling this function.
+    pub fn set(ccm: &mut CCM, ccm_analog: &CCM_ANALOG, selection: Selection, divider: u32) -> u32 {
+        set_selection(ccm, selection);
+        set_divider(ccm, divider);
+        frequency(ccm, ccm_analog)
+    }
 }
 
 macro_rules! ccm_flexio {
@@ -11,7 +11,7 @@
 //! For consistency, we refer to the 1010's core clock root as the AHB clock
 //! root.
 
-use crate::ral::{self, ccm::CCM};
+use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
 /// Set the AHB divider.
 ///
@@ -55,3 +55,16 @@ pub fn selection(ccm: &CCM) -> Selection {
         _ => unreachable!(),
     }
 }
+
+/// Returns the AHB clock frequency (Hz).
+///
+/// This follows the AHB clock's selection through the peripheral clock
+/// muxes, reading PLL and PFD dividers from `ccm_analog`. The AHB clock
+/// also clocks the ARM core.
+pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+    let source_hz = match selection(ccm) {
+        Selection::PrePeriphClkSel => crate::ccm::pre_periph_clk::frequency(ccm, ccm_analog),
+        Selection::PeriphClk2Sel => crate::ccm::periph_clk2::frequency(ccm, ccm_analog),
+    };
+    source_hz / divider(ccm)
+}
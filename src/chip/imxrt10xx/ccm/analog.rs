@@ -5,6 +5,38 @@
 
 pub use crate::chip::config::ccm::analog::*;
 
+/// Compute the frequency of a PLL's phase fractional divider (PFD).
+///
+/// `pll_hz` is the PLL frequency, and `frac` is the PFD's fractional
+/// divider. The PFD frequency is `pll_hz * 18 / frac`. The implementation
+/// clamps `frac` between [`pll3::MIN_FRAC`] and [`pll3::MAX_FRAC`].
+pub const fn pfd_frequency(pll_hz: u32, frac: u32) -> u32 {
+    let frac = if frac < pll3::MIN_FRAC as u32 {
+        pll3::MIN_FRAC as u32
+    } else if frac > pll3::MAX_FRAC as u32 {
+        pll3::MAX_FRAC as u32
+    } else {
+        frac
+    };
+    (pll_hz as u64 * 18 / frac as u64) as u32
+}
+
+/// Compute the output of PLL2 or PLL3 from its bypass and loop divider
+/// settings.
+///
+/// A `div_select` of 0 multiplies the oscillator by 20; otherwise, the
+/// PLL multiplies the oscillator by 22.
+const fn loop_frequency(bypass: bool, div_select: u32) -> u32 {
+    let osc_hz = crate::ccm::XTAL_OSCILLATOR_HZ;
+    if bypass {
+        osc_hz
+    } else if div_select == 0 {
+        osc_hz * 20
+    } else {
+        osc_hz * 22
+    }
+}
+
 /// The system PLL.
 pub mod pll2 {
     /// PLL2 frequency (Hz).
@@ -18,6 +50,18 @@ pub mod pll2 {
     pub const MIN_FRAC: u8 = super::pll3::MIN_FRAC;
     /// The largest PLL2_PFD divider.
     pub const MAX_FRAC: u8 = super::pll3::MAX_FRAC;
+
+    use crate::ral;
+
+    /// Returns the PLL2 frequency (Hz), read from the PLL registers.
+    ///
+    /// Unlike [`FREQUENCY`], this reflects the PLL's loop divider and
+    /// bypass setting. A bypassed PLL outputs the oscillator frequency.
+    pub fn frequency(ccm_analog: &ral::ccm_analog::CCM_ANALOG) -> u32 {
+        let (bypass, div_select) =
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PLL_SYS, BYPASS, DIV_SELECT);
+        super::loop_frequency(bypass != 0, div_select)
+    }
 }
 
 /// The USB PLL.
@@ -39,6 +83,16 @@ pub mod pll3 {
 
     use crate::ral;
 
+    /// Returns the PLL3 frequency (Hz), read from the PLL registers.
+    ///
+    /// Unlike [`FREQUENCY`], this reflects the PLL's loop divider and
+    /// bypass setting. A bypassed PLL outputs the oscillator frequency.
+    pub fn frequency(ccm_analog: &ral::ccm_analog::CCM_ANALOG) -> u32 {
+        let (bypass, div_select) =
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PLL_USB1, BYPASS, DIV_SELECT);
+        super::loop_frequency(bypass != 0, div_select)
+    }
+
     /// Restart the USB(1) PLL.
     pub fn restart(ccm_analog: &mut ral::ccm_analog::CCM_ANALOG) {
         loop {
@@ -65,3 +119,15 @@ pub mod pll3 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{loop_frequency, pll2, pll3};
+
+    #[test]
+    fn loop_frequencies() {
+        assert_eq!(loop_frequency(false, 0), pll3::FREQUENCY);
+        assert_eq!(loop_frequency(false, 1), pll2::FREQUENCY);
+        assert_eq!(loop_frequency(true, 1), crate::ccm::XTAL_OSCILLATOR_HZ);
+    }
+}
@@ -375,4 +375,39 @@ where
     }]
 }
 
+/// Returns the analog comparator (ACMP) clock gate locator.
+#[cfg(not(chip = "imxrt1010"))]
+#[inline(always)]
+pub const fn acmp<const N: u8>() -> Locator
+where
+    ral::cmp::Instance<N>: ral::Valid,
+{
+    [
+        locator(CCGR3, CG10),
+        locator(CCGR3, CG11),
+        locator(CCGR3, CG12),
+        locator(CCGR3, CG13),
+    ][N as usize - 1]
+}
+
+/// Returns the FlexCAN bus clock gate locator.
+#[cfg(not(chip = "imxrt1010"))]
+#[inline(always)]
+pub const fn can_bus<const N: u8>() -> Locator
+where
+    ral::can::Instance<N>: ral::Valid,
+{
+    [locator(CCGR0, CG7), locator(CCGR0, CG9)][N as usize - 1]
+}
+
+/// Returns the FlexCAN serial clock gate locator.
+#[cfg(not(chip = "imxrt1010"))]
+#[inline(always)]
+pub const fn can_serial<const N: u8>() -> Locator
+where
+    ral::can::Instance<N>: ral::Valid,
+{
+    [locator(CCGR0, CG8), locator(CCGR0, CG10)][N as usize - 1]
+}
+
 pub use crate::chip::config::ccm::clock_gate::*;
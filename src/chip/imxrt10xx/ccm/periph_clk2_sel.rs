@@ -2,7 +2,7 @@
 //!
 //! Dividers are chip specific, and may not exist.
 
-use crate::ral::{self, ccm::CCM};
+use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
 /// Peripheral CLK2 selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,3 +34,27 @@ pub fn selection(ccm: &CCM) -> Selection {
         _ => unreachable!(),
     }
 }
+
+/// Returns the peripheral clock 2 frequency (Hz).
+///
+/// If the chip has a PERIPH_CLK2 divider, the frequency includes the
+/// divider. This reads PLL3 from `ccm_analog`, and assumes that PLL2's
+/// bypass clock is the oscillator.
+pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+    let source_hz = match selection(ccm) {
+        Selection::Pll3Sw => crate::ccm::analog::pll3::frequency(ccm_analog),
+        Selection::Osc | Selection::Pll2Bypass => crate::ccm::XTAL_OSCILLATOR_HZ,
+    };
+    source_hz / podf(ccm)
+}
+
+#[cfg(not(chip = "imxrt1010"))]
+fn podf(ccm: &CCM) -> u32 {
+    super::periph_clk2_podf::divider(ccm)
+}
+
+// There's no divider at the PERIPH_CLK2 output.
+#[cfg(chip = "imxrt1010")]
+fn podf(_: &CCM) -> u32 {
+    1
+}
@@ -1,6 +1,6 @@
 //! Pre-peripheral clock.
 
-use crate::ral::{self, ccm::CCM};
+use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
 /// Pre-peripheral clock selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,3 +34,29 @@ pub fn selection(ccm: &CCM) -> Selection {
         _ => unreachable!(),
     }
 }
+
+/// Returns the pre-peripheral clock frequency (Hz).
+///
+/// This reads the PLL1 loop divider, and PLL2 and its PFD dividers, from
+/// `ccm_analog`. The PLL1 selection includes the ARM divider.
+pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+    use crate::ccm::{
+        analog::{pfd_frequency, pll1, pll2},
+        arm_divider,
+    };
+    match selection(ccm) {
+        Selection::Pll2 => pll2::frequency(ccm_analog),
+        Selection::Pll2Pfd2 => pfd_frequency(
+            pll2::frequency(ccm_analog),
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_528, PFD2_FRAC),
+        ),
+        Selection::Pll2Pfd0 => pfd_frequency(
+            pll2::frequency(ccm_analog),
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_528, PFD0_FRAC),
+        ),
+        Selection::Pll1 => {
+            let div_sel = ral::read_reg!(ral::ccm_analog, ccm_analog, PLL_ARM, DIV_SELECT);
+            pll1::frequency(div_sel) / arm_divider::divider(ccm)
+        }
+    }
+}
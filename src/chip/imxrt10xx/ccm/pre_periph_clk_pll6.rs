@@ -1,6 +1,6 @@
 //! Pre-peripheral clock.
 
-use crate::ral::{self, ccm::CCM};
+use crate::ral::{self, ccm::CCM, ccm_analog::CCM_ANALOG};
 
 /// Pre-peripheral clock selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,3 +34,34 @@ pub fn selection(ccm: &CCM) -> Selection {
         _ => unreachable!(),
     }
 }
+
+/// Returns the pre-peripheral clock frequency (Hz).
+///
+/// This reads PLL2, PLL3, and their PFD dividers from `ccm_analog`. If the
+/// chip has an ARM divider, the PLL6 selection includes the ARM divider.
+pub fn frequency(ccm: &CCM, ccm_analog: &CCM_ANALOG) -> u32 {
+    use crate::ccm::analog::{pfd_frequency, pll2, pll3, pll6};
+    match selection(ccm) {
+        Selection::Pll2 => pll2::frequency(ccm_analog),
+        Selection::Pll3Pfd3 => pfd_frequency(
+            pll3::frequency(ccm_analog),
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_480, PFD3_FRAC),
+        ),
+        Selection::Pll2Pfd3 => pfd_frequency(
+            pll2::frequency(ccm_analog),
+            ral::read_reg!(ral::ccm_analog, ccm_analog, PFD_528, PFD3_FRAC),
+        ),
+        Selection::Pll6 => pll6::FREQUENCY / arm_divider(ccm),
+    }
+}
+
+#[cfg(not(chip = "imxrt1010"))]
+fn arm_divider(ccm: &CCM) -> u32 {
+    crate::ccm::arm_divider::divider(ccm)
+}
+
+// There's no ARM divider.
+#[cfg(chip = "imxrt1010")]
+fn arm_divider(_: &CCM) -> u32 {
+    1
+}
@@ -0,0 +1,211 @@
+//! PWM-synchronized ADC sampling.
+//!
+//! A [`ControlLoop`] samples a sequence of ADC channels once per PWM period,
+//! and it lands the results in a buffer. It's the sampling pipeline of a
+//! field-oriented motor controller, or of a switching converter. The pipeline
+//! is
+//!
+//! 1. a FlexPWM submodule pulses an output trigger when a value register
+//!    compares. See [`OutputTriggers`](crate::flexpwm::OutputTriggers).
+//! 2. the crossbar switch, XBARA, routes that trigger to an ADC_ETC trigger.
+//! 3. the ADC_ETC trigger runs a chain that converts each channel, back to
+//!    back. See [`DmaSequence`](crate::adc::DmaSequence).
+//! 4. at the end of the chain, the ADC_ETC signals a DMA channel, which copies
+//!    every result into your buffer.
+//!
+//! [`ControlLoop::read`] waits for the next set of samples.
+//!
+//! # Timing
+//!
+//! The PWM trigger is synchronous to the PWM counter, so the first conversion
+//! starts a fixed number of clock cycles after the compare. The crossbar and
+//! the ADC_ETC synchronize the trigger to the ADC clock, which adds up to one
+//! ADC clock cycle of jitter.
+//!
+//! The ADC converts one channel at a time. Sample `n` is taken about `n`
+//! conversion times after the trigger, where the conversion time depends on the
+//! ADC clock, [sample time](crate::adc::SampleTime), resolution, and averaging.
+//! Put the channels that need to be simultaneous, like phase currents, at the
+//! start of the sequence. The samples are ready about `channels.len()`
+//! conversion times after the trigger; place the trigger so that the samples
+//! are ready before your control loop needs them.
+//!
+//! # Example
+//!
+//! Sample three phase currents and the bus voltage at the peak of a
+//! center-aligned PWM.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//! use hal::{adc, control_loop::ControlLoop, flexpwm};
+//!
+//! # async fn f() -> Result<(), hal::dma::Error> {
+//! let (mut pwm, (mut sm0, _, _, _)) = flexpwm::new(unsafe { ral::pwm::PWM1::instance() });
+//! pwm.set_center_aligned(&mut sm0, 1000).unwrap();
+//! // VAL0 compares at the peak of the center-aligned period.
+//! let triggers = flexpwm::OutputTriggers::VAL0;
+//!
+//! let adc1 = adc::Adc::new(
+//!     unsafe { ral::adc::ADC1::instance() },
+//!     adc::ClockSelect::ADACK,
+//!     adc::ClockDivision::Div2,
+//! );
+//! let adc_etc = unsafe { ral::adc_etc::ADC_ETC::instance() };
+//! let mut xbar = unsafe { ral::xbara1::XBARA1::instance() };
+//! let mut channel = // DMA channel 7...
+//!     # unsafe { hal::dma::DMA.channel(7) };
+//!
+//! let mut control = ControlLoop::new(adc1, adc_etc, &mut xbar, &sm0, triggers, &[1, 2, 3, 4]);
+//! sm0.set_load_ok(&pwm);
+//! sm0.set_running(&mut pwm, true);
+//!
+//! let mut samples = [0u16; 4];
+//! loop {
+//!     control.read(&mut channel, &mut samples).await?;
+//!     let [ia, ib, ic, vbus] = samples;
+//!     // Run the controller...
+//! }
+//! # }
+//! ```
+
+use crate::adc::{Adc, DmaSequence};
+use crate::dma::channel::Channel;
+use crate::flexpwm::{OutputTriggers, Submodule};
+use crate::ral;
+
+/// The XBARA input for a FlexPWM submodule's output triggers.
+///
+/// The input is the logical OR of the submodule's `PWM_OUT_TRIG0` and
+/// `PWM_OUT_TRIG1`. `pwm` is the FlexPWM instance, starting at 1, and
+/// `submodule` is the submodule, starting at 0.
+pub const fn pwm_trigger_input(pwm: u8, submodule: u8) -> u16 {
+    40 + 4 * (pwm as u16 - 1) + submodule as u16
+}
+
+/// The XBARA output for an ADC_ETC trigger.
+///
+/// ADC_ETC triggers 0 through 3 control ADC1, and triggers 4 through 7
+/// control ADC2.
+pub const fn adc_etc_trigger_output(trigger: u8) -> u16 {
+    103 + trigger as u16
+}
+
+/// Route an XBARA input to an XBARA output.
+///
+/// `output` is an ADC_ETC trigger output; see
+/// [`adc_etc_trigger_output`]. Other outputs don't change.
+///
+/// # Panics
+///
+/// Panics if `output` isn't an ADC_ETC trigger output.
+pub fn xbar_connect(xbar: &mut ral::xbara1::XBARA1, input: u16, output: u16) {
+    use ral::xbara1;
+    match output {
+        103 => ral::modify_reg!(xbara1, xbar, SEL51, SEL103: input),
+        104 => ral::modify_reg!(xbara1, xbar, SEL52, SEL104: input),
+        105 => ral::modify_reg!(xbara1, xbar, SEL52, SEL105: input),
+        106 => ral::modify_reg!(xbara1, xbar, SEL53, SEL106: input),
+        107 => ral::modify_reg!(xbara1, xbar, SEL53, SEL107: input),
+        108 => ral::modify_reg!(xbara1, xbar, SEL54, SEL108: input),
+        109 => ral::modify_reg!(xbara1, xbar, SEL54, SEL109: input),
+        110 => ral::modify_reg!(xbara1, xbar, SEL55, SEL110: input),
+        _ => panic!("XBARA output {output} isn't an ADC_ETC trigger"),
+    }
+}
+
+/// A PWM-synchronized ADC sequence.
+///
+/// See the [module-level documentation](crate::control_loop) for more information.
+pub struct ControlLoop<const N: u8> {
+    sequence: DmaSequence<N>,
+}
+
+impl<const N: u8> ControlLoop<N> {
+    /// Create a control loop that samples `channels` whenever the PWM
+    /// submodule `sm` generates an output trigger.
+    ///
+    /// This enables `triggers` on the submodule, routes the submodule's
+    /// output triggers through the XBARA to the ADC's first ADC_ETC trigger,
+    /// and configures that trigger to convert each of `channels`. You're
+    /// responsible for configuring the channels' pins as ADC inputs, and for
+    /// running the PWM submodule.
+    ///
+    /// The control loop takes the ADC_ETC, so only one ADC can use it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is empty, if there are more than
+    /// [`MAX_SEQUENCE_LEN`](crate::adc::MAX_SEQUENCE_LEN) channels, or if any
+    /// channel is greater than 15.
+    pub fn new<const P: u8, const M: u8>(
+        adc: Adc<N>,
+        adc_etc: ral::adc_etc::ADC_ETC,
+        xbar: &mut ral::xbara1::XBARA1,
+        sm: &Submodule<P, M>,
+        triggers: OutputTriggers,
+        channels: &[u8],
+    ) -> Self {
+        let sequence = DmaSequence::new(adc, adc_etc, channels);
+        xbar_connect(
+            xbar,
+            pwm_trigger_input(P, M),
+            adc_etc_trigger_output(sequence.trigger()),
+        );
+        sm.set_output_triggers(triggers);
+
+        Self { sequence }
+    }
+
+    /// Returns the number of channels in the sequence.
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Returns `true` if the sequence has no channels.
+    ///
+    /// This is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    /// Wait for the next set of samples.
+    ///
+    /// When the future resolves, `buffer[n]` holds the result for
+    /// `channels[n]`. Call this again to capture the next set of samples.
+    /// If you don't call `read` before the next trigger, those samples are
+    /// lost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length doesn't match the sequence length.
+    pub async fn read(
+        &mut self,
+        channel: &mut Channel,
+        buffer: &mut [u16],
+    ) -> Result<(), crate::dma::Error> {
+        self.sequence.dma_read_sequence(channel, buffer).await
+    }
+
+    /// Release the ADC and the ADC_ETC.
+    ///
+    /// This disables the ADC_ETC trigger, and returns the ADC to software
+    /// trigger mode. It doesn't change the PWM submodule or the XBARA.
+    pub fn release(self) -> (Adc<N>, ral::adc_etc::ADC_ETC) {
+        self.sequence.release()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adc_etc_trigger_output, pwm_trigger_input};
+
+    #[test]
+    fn xbar_numbers() {
+        assert_eq!(pwm_trigger_input(1, 0), 40);
+        assert_eq!(pwm_trigger_input(2, 3), 47);
+        assert_eq!(pwm_trigger_input(4, 3), 55);
+        assert_eq!(adc_etc_trigger_output(0), 103);
+        assert_eq!(adc_etc_trigger_output(4), 107);
+    }
+}
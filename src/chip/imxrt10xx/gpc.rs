@@ -0,0 +1,168 @@
+//! General power controller.
+//!
+//! The GPC decides which interrupts wake the processor from low-power
+//! modes, like STOP. An interrupt wakes the processor only if it's
+//! unmasked in the GPC. Use [`Gpc::set_wakeup`] to select wakeup
+//! interrupts.
+//!
+//! The GPC only supports NVIC interrupts 32 through 191. The first 32
+//! interrupts, which include the DMA channel interrupts, cannot wake the
+//! processor.
+//!
+//! # GPIO wakeup
+//!
+//! Use [`Gpc::enable_gpio_wakeup`] to wake the processor when a GPIO input
+//! changes. This works for the GPIO1 through GPIO5 ports that exist on your
+//! chip. The fast GPIO ports, GPIO6 through GPIO9, cannot wake the processor;
+//! they're clocked by the AHB clock, which stops in low-power modes. Use the
+//! equivalent GPIO1 through GPIO4 port, selected in the IOMUXC GPR, instead.
+//!
+//! # Example
+//!
+//! Wake from STOP when a GPIO1 input sees a rising edge.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::gpio::{Port, Trigger};
+//!
+//! let mut gpio1 = Port::new(unsafe { ral::gpio::GPIO1::instance() });
+//! let input = hal::gpio::Input::without_pin(&mut gpio1, 3);
+//!
+//! let mut gpc = hal::gpc::Gpc::new(unsafe { ral::gpc::GPC::instance() });
+//! let interrupt = gpc.enable_gpio_wakeup(&mut gpio1, &input, Trigger::RisingEdge).unwrap();
+//!
+//! // Unmask `interrupt` in the NVIC, then enter STOP mode...
+//! # let _ = interrupt;
+//! ```
+
+use crate::{
+    gpio::{Input, Port, Trigger},
+    ral::{self, Interrupt},
+};
+
+/// The general power controller.
+pub struct Gpc {
+    gpc: ral::gpc::GPC,
+}
+
+impl Gpc {
+    /// Take ownership of the GPC.
+    pub fn new(gpc: ral::gpc::GPC) -> Self {
+        Self { gpc }
+    }
+
+    /// Release the GPC peripheral.
+    pub fn release(self) -> ral::gpc::GPC {
+        self.gpc
+    }
+
+    fn imr(&self, index: usize) -> &ral::RWRegister<u32> {
+        match index {
+            0 => &self.gpc.IMR1,
+            1 => &self.gpc.IMR2,
+            2 => &self.gpc.IMR3,
+            3 => &self.gpc.IMR4,
+            4 => &self.gpc.IMR5,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns `true` if `interrupt` can wake the processor.
+    pub fn is_wakeup(&self, interrupt: Interrupt) -> bool {
+        imr_position(interrupt as u16)
+            .map(|(index, bit)| self.imr(index).read() & (1 << bit) == 0)
+            .unwrap_or(false)
+    }
+
+    /// Allow (`true`) or prevent (`false`) `interrupt` from waking the processor.
+    ///
+    /// Returns `false` if the GPC cannot use `interrupt` to wake the processor.
+    /// In this case, the call does nothing.
+    pub fn set_wakeup(&mut self, interrupt: Interrupt, enable: bool) -> bool {
+        if let Some((index, bit)) = imr_position(interrupt as u16) {
+            let imr = self.imr(index);
+            let mask = imr.read();
+            imr.write(if enable {
+                mask & !(1 << bit)
+            } else {
+                mask | 1 << bit
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wake the processor when `input` triggers.
+    ///
+    /// This configures the GPIO interrupt for `input` on `port`, then allows
+    /// the input's GPIO interrupt to wake the processor. It returns the
+    /// interrupt. You're responsible for unmasking that interrupt in the NVIC,
+    /// and handling it.
+    ///
+    /// Returns `None` if the port cannot wake the processor. In this case,
+    /// the call does nothing. `input` must belong to `port`.
+    pub fn enable_gpio_wakeup<P, const N: u8>(
+        &mut self,
+        port: &mut Port<N>,
+        input: &Input<P>,
+        trigger: Trigger,
+    ) -> Option<Interrupt> {
+        let interrupt = gpio_interrupt(N, input.offset())?;
+        port.set_interrupt(input, Some(trigger));
+        self.set_wakeup(interrupt, true);
+        Some(interrupt)
+    }
+}
+
+/// Returns the combined GPIO interrupt for a pin on a GPIO port.
+fn gpio_interrupt(port: u8, offset: u32) -> Option<Interrupt> {
+    use Interrupt::*;
+    Some(match (port, offset < 16) {
+        (1, true) => GPIO1_COMBINED_0_15,
+        (1, false) => GPIO1_COMBINED_16_31,
+        (2, true) => GPIO2_COMBINED_0_15,
+        #[cfg(not(chip = "imxrt1010"))]
+        (2, false) => GPIO2_COMBINED_16_31,
+        #[cfg(not(chip = "imxrt1010"))]
+        (3, true) => GPIO3_COMBINED_0_15,
+        #[cfg(not(chip = "imxrt1010"))]
+        (3, false) => GPIO3_COMBINED_16_31,
+        #[cfg(any(chip = "imxrt1060", chip = "imxrt1064"))]
+        (4, true) => GPIO4_COMBINED_0_15,
+        #[cfg(any(chip = "imxrt1060", chip = "imxrt1064"))]
+        (4, false) => GPIO4_COMBINED_16_31,
+        (5, true) => GPIO5_COMBINED_0_15,
+        #[cfg(not(chip = "imxrt1010"))]
+        (5, false) => GPIO5_COMBINED_16_31,
+        _ => return None,
+    })
+}
+
+/// Returns the IMR register index, and bit, that masks an interrupt.
+///
+/// `IMR1` masks interrupts 32 through 63, and `IMR5` masks interrupts
+/// 160 through 191.
+const fn imr_position(irq: u16) -> Option<(usize, u32)> {
+    match irq {
+        32..=191 => Some(((irq / 32 - 1) as usize, (irq % 32) as u32)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::imr_position;
+
+    #[test]
+    fn imr() {
+        assert_eq!(imr_position(0), None);
+        assert_eq!(imr_position(31), None);
+        assert_eq!(imr_position(32), Some((0, 0)));
+        assert_eq!(imr_position(80), Some((1, 16)));
+        assert_eq!(imr_position(191), Some((4, 31)));
+        assert_eq!(imr_position(192), None);
+    }
+}
@@ -94,6 +94,10 @@
 //! ```
 
 use crate::ral;
+use crate::snvs::{
+    srtc::{calibration_from_ppm, compensation_ppm, Srtc},
+    LpCore,
+};
 
 /// Indicates that the temperature monitor is powered down.
 ///
@@ -333,3 +337,26 @@ impl TempMon {
         );
     }
 }
+
+impl Srtc {
+    /// Measure the die temperature, then calibrate the SRTC for that temperature.
+    ///
+    /// `table` describes the crystal's correction over temperature, in
+    /// millidegrees Celsius and parts per million. See
+    /// [`compensation_ppm`] for more information. Returns the calibration
+    /// counts that were applied.
+    ///
+    /// This measures the temperature like [`TempMon::measure_temp`]. Call it
+    /// periodically, like once a minute, to track the temperature.
+    pub fn auto_calibrate(
+        &mut self,
+        core: &mut LpCore,
+        tempmon: &mut TempMon,
+        table: &[(i32, i16)],
+    ) -> nb::Result<i8, PowerDownError> {
+        let temperature = tempmon.measure_temp()?;
+        let counts = calibration_from_ppm(compensation_ppm(temperature, table).into());
+        self.set_calibration(core, counts);
+        Ok(counts)
+    }
+}
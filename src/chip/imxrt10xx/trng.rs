@@ -47,8 +47,32 @@
 //! let random_data = nb::block!(trng.next_u32()).ok()?;
 //! # Some(()) }();
 //! ```
+//!
+//! ## Async
+//!
+//! [`next_u32`](Trng::next_u32) never blocks; it returns "would block" while the TRNG
+//! generates the next block. Use [`next_u32_async`](Trng::next_u32_async) to wait
+//! for entropy in an async task without blocking the executor. The future waits
+//! for a TRNG interrupt, so call [`on_interrupt`] in your TRNG interrupt handler,
+//! and unmask the TRNG interrupt.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//!
+//! // #[cortex_m_rt::interrupt]
+//! fn TRNG() {
+//!     // Safety: called in the TRNG interrupt handler.
+//!     unsafe { hal::trng::on_interrupt() };
+//! }
+//! ```
+
+use core::{
+    cell::RefCell,
+    fmt,
+    task::{Poll, Waker},
+};
 
-use core::fmt;
+use cortex_m::interrupt::{self, Mutex};
 
 use crate::ral::trng;
 use crate::ral::{modify_reg, read_reg, write_reg};
@@ -190,6 +214,60 @@ impl Trng {
         data
     }
 
+    /// Wait for the next randomly-generated `u32`.
+    ///
+    /// This is the async version of [`next_u32`](Self::next_u32). When the
+    /// driver has used all of its entropy, the future waits while the TRNG
+    /// generates the next block.
+    ///
+    /// While it waits, the future enables all TRNG [`Interrupts`]. When the
+    /// block is valid, or when the TRNG reports an error, [`on_interrupt`]
+    /// disables the interrupts and wakes the future. If you never call
+    /// `on_interrupt`, the future never completes.
+    pub async fn next_u32_async(&mut self) -> Result<u32, Error> {
+        core::future::poll_fn(|cx| match self.next_u32() {
+            Ok(data) => Poll::Ready(Ok(data)),
+            Err(nb::Error::Other(err)) => Poll::Ready(Err(err)),
+            Err(nb::Error::WouldBlock) => {
+                interrupt::free(|cs| {
+                    *WAKER.borrow(cs).borrow_mut() = Some(cx.waker().clone());
+                });
+                // If the block became valid since we checked, the interrupt
+                // is already pending.
+                self.set_interrupts(Interrupts::all());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Read the interrupt enables.
+    pub fn interrupts(&self) -> Interrupts {
+        Interrupts::from_bits_truncate(read_reg!(trng, self.reg, INT_MASK))
+    }
+
+    /// Set the interrupt enables.
+    ///
+    /// Interrupts that aren't in `interrupts` are disabled.
+    pub fn set_interrupts(&mut self, interrupts: Interrupts) {
+        write_reg!(trng, self.reg, INT_MASK, interrupts.bits());
+    }
+
+    /// Read the interrupt status.
+    ///
+    /// A status flag is set regardless of its interrupt enable.
+    pub fn interrupt_status(&self) -> Interrupts {
+        Interrupts::from_bits_truncate(read_reg!(trng, self.reg, INT_STATUS))
+    }
+
+    /// Clear interrupt status flags.
+    ///
+    /// Flags that aren't in `status` don't change.
+    pub fn clear_interrupt_status(&mut self, status: Interrupts) {
+        // Writing zero clears the status bit; writing one has no effect.
+        write_reg!(trng, self.reg, INT_CTRL, (!status).bits());
+    }
+
     /// Retrieve another block of random numbers if we've used them all up.
     fn retrieve_if_needed(&mut self) -> nb::Result<(), Error> {
         if self.index >= self.block.len() {
@@ -271,6 +349,43 @@ impl Trng {
     }
 }
 
+/// The waker for a [`Trng::next_u32_async`] future.
+static WAKER: Mutex<RefCell<Option<Waker>>> = Mutex::new(RefCell::new(None));
+
+/// Handle a TRNG interrupt.
+///
+/// If an enabled TRNG interrupt is active, `on_interrupt` disables and clears
+/// the interrupt, then wakes the task that's waiting in
+/// [`next_u32_async`](Trng::next_u32_async). Call `on_interrupt` in your TRNG
+/// interrupt handler. If you use the TRNG interrupts for your own purposes,
+/// handle them before you call `on_interrupt`.
+///
+/// # Safety
+///
+/// `on_interrupt` modifies the TRNG interrupt registers, which the [`Trng`]
+/// driver owns. Caller must ensure that `on_interrupt` is only called in the
+/// TRNG interrupt handler.
+pub unsafe fn on_interrupt() {
+    // Safety: caller ensures that this is called in the TRNG interrupt handler,
+    // which doesn't race with the driver's register access.
+    let reg = unsafe { trng::TRNG::instance() };
+    let active = read_reg!(trng, reg, INT_STATUS) & read_reg!(trng, reg, INT_MASK);
+    let active = Interrupts::from_bits_truncate(active);
+    if active.is_empty() {
+        return;
+    }
+
+    modify_reg!(trng, reg, INT_MASK, |mask| mask & !active.bits());
+    // Writing zero clears the status bit; writing one has no effect.
+    write_reg!(trng, reg, INT_CTRL, (!active).bits());
+
+    interrupt::free(|cs| {
+        if let Some(waker) = WAKER.borrow(cs).borrow_mut().take() {
+            waker.wake();
+        }
+    });
+}
+
 /// Wrapper struct around [`TRNG`] that implements `RngCore`.
 #[cfg(feature = "rand_core")]
 pub struct RngCoreWrapper(Trng);
@@ -377,6 +492,21 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// TRNG interrupts.
+    ///
+    /// Use these to enable interrupts with [`Trng::set_interrupts`], and to
+    /// check and clear the interrupt status.
+    pub struct Interrupts : u32 {
+        /// A hardware error, like a statistical test failure.
+        const HARDWARE_ERROR = 1 << 0;
+        /// A new block of entropy is valid.
+        const ENTROPY_VALID = 1 << 1;
+        /// The frequency count failed.
+        const FREQUENCY_COUNT_FAIL = 1 << 2;
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "An error occurred in the TRNG module")
@@ -406,7 +536,8 @@ impl eh02::blocking::rng::Read for Trng {
 
 #[cfg(test)]
 mod tests {
-    use super::RetryCount;
+    use super::{Interrupts, RetryCount};
+    use crate::ral::trng;
 
     #[test]
     fn retry_count() {
@@ -416,4 +547,20 @@ mod tests {
         }
         assert!(RetryCount::new(16).is_none());
     }
+
+    #[test]
+    fn interrupt_bits() {
+        assert_eq!(
+            Interrupts::HARDWARE_ERROR.bits(),
+            trng::INT_MASK::HW_ERR::mask
+        );
+        assert_eq!(
+            Interrupts::ENTROPY_VALID.bits(),
+            trng::INT_MASK::ENT_VAL::mask
+        );
+        assert_eq!(
+            Interrupts::FREQUENCY_COUNT_FAIL.bits(),
+            trng::INT_MASK::FRQ_CT_FAIL::mask
+        );
+    }
 }
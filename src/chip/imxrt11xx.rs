@@ -3,8 +3,17 @@
 //! Shared modules may rely on configurations from the `config` module.
 
 pub mod ccm;
+#[path = "clock_gated.rs"]
+mod clock_gated;
+pub mod dac;
 #[path = "dma.rs"]
 pub mod dma;
+#[path = "flexcan.rs"]
+pub mod flexcan;
+#[path = "smartled.rs"]
+pub mod smartled;
+#[path = "src.rs"]
+pub mod src;
 pub mod usbphy;
 
 cfg_if::cfg_if! {
@@ -15,7 +24,8 @@ cfg_if::cfg_if! {
 }
 
 pub(crate) mod reexports {
-    pub use super::usbphy;
+    pub use super::src::reset_to_bootloader;
+    pub use super::{dac, flexcan, smartled, src, usbphy};
 }
 
 pub(crate) mod iomuxc {
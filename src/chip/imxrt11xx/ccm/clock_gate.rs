@@ -139,6 +139,17 @@ pub const fn usb() -> Locator {
     Locator::new(115)
 }
 
+/// Returns the FlexCAN clock gate locator.
+#[inline(always)]
+pub const fn can<const N: u8>() -> Locator
+where
+    ral::can::Instance<N>: ral::Valid,
+{
+    // CAN1 -> LPCG83
+    // CAN3 -> LPCG85
+    Locator::new(N as usize + 82)
+}
+
 /// Returns the LPUART clock gate locator.
 #[inline(always)]
 pub const fn lpuart<const N: u8>() -> Locator
@@ -0,0 +1,261 @@
+//! 12-bit digital-to-analog converter.
+//!
+//! The [`Dac`] converts 12-bit values to an analog voltage. In
+//! [buffer mode](Mode::Buffer), each value that you write changes the output
+//! right away. Use [`set_value`](Dac::set_value) for a static output, like a
+//! comparator reference.
+//!
+//! In [FIFO mode](Mode::Fifo), values wait in a FIFO until a trigger moves the
+//! next value to the output. Use FIFO mode with a hardware trigger to play out
+//! a waveform at a fixed rate. A DMA channel can keep the FIFO full; see
+//! [`DmaDestination`].
+//!
+//! This driver is only available on 11xx chips.
+//!
+//! # Example
+//!
+//! Output half of the reference voltage.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::dac::{Dac, Reference};
+//!
+//! let mut dac = Dac::new(unsafe { ral::dac::DAC::instance() });
+//! dac.set_reference(Reference::Ref1);
+//! dac.set_output_enable(true);
+//! dac.set_enable(true);
+//! dac.set_value(2048);
+//! ```
+
+use crate::ral;
+
+/// The largest DAC value.
+pub const MAX_VALUE: u16 = 0xFFF;
+
+/// The digital-to-analog converter.
+pub struct Dac {
+    dac: ral::dac::DAC,
+}
+
+/// The DAC's reference voltage.
+///
+/// See your chip's reference manual for the voltage that each
+/// reference selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reference {
+    /// `DACREF_1`.
+    Ref1,
+    /// `DACREF_2`.
+    Ref2,
+}
+
+/// How the DAC uses written values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Each written value immediately changes the output.
+    Buffer,
+    /// Written values enter a FIFO, and each trigger outputs the next value.
+    Fifo,
+    /// Like [`Fifo`](Mode::Fifo), but the DAC replays the FIFO forwards,
+    /// then backwards, after it reaches the last value.
+    SwingBack,
+}
+
+/// Selects the source that advances the FIFO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// A hardware trigger, routed from another peripheral.
+    Hardware,
+    /// Software triggers, with [`Dac::trigger`].
+    Software,
+}
+
+bitflags::bitflags! {
+    /// DAC FIFO status.
+    pub struct Status : u32 {
+        /// The FIFO is full.
+        const FULL = 1 << 0;
+        /// The FIFO is nearly empty; it holds one value.
+        ///
+        /// This is a fixed level. Use [`WATERMARK`](Self::WATERMARK)
+        /// for the configurable watermark level.
+        const NEARLY_EMPTY = 1 << 1;
+        /// The FIFO is at, or below, the watermark.
+        const WATERMARK = 1 << 2;
+        /// A trigger happened when the FIFO was empty.
+        const UNDERFLOW = 1 << 3;
+        /// A value was written when the FIFO was full.
+        const OVERFLOW = 1 << 4;
+    }
+}
+
+impl Dac {
+    /// Take ownership of the DAC.
+    ///
+    /// This resets the DAC, which disables it, selects buffer mode,
+    /// and empties the FIFO. Make sure that the DAC's clock is enabled.
+    pub fn new(dac: ral::dac::DAC) -> Self {
+        ral::write_reg!(ral::dac, dac, CR, SWRST: 1);
+        ral::write_reg!(ral::dac, dac, CR, 0);
+        Self { dac }
+    }
+
+    /// Release the DAC instance.
+    ///
+    /// This doesn't change the DAC.
+    pub fn release(self) -> ral::dac::DAC {
+        self.dac
+    }
+
+    /// Enable (`true`) or disable (`false`) the DAC.
+    pub fn set_enable(&mut self, enable: bool) {
+        ral::modify_reg!(ral::dac, self.dac, CR, DACEN: enable as u32);
+    }
+
+    /// Returns `true` if the DAC is enabled.
+    pub fn is_enabled(&self) -> bool {
+        ral::read_reg!(ral::dac, self.dac, CR, DACEN == 1)
+    }
+
+    /// Select the reference voltage.
+    ///
+    /// Select the reference while the DAC is disabled.
+    pub fn set_reference(&mut self, reference: Reference) {
+        ral::modify_reg!(ral::dac, self.dac, CR, DACRFS: (reference == Reference::Ref2) as u32);
+    }
+
+    /// Connect (`true`) or disconnect (`false`) the DAC output and its pad.
+    pub fn set_output_enable(&mut self, enable: bool) {
+        ral::modify_reg!(ral::dac, self.dac, CR2, OEN: enable as u32);
+    }
+
+    /// Write a value to the DAC.
+    ///
+    /// In buffer mode, the output changes right away. In FIFO modes, the
+    /// value enters the FIFO. `value` is clamped to [`MAX_VALUE`].
+    pub fn set_value(&mut self, value: u16) {
+        ral::write_reg!(ral::dac, self.dac, DATA, value.min(MAX_VALUE) as u32);
+    }
+
+    /// Select how the DAC uses written values.
+    ///
+    /// Select the mode while the DAC is disabled.
+    pub fn set_mode(&mut self, mode: Mode) {
+        let (fifoen, swmd) = match mode {
+            Mode::Buffer => (0, 0),
+            Mode::Fifo => (1, 0),
+            Mode::SwingBack => (1, 1),
+        };
+        ral::modify_reg!(ral::dac, self.dac, CR, FIFOEN: fifoen, SWMD: swmd);
+    }
+
+    /// Returns the mode.
+    pub fn mode(&self) -> Mode {
+        match ral::read_reg!(ral::dac, self.dac, CR, FIFOEN, SWMD) {
+            (0, _) => Mode::Buffer,
+            (_, 0) => Mode::Fifo,
+            _ => Mode::SwingBack,
+        }
+    }
+
+    /// Select the trigger that advances the FIFO.
+    pub fn set_trigger(&mut self, trigger: Trigger) {
+        ral::modify_reg!(ral::dac, self.dac, CR, TRGSEL: (trigger == Trigger::Software) as u32);
+    }
+
+    /// Advance the FIFO with a software trigger.
+    ///
+    /// This has no effect unless you selected [`Trigger::Software`].
+    pub fn trigger(&mut self) {
+        ral::modify_reg!(ral::dac, self.dac, CR, SWTRG: 1);
+    }
+
+    /// Returns the FIFO depth, in values.
+    pub fn fifo_size(&self) -> usize {
+        1 << (ral::read_reg!(ral::dac, self.dac, PARAM, FIFOSZ) + 1)
+    }
+
+    /// Set the FIFO watermark.
+    ///
+    /// The watermark status, and DMA requests, happen when the number
+    /// of values in the FIFO is at, or below, the watermark.
+    pub fn set_watermark(&mut self, watermark: u8) {
+        ral::modify_reg!(ral::dac, self.dac, CR, WML: watermark as u32);
+    }
+
+    /// Returns the FIFO status.
+    pub fn status(&self) -> Status {
+        Status::from_bits_truncate(ral::read_reg!(ral::dac, self.dac, CR))
+    }
+
+    /// Empty the FIFO, and clear the FIFO status.
+    pub fn reset_fifo(&mut self) {
+        ral::modify_reg!(ral::dac, self.dac, CR, FIFORST: 1);
+    }
+
+    /// Enable (`true`) or disable (`false`) DMA requests.
+    ///
+    /// The DAC requests data when the FIFO is at, or below, the watermark.
+    pub fn set_dma_enable(&mut self, enable: bool) {
+        ral::modify_reg!(ral::dac, self.dac, CR, DMAEN: enable as u32);
+    }
+
+    /// Returns `true` if DMA requests are enabled.
+    pub fn is_dma_enabled(&self) -> bool {
+        ral::read_reg!(ral::dac, self.dac, CR, DMAEN == 1)
+    }
+}
+
+/// Adapter for using the DAC as a DMA destination.
+///
+/// This adapter exposes the lower-level DMA interface. However, you may
+/// find it easier to use the interface available in [`dma`](crate::dma).
+///
+/// The DAC requests data when its FIFO is at, or below, the watermark.
+/// Use a FIFO mode, and a watermark that's less than the FIFO size.
+pub struct DmaDestination {
+    dac: Dac,
+    request: u32,
+}
+
+impl DmaDestination {
+    /// Create a DMA destination for the DAC.
+    ///
+    /// `request` is the DAC's DMA MUX request source. Find it in your
+    /// chip's reference manual, in the DMA MUX mapping table.
+    pub fn new(dac: Dac, request: u32) -> Self {
+        Self { dac, request }
+    }
+
+    /// Returns the DAC's DMA MUX request source.
+    pub fn request(&self) -> u32 {
+        self.request
+    }
+
+    /// Returns a pointer to the DAC's `DATA` register.
+    ///
+    /// You should use this pointer when coordinating a DMA transfer.
+    /// You're not expected to explicitly write to this pointer in software.
+    pub fn data(&self) -> *const ral::WORegister<u32> {
+        core::ptr::addr_of!(self.dac.dac.DATA)
+    }
+
+    /// Access the DAC.
+    pub fn dac(&self) -> &Dac {
+        &self.dac
+    }
+
+    /// Mutably access the DAC.
+    pub fn dac_mut(&mut self) -> &mut Dac {
+        &mut self.dac
+    }
+
+    /// Release the DAC.
+    ///
+    /// DMA requests stay in their current state.
+    pub fn release(self) -> Dac {
+        self.dac
+    }
+}
@@ -0,0 +1,213 @@
+//! WS2812 smart LEDs.
+//!
+//! [`SmartLed`] drives a chain of WS2812 LEDs, sometimes called NeoPixels,
+//! with an LPSPI and a DMA channel. The DMA channel feeds the LPSPI, so the
+//! LED timing doesn't depend on the CPU.
+//!
+//! The driver encodes each LED data bit as four SPI bits, clocked at
+//! [`SPI_CLOCK_HZ`]. A zero is `1000`, and a one is `1100`. This produces
+//! a 333ns high time for zeros, a 667ns high time for ones, and a 1.33us
+//! bit period. After the LED data, the driver sends [`RESET_WORDS`] of
+//! zeros, holding the data line low long enough for the LEDs to latch
+//! their new colors.
+//!
+//! Connect the LPSPI's SDO pin to the data input of the first LED. The driver
+//! doesn't use the other LPSPI pins. WS2812 LEDs may need a level shifter
+//! to recognize a 3.3V data signal.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//! use hal::smartled::{self, Rgb8, SmartLed};
+//!
+//! # async fn f() -> Result<(), smartled::SmartLedError> {
+//! const LPSPI_CLK_FREQUENCY: u32 = 528_000_000 / 8;
+//! const LEDS: usize = 8;
+//!
+//! let lpspi4 = hal::lpspi::Lpspi::without_pins(unsafe { ral::lpspi::LPSPI4::instance() });
+//! let mut leds = SmartLed::new(lpspi4, LPSPI_CLK_FREQUENCY);
+//! let mut channel = // DMA channel 7...
+//!     # unsafe { hal::dma::DMA.channel(7) };
+//!
+//! let mut buffer = [0u32; smartled::buffer_len(LEDS)];
+//! let colors = [Rgb8::new(0, 0, 32); LEDS];
+//! leds.write(&mut channel, &colors, &mut buffer).await?;
+//! # Ok(()) }
+//! ```
+
+use crate::dma::channel::Channel;
+use crate::lpspi::{BitOrder, Lpspi, LpspiError};
+
+/// The LPSPI clock speed, in Hz.
+pub const SPI_CLOCK_HZ: u32 = 3_000_000;
+
+/// The number of SPI words for each LED.
+///
+/// Each LED receives 24 data bits, and each data bit is four SPI bits.
+pub const WORDS_PER_LED: usize = 3;
+
+/// The time that the data line stays low after the LED data, in microseconds.
+///
+/// Older WS2812 LEDs latch after 50us. Newer LEDs need up to 280us.
+const RESET_US: u32 = 300;
+
+/// The number of zero SPI words that follow the LED data.
+pub const RESET_WORDS: usize = ((RESET_US * (SPI_CLOCK_HZ / 1_000_000)) as usize).div_ceil(32);
+
+/// The largest number of words in one LPSPI transaction.
+const MAX_TRANSACTION_WORDS: usize = 128;
+
+/// The SPI bits for a zero data bit.
+const ZERO: u32 = 0b1000;
+/// The SPI bits for a one data bit.
+const ONE: u32 = 0b1100;
+
+/// Returns the buffer length, in words, that holds the encoded data
+/// for `leds` LEDs.
+pub const fn buffer_len(leds: usize) -> usize {
+    leds * WORDS_PER_LED + RESET_WORDS
+}
+
+/// An LED color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb8 {
+    /// Red.
+    pub r: u8,
+    /// Green.
+    pub g: u8,
+    /// Blue.
+    pub b: u8,
+}
+
+impl Rgb8 {
+    /// Create a color from its components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// Errors when writing LED colors.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum SmartLedError {
+    /// The buffer can't hold the encoded colors.
+    ///
+    /// See [`buffer_len`].
+    BufferTooSmall,
+    /// The LPSPI couldn't start the transfer.
+    Lpspi(LpspiError),
+    /// The DMA channel reported an error.
+    Dma(crate::dma::Error),
+}
+
+/// Encode one byte of LED data.
+const fn encode_byte(byte: u8) -> u32 {
+    let mut word = 0;
+    let mut bit = 0;
+    while bit < 8 {
+        let pattern = if byte & (0x80 >> bit) != 0 { ONE } else { ZERO };
+        word = (word << 4) | pattern;
+        bit += 1;
+    }
+    word
+}
+
+/// Encode `colors` into `buffer`, followed by the reset words.
+///
+/// Returns the number of words written, or `None` if the buffer is
+/// too small.
+fn encode(colors: &[Rgb8], buffer: &mut [u32]) -> Option<usize> {
+    let len = buffer_len(colors.len());
+    let buffer = buffer.get_mut(..len)?;
+    let (data, reset) = buffer.split_at_mut(colors.len() * WORDS_PER_LED);
+    for (words, color) in data.chunks_exact_mut(WORDS_PER_LED).zip(colors) {
+        // WS2812 LEDs expect green, then red, then blue.
+        words[0] = encode_byte(color.g);
+        words[1] = encode_byte(color.r);
+        words[2] = encode_byte(color.b);
+    }
+    reset.fill(0);
+    Some(len)
+}
+
+/// A chain of WS2812 LEDs.
+///
+/// See the [module-level documentation](crate::smartled) for more information.
+pub struct SmartLed<P, const N: u8> {
+    lpspi: Lpspi<P, N>,
+}
+
+impl<P, const N: u8> SmartLed<P, N> {
+    /// Create a LED driver.
+    ///
+    /// `source_clock_hz` is the LPSPI peripheral clock speed. This sets the
+    /// LPSPI clock to [`SPI_CLOCK_HZ`], and it sends the most significant
+    /// bit first.
+    pub fn new(mut lpspi: Lpspi<P, N>, source_clock_hz: u32) -> Self {
+        lpspi.disabled(|spi| spi.set_clock_hz(source_clock_hz, SPI_CLOCK_HZ));
+        lpspi.set_bit_order(BitOrder::Msb);
+        Self { lpspi }
+    }
+
+    /// Release the LPSPI driver.
+    pub fn release(self) -> Lpspi<P, N> {
+        self.lpspi
+    }
+
+    /// Send `colors` to the LEDs.
+    ///
+    /// `colors[0]` is the color of the first LED in the chain. This encodes
+    /// the colors into `buffer`, then uses the DMA channel to send the buffer.
+    /// `buffer` must hold at least [`buffer_len`] words for the number of
+    /// colors.
+    ///
+    /// The future completes when the DMA channel moves the last word into the
+    /// LPSPI. The LPSPI may still be sending the reset words, so the LEDs may
+    /// not show their colors yet. You may write the next colors right away.
+    pub async fn write(
+        &mut self,
+        channel: &mut Channel,
+        colors: &[Rgb8],
+        buffer: &mut [u32],
+    ) -> Result<(), SmartLedError> {
+        let len = encode(colors, buffer).ok_or(SmartLedError::BufferTooSmall)?;
+        // The data line stays low between transactions, which
+        // only stretches the low time of the previous bit.
+        for chunk in buffer[..len].chunks(MAX_TRANSACTION_WORDS) {
+            self.lpspi
+                .dma_write(channel, chunk)
+                .map_err(SmartLedError::Lpspi)?
+                .await
+                .map_err(SmartLedError::Dma)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{buffer_len, encode, encode_byte, Rgb8, RESET_WORDS};
+
+    #[test]
+    fn encode_bytes() {
+        assert_eq!(encode_byte(0x00), 0x8888_8888);
+        assert_eq!(encode_byte(0xFF), 0xCCCC_CCCC);
+        assert_eq!(encode_byte(0xA5), 0xC8C8_8C8C);
+    }
+
+    #[test]
+    fn encode_colors() {
+        assert_eq!(RESET_WORDS, 29);
+        let mut buffer = [u32::MAX; buffer_len(2) + 1];
+        let colors = [Rgb8::new(0xFF, 0x00, 0x0F), Rgb8::new(0, 0, 0)];
+        assert_eq!(encode(&colors, &mut buffer), Some(buffer_len(2)));
+        assert_eq!(buffer[..3], [0x8888_8888, 0xCCCC_CCCC, 0x8888_CCCC]);
+        assert_eq!(buffer[3..6], [0x8888_8888; 3]);
+        assert!(buffer[6..buffer_len(2)].iter().all(|&word| word == 0));
+        assert_eq!(buffer[buffer_len(2)], u32::MAX);
+
+        assert_eq!(encode(&colors, &mut buffer[..buffer_len(2) - 1]), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! System reset controller.
+//!
+//! Use these APIs to learn why the processor reset. The reset
+//! cause is sticky; it accumulates across warm resets until you
+//! clear it. Clear the reset cause after you've read it, so that the
+//! next boot only observes the newest reset reasons.
+//!
+//! # Example
+//!
+//! Check if the watchdog reset the processor, then clear the reset
+//! cause.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::src::{self, ResetCause};
+//!
+//! let mut src = unsafe { ral::src::SRC::instance() };
+//! let cause = src::reset_cause(&src);
+//! if cause.intersects(ResetCause::WATCHDOG) {
+//!     // Enter a safe mode...
+//! }
+//! src::clear_reset_cause(&mut src, cause);
+//! ```
+//!
+//! Use [`reset()`](crate::reset) to reset the system. Use [`reset_to_bootloader()`]
+//! to enter the ROM bootloader's serial downloader.
+
+use crate::ral;
+
+#[cfg(family = "imxrt10xx")]
+bitflags::bitflags! {
+    /// The reasons for the most recent reset(s).
+    ///
+    /// More than one flag may be set. For instance, a power-on
+    /// reset also indicates that the reset pin was asserted.
+    pub struct ResetCause : u32 {
+        /// Power-on reset, or reset via the POR_B pin.
+        const POWER_ON = 1 << 0;
+        /// Core lockup, or software reset through the
+        /// application interrupt and reset control register.
+        const LOCKUP_SYSRESETREQ = 1 << 1;
+        /// Reset from the central security unit.
+        const CSU = 1 << 2;
+        /// Reset from the user reset pin.
+        const USER = 1 << 3;
+        /// Watchdog timeout, from WDOG1 or WDOG2.
+        const WDOG = 1 << 4;
+        /// JTAG reset.
+        const JTAG = 1 << 5;
+        /// Software reset through the JTAG interface.
+        const JTAG_SOFTWARE = 1 << 6;
+        /// Watchdog timeout from WDOG3.
+        const WDOG3 = 1 << 7;
+        /// Temperature sensor reset.
+        const TEMPERATURE_SENSOR = 1 << 8;
+
+        /// Any watchdog timeout.
+        const WATCHDOG = Self::WDOG.bits | Self::WDOG3.bits;
+    }
+}
+
+#[cfg(family = "imxrt11xx")]
+bitflags::bitflags! {
+    /// The reasons for the most recent reset(s).
+    ///
+    /// More than one flag may be set. For instance, a power-on
+    /// reset also indicates that the reset pin was asserted.
+    ///
+    /// These flags represent the reset reasons recorded for the
+    /// Cortex-M7 core.
+    pub struct ResetCause : u32 {
+        /// Power-on reset, or reset via the POR_B pin.
+        const POWER_ON = 1 << 0;
+        /// Software reset requested by the Cortex-M7.
+        const M7_REQUEST = 1 << 1;
+        /// Cortex-M7 lockup.
+        const M7_LOCKUP = 1 << 2;
+        /// Reset from the central security unit.
+        const CSU = 1 << 3;
+        /// Reset from the user reset pin.
+        const USER = 1 << 4;
+        /// Watchdog timeout, from WDOG1 or WDOG2.
+        const WDOG = 1 << 5;
+        /// JTAG reset.
+        const JTAG = 1 << 6;
+        /// Software reset through the JTAG interface.
+        const JTAG_SOFTWARE = 1 << 7;
+        /// Watchdog timeout from WDOG3.
+        const WDOG3 = 1 << 8;
+        /// Watchdog timeout from WDOG4.
+        const WDOG4 = 1 << 9;
+        /// Temperature sensor reset.
+        const TEMPERATURE_SENSOR = 1 << 10;
+        /// Software reset requested by the Cortex-M4.
+        const M4_REQUEST = 1 << 11;
+        /// Cortex-M4 lockup.
+        const M4_LOCKUP = 1 << 12;
+        /// Over-voltage reset.
+        const OVERVOLTAGE = 1 << 13;
+        /// Code watchdog reset.
+        const CDOG = 1 << 14;
+
+        /// Any watchdog timeout.
+        const WATCHDOG = Self::WDOG.bits | Self::WDOG3.bits | Self::WDOG4.bits | Self::CDOG.bits;
+    }
+}
+
+/// Returns the reasons for the most recent reset(s).
+///
+/// The returned flags accumulate across resets until you
+/// [clear them](clear_reset_cause).
+pub fn reset_cause(src: &ral::src::SRC) -> ResetCause {
+    ResetCause::from_bits_truncate(ral::read_reg!(ral::src, src, SRSR))
+}
+
+/// Clear the reset cause flags.
+///
+/// Only the flags set in `cause` are cleared. Use [`ResetCause::all()`]
+/// to clear all flags.
+pub fn clear_reset_cause(src: &mut ral::src::SRC, cause: ResetCause) {
+    ral::write_reg!(ral::src, src, SRSR, cause.bits());
+}
+
+/// Clear all reset cause flags.
+///
+/// This is the same as calling [`clear_reset_cause`] with
+/// [`ResetCause::all()`].
+pub fn clear_reset_flags(src: &mut ral::src::SRC) {
+    clear_reset_cause(src, ResetCause::all());
+}
+
+/// Location of the pointer to the ROM bootloader API tree.
+#[cfg(family = "imxrt10xx")]
+const ROM_API_TREE: *const *const usize = 0x0020_001C as _;
+/// Location of the pointer to the ROM bootloader API tree.
+#[cfg(family = "imxrt11xx")]
+const ROM_API_TREE: *const *const usize = 0x0021_001C as _;
+
+/// The index of the `runBootloader` function in the ROM API tree.
+///
+/// The tree starts with the ROM version and the copyright string.
+const RUN_BOOTLOADER_INDEX: usize = 2;
+
+/// `runBootloader` argument that selects the serial downloader, and
+/// automatically detects the serial interface.
+///
+/// The fields are the tag (`0xEB`), boot mode (1, serial downloader),
+/// serial interface (0, auto detect), and image index (0).
+const SERIAL_DOWNLOADER: u32 = 0xEB10_0000;
+
+/// Enter the ROM bootloader's serial downloader.
+///
+/// The serial downloader waits for a host to send new firmware over
+/// USB or UART. Use this to start a firmware update from your application.
+///
+/// This uses the boot ROM's bootloader API. The ROM takes control of the
+/// processor, then enters the serial downloader, independent of the boot
+/// mode pins and fuses. Since the ROM selects the boot mode itself, there's
+/// no need to stage a value in a persistent register, like the SRC general
+/// purpose registers or the SNVS low-power general purpose register, before
+/// the reset.
+///
+/// This call disables interrupts before entering the ROM.
+/// It never returns.
+pub fn reset_to_bootloader() -> ! {
+    cortex_m::interrupt::disable();
+    let arg = SERIAL_DOWNLOADER;
+    // Safety: the API tree location, and the function's location in that tree,
+    // is correct for this chip family. The API tree is in ROM, so it's always
+    // valid to read.
+    unsafe {
+        let tree = ROM_API_TREE.read_volatile();
+        let run_bootloader = tree.add(RUN_BOOTLOADER_INDEX).read_volatile();
+        let run_bootloader: extern "C" fn(*const u32) = core::mem::transmute(run_bootloader);
+        run_bootloader(&arg);
+    }
+    // The ROM doesn't return. If it does, reset.
+    crate::reset()
+}
@@ -0,0 +1,120 @@
+//! Cycle-accurate delays.
+//!
+//! [`CycleDelay`] uses the Cortex-M DWT cycle counter for busy-wait delays.
+//! It resolves delays to a single core clock cycle, so it's useful for the
+//! sub-microsecond pulses that a [PIT](crate::pit) or [GPT](crate::gpt)
+//! can't produce. The delay blocks the CPU, and interrupts that fire during
+//! the delay only lengthen it.
+//!
+//! `CycleDelay` needs the core clock frequency, `HZ`, to turn durations into
+//! cycles. If the core clock changes, the delays are wrong.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use eh1::delay::DelayNs;
+//!
+//! const CORE_CLOCK_HZ: u32 = 600_000_000;
+//!
+//! let mut cortex_m = cortex_m::Peripherals::take().unwrap();
+//! let mut delay = hal::delay::CycleDelay::<CORE_CLOCK_HZ>::new(&mut cortex_m.DCB, cortex_m.DWT);
+//!
+//! // Hold a reset line for at least 100ns.
+//! delay.delay_ns(100);
+//!
+//! let start = delay.cycles();
+//! delay.delay_cycles(60);
+//! assert!(delay.cycles().wrapping_sub(start) >= 60);
+//! ```
+
+use cortex_m::peripheral::{DCB, DWT};
+
+/// A busy-wait delay that counts core clock cycles.
+///
+/// `HZ` is the core clock frequency. See the [module-level documentation](crate::delay)
+/// for more information.
+pub struct CycleDelay<const HZ: u32> {
+    dwt: DWT,
+}
+
+impl<const HZ: u32> CycleDelay<HZ> {
+    /// Enable the DWT cycle counter, and create a delay.
+    ///
+    /// This enables tracing in the DCB, unlocks the DWT, and starts the
+    /// cycle counter. The cycle counter keeps counting from its current
+    /// value.
+    pub fn new(dcb: &mut DCB, mut dwt: DWT) -> Self {
+        dcb.enable_trace();
+        DWT::unlock();
+        dwt.enable_cycle_counter();
+        Self { dwt }
+    }
+
+    /// Release the DWT.
+    ///
+    /// The cycle counter keeps running.
+    pub fn release(self) -> DWT {
+        self.dwt
+    }
+
+    /// Returns the cycle counter.
+    ///
+    /// The counter increments once per core clock cycle, and it wraps
+    /// around. Use `wrapping_sub` to compute the cycles between two readings.
+    pub fn cycles(&self) -> u32 {
+        DWT::cycle_count()
+    }
+
+    /// Block for at least `cycles` core clock cycles.
+    pub fn delay_cycles(&self, cycles: u32) {
+        let start = DWT::cycle_count();
+        while DWT::cycle_count().wrapping_sub(start) < cycles {}
+    }
+
+    /// Block for at least `cycles` core clock cycles, which may exceed
+    /// the cycle counter's range.
+    fn delay_long(&self, mut cycles: u64) {
+        while cycles > 0 {
+            let chunk = cycles.min(u32::MAX as u64);
+            self.delay_cycles(chunk as u32);
+            cycles -= chunk;
+        }
+    }
+}
+
+/// Returns the number of `hz` clock cycles in `time` units, where each
+/// unit is `1 / per_second` seconds.
+///
+/// Rounds up, so that delays are never shorter than requested.
+const fn cycles(time: u32, per_second: u64, hz: u32) -> u64 {
+    (time as u64 * hz as u64).div_ceil(per_second)
+}
+
+impl<const HZ: u32> eh1::delay::DelayNs for CycleDelay<HZ> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_long(cycles(ns, 1_000_000_000, HZ));
+    }
+    fn delay_us(&mut self, us: u32) {
+        self.delay_long(cycles(us, 1_000_000, HZ));
+    }
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_long(cycles(ms, 1_000, HZ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cycles;
+
+    #[test]
+    fn duration_to_cycles() {
+        const HZ: u32 = 600_000_000;
+        assert_eq!(cycles(100, 1_000_000_000, HZ), 60);
+        assert_eq!(cycles(1, 1_000_000_000, HZ), 1);
+        assert_eq!(cycles(0, 1_000_000_000, HZ), 0);
+        assert_eq!(cycles(1, 1_000_000_000, 24_000_000), 1);
+        assert_eq!(cycles(1, 1_000_000, HZ), 600);
+        assert_eq!(cycles(u32::MAX, 1_000, HZ), u32::MAX as u64 * 600_000);
+    }
+}
@@ -0,0 +1,39 @@
+//! Direct memory access.
+//!
+//! This module re-exports the `imxrt-dma` package, and extends it with
+//! HAL-specific helpers.
+
+pub use imxrt_dma::*;
+
+mod buffer;
+mod chunked;
+mod dmamux;
+mod fill;
+mod major_loop;
+mod minor_loop;
+mod periodic;
+mod queue;
+mod ring;
+mod scoped;
+mod tcd;
+mod transfer_config;
+
+pub use buffer::{LinearBuffer, LinearBufferMut, MAX_TRANSFER_ELEMENTS};
+pub use chunked::{chunked_read, chunked_write};
+pub use dmamux::{
+    disable_request, enable_request, is_always_on, is_periodic_trigger, is_request_enabled,
+    set_always_on, set_periodic_trigger, PERIODIC_TRIGGER_CHANNELS,
+};
+pub use fill::fill;
+pub use major_loop::set_major_iterations;
+pub use minor_loop::{
+    set_minor_loop_offset, MinorLoopOffset, MinorLoopOffsetError, MAX_MINOR_LOOP_OFFSET,
+    MAX_MINOR_LOOP_OFFSET_BYTES, MIN_MINOR_LOOP_OFFSET,
+};
+pub use periodic::{periodic_read, periodic_write};
+pub use queue::{Job, TransferQueue};
+pub use ring::{RingError, RingReader, MAX_RING_ELEMENTS};
+pub use scoped::ScopedChannel;
+pub use tcd::{tcd_snapshot, TcdControl, TcdSnapshot};
+pub(crate) use transfer_config::reset_tcd;
+pub use transfer_config::{configure_transfer, TransferConfig, TransferConfigError, TransferSide};
@@ -0,0 +1,197 @@
+//! Buffers that are checked for DMA transfers.
+
+use super::Element;
+
+/// The maximum number of elements in a single DMA transfer.
+///
+/// The DMA channel's transfer iteration counter is 15 bits wide.
+/// A transfer that uses one minor loop per element cannot move
+/// more than this many elements.
+pub const MAX_TRANSFER_ELEMENTS: usize = (1 << 15) - 1;
+
+/// Returns `true` if a buffer with `len` elements can be transferred
+/// in one DMA transfer.
+const fn is_valid_len(len: usize) -> bool {
+    0 < len && len <= MAX_TRANSFER_ELEMENTS
+}
+
+/// Returns `true` if `bytes` can be reinterpreted as a buffer of `E`.
+///
+/// The transfer control descriptor requires that each address is aligned
+/// to the element size.
+fn is_aligned_for<E: Element>(bytes: &[u8]) -> bool {
+    // Element sizes are powers of two.
+    let mask = core::mem::size_of::<E>() - 1;
+    (bytes.as_ptr() as usize) & mask == 0 && bytes.len() & mask == 0
+}
+
+/// A buffer that's valid for a single linear DMA transfer.
+///
+/// A `LinearBuffer` is never empty, and it never has more than
+/// [`MAX_TRANSFER_ELEMENTS`] elements. Its address is always
+/// aligned for the element size.
+///
+/// Use [`from_bytes`](Self::from_bytes) to safely reinterpret a
+/// byte buffer as a buffer of larger elements. If the byte buffer
+/// isn't aligned for the element, the conversion fails, instead of
+/// producing a transfer that corrupts data.
+///
+/// # Example
+///
+/// ```
+/// use imxrt_hal::dma::LinearBuffer;
+///
+/// static BUFFER: [u16; 4] = [1, 2, 3, 4];
+/// let buffer = LinearBuffer::new(&BUFFER).unwrap();
+/// assert_eq!(buffer.len(), 4);
+///
+/// // Too long for a single transfer.
+/// static TOO_LONG: [u8; 32768] = [0; 32768];
+/// assert!(LinearBuffer::new(&TOO_LONG).is_none());
+///
+/// // A u16 buffer cannot start at an odd address.
+/// #[repr(align(2))]
+/// struct Bytes([u8; 8]);
+/// static BYTES: Bytes = Bytes([0; 8]);
+/// assert!(LinearBuffer::<u16>::from_bytes(&BYTES.0[..4]).is_some());
+/// assert!(LinearBuffer::<u16>::from_bytes(&BYTES.0[1..5]).is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LinearBuffer<'a, E> {
+    buffer: &'a [E],
+}
+
+impl<'a, E: Element> LinearBuffer<'a, E> {
+    /// Check that `buffer` can be used for a single DMA transfer.
+    ///
+    /// Returns `None` if the buffer is empty, or if it's larger than
+    /// [`MAX_TRANSFER_ELEMENTS`].
+    pub const fn new(buffer: &'a [E]) -> Option<Self> {
+        if is_valid_len(buffer.len()) {
+            Some(Self { buffer })
+        } else {
+            None
+        }
+    }
+
+    /// Reinterpret a byte buffer as a buffer of `E` elements.
+    ///
+    /// Returns `None` if the byte buffer's address or length isn't a
+    /// multiple of the element size, or if the resulting buffer isn't
+    /// valid for [`new`](Self::new).
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        if !is_aligned_for::<E>(bytes) {
+            return None;
+        }
+        let len = bytes.len() / core::mem::size_of::<E>();
+        // Safety: address is aligned for E, and the length is within the
+        // byte buffer. E is an unsigned integer, so every bit pattern is valid.
+        let buffer = unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), len) };
+        Self::new(buffer)
+    }
+
+    /// Returns the number of elements in the buffer.
+    ///
+    /// This is never zero.
+    #[allow(clippy::len_without_is_empty)] // Never empty.
+    pub const fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the checked buffer.
+    pub const fn as_slice(&self) -> &'a [E] {
+        self.buffer
+    }
+}
+
+/// A mutable buffer that's valid for a single linear DMA transfer.
+///
+/// This is the mutable equivalent of [`LinearBuffer`]; it has
+/// the same guarantees.
+#[derive(Debug)]
+pub struct LinearBufferMut<'a, E> {
+    buffer: &'a mut [E],
+}
+
+impl<'a, E: Element> LinearBufferMut<'a, E> {
+    /// Check that `buffer` can be used for a single DMA transfer.
+    ///
+    /// Returns `None` if the buffer is empty, or if it's larger than
+    /// [`MAX_TRANSFER_ELEMENTS`].
+    pub fn new(buffer: &'a mut [E]) -> Option<Self> {
+        if is_valid_len(buffer.len()) {
+            Some(Self { buffer })
+        } else {
+            None
+        }
+    }
+
+    /// Reinterpret a mutable byte buffer as a buffer of `E` elements.
+    ///
+    /// Returns `None` if the byte buffer's address or length isn't a
+    /// multiple of the element size, or if the resulting buffer isn't
+    /// valid for [`new`](Self::new).
+    pub fn from_bytes(bytes: &'a mut [u8]) -> Option<Self> {
+        if !is_aligned_for::<E>(bytes) {
+            return None;
+        }
+        let len = bytes.len() / core::mem::size_of::<E>();
+        // Safety: address is aligned for E, and the length is within the
+        // byte buffer. E is an unsigned integer, so every bit pattern is valid.
+        // We hold the unique borrow of the bytes.
+        let buffer = unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), len) };
+        Self::new(buffer)
+    }
+
+    /// Returns the number of elements in the buffer.
+    ///
+    /// This is never zero.
+    #[allow(clippy::len_without_is_empty)] // Never empty.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the checked buffer.
+    pub fn as_mut_slice(&mut self) -> &mut [E] {
+        self.buffer
+    }
+
+    /// Release the checked buffer.
+    pub fn into_inner(self) -> &'a mut [E] {
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinearBuffer, LinearBufferMut, MAX_TRANSFER_ELEMENTS};
+
+    #[repr(align(4))]
+    struct Aligned([u8; 16]);
+
+    #[test]
+    fn length() {
+        assert!(LinearBuffer::<u8>::new(&[]).is_none());
+        assert!(LinearBuffer::new(&[0u8; MAX_TRANSFER_ELEMENTS]).is_some());
+        assert!(LinearBuffer::new(&[0u8; MAX_TRANSFER_ELEMENTS + 1]).is_none());
+        assert!(LinearBufferMut::new(&mut [0u32; 1]).is_some());
+    }
+
+    #[test]
+    fn from_bytes() {
+        let mut bytes = Aligned([0; 16]);
+        assert_eq!(
+            LinearBuffer::<u32>::from_bytes(&bytes.0).map(|buf| buf.len()),
+            Some(4)
+        );
+        assert_eq!(
+            LinearBuffer::<u16>::from_bytes(&bytes.0[2..]).map(|buf| buf.len()),
+            Some(7)
+        );
+        assert!(LinearBuffer::<u16>::from_bytes(&bytes.0[1..5]).is_none());
+        assert!(LinearBuffer::<u32>::from_bytes(&bytes.0[2..10]).is_none());
+        assert!(LinearBuffer::<u32>::from_bytes(&bytes.0[..6]).is_none());
+        assert!(LinearBufferMut::<u16>::from_bytes(&mut bytes.0[3..7]).is_none());
+        assert!(LinearBufferMut::<u16>::from_bytes(&mut bytes.0[4..8]).is_some());
+    }
+}
@@ -0,0 +1,93 @@
+//! Transfers that are larger than one DMA transfer.
+
+use core::marker::PhantomData;
+
+use super::{
+    channel::{self, Channel, Configuration},
+    peripheral::{self, Destination, Source},
+    reset_tcd, Element, Result, Transfer, MAX_TRANSFER_ELEMENTS,
+};
+
+/// Use a DMA channel to send a buffer of any length to a peripheral.
+///
+/// A single DMA transfer moves at most [`MAX_TRANSFER_ELEMENTS`]. If
+/// `buffer` is longer than that, this splits the buffer into chunks,
+/// and transfers the chunks one after another. The future resolves after
+/// the peripheral receives the last element, or when any chunk fails.
+///
+/// There's a brief pause between chunks while the channel re-arms. The
+/// peripheral holds its DMA request until the channel services it, so
+/// no elements are lost.
+pub async fn chunked_write<D, E>(
+    channel: &mut Channel,
+    buffer: &[E],
+    destination: &mut D,
+) -> Result<()>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    reset_tcd(channel);
+    for chunk in buffer.chunks(MAX_TRANSFER_ELEMENTS) {
+        peripheral::write(channel, chunk, destination).await?;
+    }
+    Ok(())
+}
+
+/// Use a DMA channel to receive a buffer of any length from a peripheral.
+///
+/// This is the receive equivalent of [`chunked_write`]. The future
+/// resolves when `buffer` is full, or when any chunk fails.
+///
+/// The peripheral's DMA request stays enabled for the whole buffer. While
+/// the channel re-arms between chunks, the peripheral holds its request,
+/// and its receive FIFO keeps collecting data.
+pub async fn chunked_read<S, E>(
+    channel: &mut Channel,
+    source: &mut S,
+    buffer: &mut [E],
+) -> Result<()>
+where
+    S: Source<E>,
+    E: Element,
+{
+    struct DisableOnDrop<'a, S: Source<E>, E: Element> {
+        channel: &'a mut Channel,
+        source: &'a mut S,
+        _elem: PhantomData<E>,
+    }
+    impl<S: Source<E>, E: Element> Drop for DisableOnDrop<'_, S, E> {
+        fn drop(&mut self) {
+            self.source.disable_source();
+            while self.channel.is_hardware_signaling() {}
+        }
+    }
+
+    channel.disable();
+    reset_tcd(channel);
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+    source.enable_source();
+    let guard = DisableOnDrop {
+        channel,
+        source,
+        _elem: PhantomData,
+    };
+
+    for chunk in buffer.chunks_mut(MAX_TRANSFER_ELEMENTS) {
+        // Safety: the hardware source address is valid, otherwise the Source
+        // impl is unsound. The chunk is no longer than one transfer, and the
+        // future borrows it for the duration of the transfer.
+        unsafe {
+            channel::set_source_hardware(guard.channel, guard.source.source_address());
+            channel::set_destination_linear_buffer(guard.channel, chunk);
+            guard
+                .channel
+                .set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            guard.channel.set_transfer_iterations(chunk.len() as u16);
+        }
+        // Safety: transfer is described above.
+        unsafe { Transfer::new(guard.channel) }.await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! DMAMUX channel settings.
+//!
+//! `set_channel_configuration` writes the channel's whole DMAMUX
+//! configuration, and its `Configuration` can't express every combination
+//! of settings. These functions modify one DMAMUX setting, and keep the
+//! others. Call them after `set_channel_configuration`, since
+//! `set_channel_configuration` replaces these settings.
+
+use super::channel::Channel;
+use crate::ral;
+
+/// The number of DMA channels that support periodic triggering.
+///
+/// PIT channel `n` triggers DMA channel `n`.
+pub const PERIODIC_TRIGGER_CHANNELS: usize = 4;
+
+/// Returns the DMAMUX registers.
+fn dmamux() -> &'static ral::dmamux::RegisterBlock {
+    // Safety: static peripheral memory. Callers exclusively borrow the
+    // channel, so nothing else writes its DMAMUX configuration.
+    unsafe { &*ral::dmamux::DMAMUX }
+}
+
+/// Enable the DMAMUX channel's request routing.
+///
+/// The channel resumes receiving requests from the source that it had
+/// before [`disable_request`]. This doesn't change the source, or any other
+/// DMAMUX setting.
+pub fn enable_request(channel: &mut Channel) {
+    ral::modify_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], ENBL: 1);
+}
+
+/// Disable the DMAMUX channel's request routing.
+///
+/// The DMA channel stops receiving hardware requests, but the DMAMUX keeps the
+/// channel's source. Use this to pause a peripheral transfer while you
+/// reconfigure the peripheral, then call [`enable_request`] to resume.
+///
+/// The DMA channel may still finish a minor loop that started before this
+/// call.
+pub fn disable_request(channel: &mut Channel) {
+    ral::modify_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], ENBL: 0);
+}
+
+/// Returns `true` if the DMAMUX channel routes requests to the DMA channel.
+pub fn is_request_enabled(channel: &Channel) -> bool {
+    ral::read_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], ENBL == 1)
+}
+
+/// Enable the DMAMUX channel, and keep its request always asserted.
+///
+/// The DMAMUX ignores the channel's source. The channel runs as soon as you
+/// enable it, or, when [periodic triggering](set_periodic_trigger) is
+/// enabled, each time its PIT channel elapses. Use this for memory-to-memory
+/// transfers, and for timer-paced transfers.
+pub fn set_always_on(channel: &mut Channel) {
+    ral::modify_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], ENBL: 1, A_ON: 1);
+}
+
+/// Returns `true` if the DMAMUX channel's request is always asserted.
+pub fn is_always_on(channel: &Channel) -> bool {
+    ral::read_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], A_ON == 1)
+}
+
+/// Enable (`true`) or disable (`false`) periodic triggering.
+///
+/// When enabled, the DMAMUX only passes the channel's request when PIT
+/// channel `n` elapses, where `n` is the DMA channel number. Combine this
+/// with [`set_always_on`] to run one minor loop each PIT period.
+///
+/// # Panics
+///
+/// Panics if `enable` is `true`, and the DMA channel doesn't support
+/// periodic triggering. See [`PERIODIC_TRIGGER_CHANNELS`].
+pub fn set_periodic_trigger(channel: &mut Channel, enable: bool) {
+    assert!(
+        !enable || channel.channel() < PERIODIC_TRIGGER_CHANNELS,
+        "DMA channel {} doesn't support periodic triggering",
+        channel.channel()
+    );
+    ral::modify_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], TRIG: enable as u32);
+}
+
+/// Returns `true` if periodic triggering is enabled.
+pub fn is_periodic_trigger(channel: &Channel) -> bool {
+    ral::read_reg!(ral::dmamux, dmamux(), CHCFG[channel.channel()], TRIG == 1)
+}
@@ -0,0 +1,75 @@
+//! Fill memory with a value.
+
+use super::{
+    channel::{self, Channel},
+    dmamux, reset_tcd, Element, Result, Transfer, MAX_TRANSFER_ELEMENTS,
+};
+
+/// Use a DMA channel to write `value` into every element of `destination`.
+///
+/// This is the DMA equivalent of `destination.fill(value)`. The channel
+/// reads `value` from a fixed address, and writes it into each element of
+/// `destination`. Use this to clear large buffers, like framebuffers,
+/// without occupying the CPU.
+///
+/// The future holds `value`, and the channel reads `value` from the
+/// future. So `value` outlives the transfer, even if you drop the future
+/// before it completes. Keep the future pinned in one place until it
+/// resolves, which `.await` does for you.
+///
+/// The channel runs from an always-on DMAMUX request. Any previous
+/// DMAMUX configuration is replaced. If `destination` is longer than
+/// [`MAX_TRANSFER_ELEMENTS`], this fills the buffer in chunks.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+///
+/// # async fn f() -> hal::dma::Result<()> {
+/// let mut channel = // DMA channel 7...
+///     # unsafe { hal::dma::DMA.channel(7) };
+/// let mut framebuffer = [0u16; 320 * 240];
+///
+/// const BACKGROUND: u16 = 0x001F;
+/// hal::dma::fill(&mut channel, BACKGROUND, &mut framebuffer).await?;
+/// # Ok(()) }
+/// ```
+pub async fn fill<E: Element>(
+    channel: &mut Channel,
+    value: E,
+    destination: &mut [E],
+) -> Result<()> {
+    for chunk in destination.chunks_mut(MAX_TRANSFER_ELEMENTS) {
+        fill_chunk(channel, &value, chunk).await?;
+    }
+    Ok(())
+}
+
+/// Fill a destination that fits within one transfer.
+async fn fill_chunk<E: Element>(
+    channel: &mut Channel,
+    value: &E,
+    destination: &mut [E],
+) -> Result<()> {
+    channel.disable();
+    reset_tcd(channel);
+    channel.set_disable_on_completion(true);
+    channel.set_channel_configuration(channel::Configuration::Off);
+    dmamux::set_always_on(channel);
+
+    // Safety: the value and destination lifetimes are captured by the
+    // future. The source address never changes, so the channel only reads
+    // `value`. The destination's length fits in the transfer iterations.
+    unsafe {
+        channel.set_source_address(value);
+        channel.set_source_offset(0);
+        channel.set_source_attributes::<E>(0);
+        channel.set_source_last_address_adjustment(0);
+        channel::set_destination_linear_buffer(channel, destination);
+        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+        channel.set_transfer_iterations(destination.len() as u16);
+    }
+    // Safety: transfer is correctly defined.
+    unsafe { Transfer::new(channel) }.await
+}
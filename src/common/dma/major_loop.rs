@@ -0,0 +1,71 @@
+//! Major loop interrupts.
+//!
+//! A DMA channel moves one minor loop for each hardware request, and it
+//! counts minor loops in its current iteration count, `CITER`. When `CITER`
+//! reaches zero, the major loop completes. The channel can interrupt, then
+//! it reloads `CITER` from the beginning iteration count, `BITER`. If the
+//! channel isn't disabled on completion, the next request starts the next
+//! major loop.
+//!
+//! The major loop count is independent of the minor loop size. A channel
+//! that moves one ADC sample per minor loop, with 256 major iterations,
+//! interrupts once per 256 samples.
+
+use super::{channel::Channel, transfer_config::check_iterations, TransferConfigError};
+use crate::ral;
+
+/// Set the number of minor loops in each major loop, and interrupt only when
+/// the major loop completes.
+///
+/// This sets both `BITER` and `CITER` to `iterations`, enables the major loop
+/// interrupt, and disables the half major loop interrupt. The channel
+/// interrupts once every `iterations` minor loops. Use this for streaming
+/// transfers, where an interrupt for every minor loop is too frequent.
+///
+/// To keep streaming after each major loop, don't disable the channel on
+/// completion, and adjust the addresses with the last address adjustments,
+/// or use a circular buffer. Your interrupt handler should clear the channel's
+/// interrupt.
+///
+/// Returns an error, without changing the channel, if `iterations` is zero
+/// or larger than [`MAX_TRANSFER_ELEMENTS`](super::MAX_TRANSFER_ELEMENTS).
+///
+/// # Safety
+///
+/// This modifies the transfer control descriptor, so it has the same safety
+/// requirements as `set_transfer_iterations`. Make sure that `iterations`
+/// minor loops stay within your source and destination.
+pub unsafe fn set_major_iterations(
+    channel: &mut Channel,
+    iterations: u16,
+) -> Result<(), TransferConfigError> {
+    check_iterations(iterations)?;
+    // Safety: caller upholds the transfer's memory requirements.
+    unsafe { channel.set_transfer_iterations(iterations) };
+    channel.set_interrupt_on_completion(true);
+    // Safety: static peripheral memory. The channel is exclusively borrowed,
+    // so nothing else writes its TCD.
+    let dma = unsafe { &*ral::dma::DMA };
+    let tcd = &dma.TCD[channel.channel()];
+    ral::modify_reg!(ral::dma::tcd, tcd, TCD_CSR, INTHALF: 0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_iterations, TransferConfigError};
+    use crate::dma::MAX_TRANSFER_ELEMENTS;
+
+    #[test]
+    fn iterations() {
+        assert_eq!(check_iterations(1), Ok(()));
+        assert_eq!(check_iterations(256), Ok(()));
+        assert_eq!(check_iterations(MAX_TRANSFER_ELEMENTS as u16), Ok(()));
+        assert_eq!(check_iterations(0), Err(TransferConfigError::Iterations(0)));
+        let too_many = MAX_TRANSFER_ELEMENTS as u16 + 1;
+        assert_eq!(
+            check_iterations(too_many),
+            Err(TransferConfigError::Iterations(too_many))
+        );
+    }
+}
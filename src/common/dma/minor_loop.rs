@@ -0,0 +1,147 @@
+//! Minor loop offsets.
+//!
+//! The DMA channel already exposes per-element offsets, and last address
+//! adjustments, through `set_source_offset`, `set_destination_offset`, and
+//! the `*_last_address_adjustment` methods. Minor loop offsets add one more
+//! address adjustment after each minor loop. Together, these let a
+//! channel produce strided access patterns, like splitting an interleaved
+//! stream into separate buffers.
+
+use super::channel::Channel;
+use crate::ral;
+
+/// The largest number of bytes in a minor loop that uses an offset.
+pub const MAX_MINOR_LOOP_OFFSET_BYTES: u32 = (1 << 10) - 1;
+
+/// The smallest minor loop offset, in bytes.
+pub const MIN_MINOR_LOOP_OFFSET: i32 = -(1 << 19);
+/// The largest minor loop offset, in bytes.
+pub const MAX_MINOR_LOOP_OFFSET: i32 = (1 << 19) - 1;
+
+/// A minor loop offset.
+///
+/// After each minor loop, the DMA channel adds `offset` bytes to the
+/// selected addresses. The offset is added after the element offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinorLoopOffset {
+    /// The offset, in bytes.
+    ///
+    /// Must be between [`MIN_MINOR_LOOP_OFFSET`] and [`MAX_MINOR_LOOP_OFFSET`].
+    pub offset: i32,
+    /// Apply the offset to the source address.
+    pub source: bool,
+    /// Apply the offset to the destination address.
+    pub destination: bool,
+}
+
+/// Errors when setting a minor loop offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MinorLoopOffsetError {
+    /// The minor loop is zero bytes, or larger than [`MAX_MINOR_LOOP_OFFSET_BYTES`].
+    MinorLoopBytes(u32),
+    /// The offset is outside of the supported range.
+    Offset(i32),
+}
+
+/// Encode the minor loop byte count with an offset.
+fn encode(minor_loop_bytes: u32, offset: MinorLoopOffset) -> Result<u32, MinorLoopOffsetError> {
+    if !(1..=MAX_MINOR_LOOP_OFFSET_BYTES).contains(&minor_loop_bytes) {
+        return Err(MinorLoopOffsetError::MinorLoopBytes(minor_loop_bytes));
+    }
+    if !(MIN_MINOR_LOOP_OFFSET..=MAX_MINOR_LOOP_OFFSET).contains(&offset.offset) {
+        return Err(MinorLoopOffsetError::Offset(offset.offset));
+    }
+    const MLOFF_MASK: u32 = (1 << 20) - 1;
+    Ok(((offset.source as u32) << 31)
+        | ((offset.destination as u32) << 30)
+        | ((offset.offset as u32 & MLOFF_MASK) << 10)
+        | minor_loop_bytes)
+}
+
+/// Set the minor loop byte count, and a minor loop offset.
+///
+/// Use this instead of `set_minor_loop_bytes` when you need a minor loop
+/// offset. Calling `set_minor_loop_bytes` afterwards removes the offset.
+///
+/// This enables minor loop mapping for the whole DMA controller. Minor
+/// loop mapping doesn't affect channels that don't use a minor loop offset,
+/// as long as their minor loops are smaller than 1GiB.
+///
+/// # Safety
+///
+/// This modifies the transfer control descriptor, so it has the same
+/// safety requirements as the other channel configuration methods. Make
+/// sure that the offsets keep the addresses within your buffers.
+pub unsafe fn set_minor_loop_offset(
+    channel: &mut Channel,
+    minor_loop_bytes: u32,
+    offset: MinorLoopOffset,
+) -> Result<(), MinorLoopOffsetError> {
+    let nbytes = encode(minor_loop_bytes, offset)?;
+    // Safety: static peripheral memory. Caller upholds TCD safety. The
+    // channel is exclusively borrowed, so nothing else writes its TCD.
+    let dma = unsafe { &*ral::dma::DMA };
+    ral::modify_reg!(ral::dma, dma, CR, EMLM: 1);
+    let tcd = &dma.TCD[channel.channel()];
+    ral::write_reg!(ral::dma::tcd, tcd, TCD_NBYTES_MLNO, nbytes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, MinorLoopOffset, MinorLoopOffsetError};
+
+    const NONE: MinorLoopOffset = MinorLoopOffset {
+        offset: 0,
+        source: false,
+        destination: false,
+    };
+
+    #[test]
+    fn encode_offsets() {
+        assert_eq!(encode(4, NONE), Ok(4));
+        assert_eq!(
+            encode(
+                2,
+                MinorLoopOffset {
+                    offset: -2,
+                    destination: true,
+                    ..NONE
+                }
+            ),
+            Ok(1 << 30 | 0xFFFFE << 10 | 2)
+        );
+        assert_eq!(
+            encode(
+                8,
+                MinorLoopOffset {
+                    offset: 16,
+                    source: true,
+                    ..NONE
+                }
+            ),
+            Ok(1 << 31 | 16 << 10 | 8)
+        );
+    }
+
+    #[test]
+    fn encode_errors() {
+        assert_eq!(
+            encode(0, NONE),
+            Err(MinorLoopOffsetError::MinorLoopBytes(0))
+        );
+        assert_eq!(
+            encode(1024, NONE),
+            Err(MinorLoopOffsetError::MinorLoopBytes(1024))
+        );
+        let offset = MinorLoopOffset {
+            offset: 1 << 19,
+            ..NONE
+        };
+        assert_eq!(
+            encode(4, offset),
+            Err(MinorLoopOffsetError::Offset(1 << 19))
+        );
+    }
+}
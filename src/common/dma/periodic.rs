@@ -0,0 +1,155 @@
+//! Transfers paced by a periodic timer.
+//!
+//! PIT channels 0 through 3 can trigger DMA channels 0 through 3. A
+//! periodic transfer moves one element each time the PIT channel elapses,
+//! without any CPU intervention. Use this to sample an ADC, or to feed a
+//! DAC, at a fixed rate.
+
+use super::{
+    channel::{self, Channel},
+    dmamux,
+    peripheral::{Destination, Source},
+    reset_tcd, Element, LinearBuffer, LinearBufferMut, Result, Transfer,
+};
+use crate::pit::Pit;
+
+/// Route the PIT trigger to the DMA channel.
+///
+/// The DMAMUX source is always enabled, so the PIT trigger alone
+/// paces the channel.
+fn set_periodic<const C: u8>(channel: &mut Channel, _: &Pit<C>) {
+    assert_eq!(
+        channel.channel(),
+        C as usize,
+        "PIT channel {C} only triggers DMA channel {C}"
+    );
+    channel.set_channel_configuration(channel::Configuration::AlwaysOn);
+    dmamux::set_periodic_trigger(channel, true);
+}
+
+/// Runs the PIT channel, and stops it when dropped.
+struct Running<'a, const C: u8>(&'a mut Pit<C>);
+
+impl<'a, const C: u8> Running<'a, C> {
+    fn start(pit: &'a mut Pit<C>) -> Self {
+        pit.enable();
+        Self(pit)
+    }
+}
+
+impl<const C: u8> Drop for Running<'_, C> {
+    fn drop(&mut self) {
+        self.0.disable();
+    }
+}
+
+/// Send one element of `buffer` to the peripheral on each PIT period.
+///
+/// The DMA channel number must match the PIT channel number. Set the PIT
+/// load value before calling this function. The transfer starts the PIT, and
+/// stops it when the transfer completes or drops. The first element moves
+/// one period after the transfer starts.
+///
+/// The PIT paces the transfer, not the peripheral. This function uses
+/// `destination` for its address, and it doesn't enable the peripheral's
+/// DMA requests. For example, to play samples through a DAC, use buffer mode,
+/// and let this transfer write each sample to the DAC.
+///
+/// # Panics
+///
+/// Panics if the DMA channel number doesn't match the PIT channel number.
+pub async fn periodic_write<D, E, const C: u8>(
+    channel: &mut Channel,
+    pit: &mut Pit<C>,
+    buffer: LinearBuffer<'_, E>,
+    destination: &mut D,
+) -> Result<()>
+where
+    D: Destination<E>,
+    E: Element,
+{
+    channel.disable();
+    reset_tcd(channel);
+    channel.set_disable_on_completion(true);
+    set_periodic(channel, pit);
+    // Safety: hardware address must be valid, otherwise the Destination
+    // impl is unsound. The buffer lifetime is captured by the future. The
+    // linear buffer's length fits in the transfer iterations.
+    unsafe {
+        channel::set_source_linear_buffer(channel, buffer.as_slice());
+        channel::set_destination_hardware(channel, destination.destination_address());
+        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+        channel.set_transfer_iterations(buffer.len() as u16);
+    }
+    let _running = Running::start(pit);
+    // Safety: transfer is correctly defined.
+    unsafe { Transfer::new(channel) }.await
+}
+
+/// Receive one element from the peripheral into `buffer` on each PIT period.
+///
+/// This is the receive equivalent of [`periodic_write`]. This function uses
+/// `source` for its address, and it doesn't enable the peripheral's DMA
+/// requests. For example, to sample an ADC, start continuous conversions,
+/// and let this transfer copy the latest result each period.
+///
+/// # Panics
+///
+/// Panics if the DMA channel number doesn't match the PIT channel number.
+///
+/// # Example
+///
+/// Sample an ADC input at 48KHz, assuming a 24MHz PIT clock.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use hal::adc::{Adc, DmaSource};
+/// use hal::dma::{self, LinearBufferMut};
+///
+/// # async fn f(adc: Adc<1>) -> Option<()> {
+/// const PIT_CLOCK_HZ: u32 = 24_000_000;
+/// let mut samples = [0u16; 480];
+///
+/// let mut channel = // DMA channel 0...
+///     # unsafe { hal::dma::DMA.channel(0) };
+/// let (mut pit0, _, _, _) = hal::pit::new(unsafe { ral::pit::PIT::instance() });
+/// pit0.set_load_timer_value(PIT_CLOCK_HZ / 48_000 - 1);
+///
+/// let mut adc = DmaSource::without_pin(adc, 0);
+/// // Start continuous conversions.
+/// adc.enable_dma();
+///
+/// let samples = LinearBufferMut::new(&mut samples)?;
+/// dma::periodic_read(&mut channel, &mut pit0, &mut adc, samples).await.ok()?;
+/// # Some(()) }
+/// ```
+pub async fn periodic_read<S, E, const C: u8>(
+    channel: &mut Channel,
+    pit: &mut Pit<C>,
+    source: &mut S,
+    mut buffer: LinearBufferMut<'_, E>,
+) -> Result<()>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel.disable();
+    reset_tcd(channel);
+    channel.set_disable_on_completion(true);
+    set_periodic(channel, pit);
+    let len = buffer.len();
+    // Safety: hardware address must be valid, otherwise the Source impl
+    // is unsound. The buffer lifetime is captured by the future. The
+    // linear buffer's length fits in the transfer iterations.
+    unsafe {
+        channel::set_source_hardware(channel, source.source_address());
+        channel::set_destination_linear_buffer(channel, buffer.as_mut_slice());
+        channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+        channel.set_transfer_iterations(len as u16);
+    }
+    let _running = Running::start(pit);
+    // Safety: transfer is correctly defined.
+    unsafe { Transfer::new(channel) }.await
+}
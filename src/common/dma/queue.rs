@@ -0,0 +1,149 @@
+//! Wait for concurrent transfers.
+
+use core::{future::Future, pin::Pin, task::Poll};
+
+use super::Result;
+
+/// A pinned DMA transfer future.
+pub type Job<'a> = Pin<&'a mut dyn Future<Output = Result<()>>>;
+
+/// A set of DMA transfers that run at the same time.
+///
+/// Each transfer, or job, is a pinned transfer future, usually on its own DMA
+/// channel. The jobs can have different types, like an LPSPI write, an LPSPI
+/// read, and a memory copy. Use [`next`](Self::next) to learn which job
+/// completes next, or [`join`](Self::join) to wait for all jobs.
+///
+/// Transfers start when they're first polled. Since the queue polls every
+/// pending job, each job starts the first time that you await the queue.
+///
+/// ```no_run
+/// use core::pin::pin;
+/// use imxrt_hal as hal;
+/// use hal::dma::{self, TransferQueue};
+///
+/// # async fn f() -> dma::Result<()> {
+/// let (mut ch7, mut ch8) = // DMA channels 7 and 8...
+///     # unsafe { (dma::DMA.channel(7), dma::DMA.channel(8)) };
+/// let (mut a, mut b) = ([0u32; 64], [0u8; 128]);
+///
+/// let fill_a = pin!(dma::fill(&mut ch7, 0xDEADBEEF, &mut a));
+/// let fill_b = pin!(dma::fill(&mut ch8, 0xAA, &mut b));
+///
+/// let mut queue = TransferQueue::new([fill_a, fill_b]);
+/// while let Some((job, result)) = queue.next().await {
+///     result?;
+///     // Job 'job' is complete...
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// Dropping the queue drops the references to the jobs, not the jobs. Like
+/// other transfer futures, a job cancels its transfer when it drops.
+pub struct TransferQueue<'a, const N: usize> {
+    jobs: [Option<Job<'a>>; N],
+}
+
+impl<'a, const N: usize> TransferQueue<'a, N> {
+    /// Create a queue of jobs.
+    ///
+    /// A job's index in `jobs` identifies the job in [`next`](Self::next)
+    /// and [`join`](Self::join).
+    pub fn new(jobs: [Job<'a>; N]) -> Self {
+        Self {
+            jobs: jobs.map(Some),
+        }
+    }
+
+    /// Returns the number of jobs that haven't completed.
+    pub fn pending(&self) -> usize {
+        self.jobs.iter().filter(|job| job.is_some()).count()
+    }
+
+    /// Returns `true` if all jobs have completed.
+    pub fn is_empty(&self) -> bool {
+        self.pending() == 0
+    }
+
+    /// Wait for the next job to complete.
+    ///
+    /// Resolves to the index and result of the job, or `None` if all
+    /// jobs have completed. If more than one job is complete, the job with
+    /// the lowest index resolves first. The other jobs keep running.
+    pub async fn next(&mut self) -> Option<(usize, Result<()>)> {
+        core::future::poll_fn(|cx| {
+            if self.is_empty() {
+                return Poll::Ready(None);
+            }
+            for (idx, slot) in self.jobs.iter_mut().enumerate() {
+                if let Some(job) = slot {
+                    if let Poll::Ready(result) = job.as_mut().poll(cx) {
+                        *slot = None;
+                        return Poll::Ready(Some((idx, result)));
+                    }
+                }
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for all jobs to complete.
+    ///
+    /// Resolves to each job's result, in job order. An error doesn't
+    /// cancel the other jobs. The results of jobs that completed before
+    /// this call are `Ok(())`.
+    pub async fn join(mut self) -> [Result<()>; N] {
+        let mut results = [const { Ok(()) }; N];
+        while let Some((idx, result)) = self.next().await {
+            results[idx] = result;
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{future::Future, pin::pin, task::Poll};
+
+    use super::TransferQueue;
+    use crate::dma::Result;
+
+    /// A future that's pending `polls` times, then resolves.
+    fn after(mut polls: u32) -> impl Future<Output = Result<()>> {
+        core::future::poll_fn(move |cx| {
+            if polls == 0 {
+                Poll::Ready(Ok(()))
+            } else {
+                polls -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+    }
+
+    #[test]
+    fn completion_order() {
+        let a = pin!(after(3));
+        let b = pin!(after(1));
+        let c = pin!(after(1));
+        let mut queue = TransferQueue::new([a, b, c]);
+        assert_eq!(queue.pending(), 3);
+
+        assert!(matches!(crate::spin_on(queue.next()), Some((1, Ok(())))));
+        assert!(matches!(crate::spin_on(queue.next()), Some((2, Ok(())))));
+        assert_eq!(queue.pending(), 1);
+        assert!(matches!(crate::spin_on(queue.next()), Some((0, Ok(())))));
+        assert!(queue.is_empty());
+        assert!(crate::spin_on(queue.next()).is_none());
+    }
+
+    #[test]
+    fn join() {
+        let a = pin!(after(2));
+        let b = pin!(after(0));
+        let queue = TransferQueue::new([a, b]);
+        let results = crate::spin_on(queue.join());
+        assert!(results.iter().all(Result::is_ok));
+    }
+}
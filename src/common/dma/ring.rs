@@ -0,0 +1,331 @@
+//! Continuous, circular DMA receive.
+
+use core::{
+    marker::PhantomData,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use super::{
+    channel::{self, Channel, Configuration},
+    peripheral::Source,
+    reset_tcd, Element, Error,
+};
+use crate::ral;
+
+/// The largest [`RingReader`] buffer, in elements.
+///
+/// This is the largest power of two that fits in a DMA transfer.
+pub const MAX_RING_ELEMENTS: usize = 1 << 14;
+
+/// Errors from a [`RingReader`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum RingError {
+    /// The DMA channel overwrote data that the reader didn't read.
+    ///
+    /// The reader discards all buffered data, and starts reading
+    /// at the newest element.
+    Overrun,
+    /// The DMA channel reported an error.
+    Dma(Error),
+}
+
+/// A DMA transfer that continuously receives data into a circular buffer.
+///
+/// The DMA channel writes elements from the peripheral into the buffer,
+/// wrapping to the start of the buffer when it reaches the end. The
+/// transfer never completes. Use [`read`](Self::read) to drain the elements
+/// that the DMA channel deposited since your last read.
+///
+/// Drop the reader to stop the transfer.
+///
+/// Read often enough to keep up with the peripheral. If the DMA channel
+/// catches up to the reader, `read` returns [`RingError::Overrun`]. The
+/// reader detects that the DMA channel lapped it once. If you wait long
+/// enough for the DMA channel to lap the reader more than once, the reader
+/// might not detect the overrun.
+pub struct RingReader<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    channel: &'a mut Channel,
+    source: &'a mut S,
+    buffer: *const E,
+    len: usize,
+    positions: Positions,
+    _buffer: PhantomData<&'a mut [E]>,
+}
+
+impl<'a, S, E> RingReader<'a, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    /// Start a continuous DMA transfer from `source` into `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    ///
+    /// - the buffer length isn't a power of two.
+    /// - the buffer isn't aligned to its size, in bytes.
+    /// - the buffer is larger than [`MAX_RING_ELEMENTS`].
+    pub fn new(channel: &'a mut Channel, source: &'a mut S, buffer: &'a mut [E]) -> Self {
+        assert!(buffer.len() <= MAX_RING_ELEMENTS);
+
+        channel.disable();
+        channel.clear_complete();
+        channel.clear_error();
+
+        reset_tcd(channel);
+        channel.set_disable_on_completion(false);
+        channel.set_channel_configuration(Configuration::enable(source.source_signal()));
+        // Safety: hardware source address must be valid, otherwise impl is unsound.
+        // Buffer lifetime captured by the reader, and the reader disables the
+        // transfer when it drops. Circular buffer helper checks size and alignment.
+        unsafe {
+            channel::set_source_hardware(channel, source.source_address());
+            channel::set_destination_circular_buffer(channel, buffer);
+            channel.set_minor_loop_bytes(core::mem::size_of::<E>() as u32);
+            channel.set_transfer_iterations(buffer.len() as u16);
+        }
+
+        source.enable_source();
+        compiler_fence(Ordering::SeqCst);
+        // Safety: see above.
+        unsafe { channel.enable() };
+
+        Self {
+            channel,
+            source,
+            buffer: buffer.as_ptr(),
+            len: buffer.len(),
+            positions: Positions::new(),
+            _buffer: PhantomData,
+        }
+    }
+
+    /// Returns the index of the next element the DMA channel will write.
+    fn write_position(&self) -> usize {
+        // Safety: static peripheral memory. Reading the destination
+        // address has no side effects.
+        let tcd = unsafe { &(*ral::dma::DMA).TCD[self.channel.channel()] };
+        let daddr = ral::read_reg!(ral::dma::tcd, tcd, TCD_DADDR) as usize;
+        let offset = daddr.wrapping_sub(self.buffer as usize) / core::mem::size_of::<E>();
+        offset & (self.len - 1)
+    }
+
+    /// Returns the number of elements that are ready to read.
+    ///
+    /// An error indicates that the DMA channel overran the reader, or
+    /// that the DMA channel reported an error. See [`read`](Self::read)
+    /// for more information.
+    pub fn available(&mut self) -> Result<usize, RingError> {
+        if self.channel.is_error() {
+            let es = self.channel.error_status();
+            self.channel.clear_error();
+            return Err(RingError::Dma(es));
+        }
+
+        let complete = self.channel.is_complete();
+        if complete {
+            self.channel.clear_complete();
+        }
+        let write = self.write_position();
+
+        // The DMA channel may have wrapped after we checked for completion.
+        if !complete && write < self.positions.write {
+            self.channel.clear_complete();
+        }
+        self.positions.observe(write, complete);
+
+        match self.positions.available(self.len) {
+            Some(count) => Ok(count),
+            None => {
+                self.positions.overrun();
+                Err(RingError::Overrun)
+            }
+        }
+    }
+
+    /// Read the elements that the DMA channel deposited since the last
+    /// read.
+    ///
+    /// Returns the number of elements copied into `buffer`. This is
+    /// zero if there's no new data. If `buffer` is smaller than the
+    /// available data, the remaining elements stay in the ring for
+    /// your next read.
+    ///
+    /// If the DMA channel overwrote elements that you didn't read, this
+    /// returns [`RingError::Overrun`], and discards all buffered elements.
+    /// Your next read only returns elements received after the overrun.
+    pub fn read(&mut self, buffer: &mut [E]) -> Result<usize, RingError> {
+        let count = self.available()?.min(buffer.len());
+        // Synchronizes with the DMA writes observed in `available`.
+        compiler_fence(Ordering::Acquire);
+        for elem in &mut buffer[..count] {
+            // Safety: index is always within the ring. The DMA channel isn't writing
+            // this element, since it's before the write position.
+            *elem = unsafe { self.buffer.add(self.positions.read).read_volatile() };
+            self.positions.consume(self.len);
+        }
+        Ok(count)
+    }
+}
+
+/// The reader and writer positions in the ring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Positions {
+    /// The index of the next element to read.
+    read: usize,
+    /// The index of the next element to write, as last observed.
+    write: usize,
+    /// The number of times the writer wrapped around the ring, less the
+    /// number of times the reader wrapped.
+    laps: usize,
+}
+
+impl Positions {
+    const fn new() -> Self {
+        Self {
+            read: 0,
+            write: 0,
+            laps: 0,
+        }
+    }
+
+    /// Observe the writer at `write`.
+    ///
+    /// `complete` indicates that the writer reached the end of the ring
+    /// since the last observation. If the writer is behind its last
+    /// position, it wrapped, even if it wasn't complete.
+    fn observe(&mut self, write: usize, complete: bool) {
+        if complete || write < self.write {
+            self.laps = self.laps.saturating_add(1);
+        }
+        self.write = write;
+    }
+
+    /// Returns the number of elements that are ready to read, or `None`
+    /// if the writer overran the reader.
+    const fn available(&self, len: usize) -> Option<usize> {
+        match self.laps {
+            0 => available(self.read, self.write, len, false),
+            1 => available(self.read, self.write, len, true),
+            _ => None,
+        }
+    }
+
+    /// Read one element.
+    fn consume(&mut self, len: usize) {
+        self.read = (self.read + 1) & (len - 1);
+        if self.read == 0 {
+            self.laps = self.laps.saturating_sub(1);
+        }
+    }
+
+    /// Discard all elements, and start reading at the writer.
+    fn overrun(&mut self) {
+        self.read = self.write;
+        self.laps = 0;
+    }
+}
+
+/// Compute the elements available between the `read` and `write` positions.
+///
+/// `lapped` indicates that the writer wrapped around the ring since the
+/// reader last observed it. Returns `None` if the writer overran the reader.
+const fn available(read: usize, write: usize, len: usize, lapped: bool) -> Option<usize> {
+    match (lapped, write >= read) {
+        (false, true) => Some(write - read),
+        (true, false) => Some(len - read + write),
+        (false, false) | (true, true) => None,
+    }
+}
+
+impl<S, E> Drop for RingReader<'_, S, E>
+where
+    S: Source<E>,
+    E: Element,
+{
+    fn drop(&mut self) {
+        self.source.disable_source();
+        while self.channel.is_hardware_signaling() {}
+        self.channel.disable();
+        self.channel.clear_complete();
+        self.channel.clear_error();
+    }
+}
+
+// Safety: the reader exclusively borrows its channel, source, and buffer.
+unsafe impl<S, E> Send for RingReader<'_, S, E>
+where
+    S: Source<E> + Send,
+    E: Element,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{available, Positions};
+
+    #[test]
+    fn no_wrap() {
+        assert_eq!(available(0, 0, 8, false), Some(0));
+        assert_eq!(available(2, 7, 8, false), Some(5));
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(available(6, 2, 8, true), Some(4));
+        assert_eq!(available(7, 0, 8, true), Some(1));
+    }
+
+    #[test]
+    fn overrun() {
+        assert_eq!(available(2, 2, 8, true), None);
+        assert_eq!(available(2, 5, 8, true), None);
+    }
+
+    fn consume(positions: &mut Positions, count: usize) {
+        for _ in 0..count {
+            positions.consume(8);
+        }
+    }
+
+    #[test]
+    fn partial_read_then_wrap() {
+        let mut positions = Positions::new();
+        positions.observe(6, false);
+        assert_eq!(positions.available(8), Some(6));
+        consume(&mut positions, 4);
+
+        positions.observe(2, true);
+        assert_eq!(positions.available(8), Some(6));
+        consume(&mut positions, 3);
+        assert_eq!(positions.available(8), Some(3));
+
+        // The writer wraps again, and passes the unread element at 7.
+        positions.observe(5, true);
+        assert_eq!(positions.available(8), None);
+        positions.overrun();
+        assert_eq!(positions.available(8), Some(0));
+    }
+
+    #[test]
+    fn reader_wraps() {
+        let mut positions = Positions::new();
+        positions.observe(7, false);
+        consume(&mut positions, 6);
+        // Wrapped without observing completion.
+        positions.observe(3, false);
+        assert_eq!(positions.available(8), Some(5));
+        consume(&mut positions, 3);
+        assert_eq!(positions.read, 1);
+        assert_eq!(positions.available(8), Some(2));
+
+        positions.observe(6, false);
+        assert_eq!(positions.available(8), Some(5));
+    }
+}
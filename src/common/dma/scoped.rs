@@ -0,0 +1,91 @@
+//! DMA channels that reset when dropped.
+
+use core::ops::{Deref, DerefMut};
+
+use super::channel::{Channel, Configuration};
+
+/// A DMA channel that stops, and resets, when dropped.
+///
+/// A `Channel` doesn't change the hardware when it's dropped. If the
+/// channel is enabled when you drop it, the DMA controller may keep
+/// servicing requests, and keep writing to the channel's destination.
+/// Wrap the channel in a `ScopedChannel` if you acquire and release
+/// channels at runtime, instead of keeping them forever.
+///
+/// When a `ScopedChannel` drops, it
+///
+/// 1. disables the channel's hardware requests.
+/// 2. waits for the channel to finish any active minor loop.
+/// 3. clears the channel's completion and error flags.
+/// 4. disables the channel's DMAMUX source.
+/// 5. resets the channel's transfer control descriptor.
+///
+/// Once the drop completes, the DMA controller no longer accesses the
+/// channel's buffers. Drop the `ScopedChannel` before you free, or reuse,
+/// any buffer that the channel might access. Since locals drop in reverse
+/// order of declaration, declare your buffers before the channel.
+///
+/// `ScopedChannel` dereferences to a `Channel`, so you can use it with
+/// any DMA API.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use hal::dma::ScopedChannel;
+///
+/// let mut channels = hal::dma::channels(
+///     unsafe { ral::dma::DMA::instance() },
+///     unsafe { ral::dmamux::DMAMUX::instance() },
+/// );
+///
+/// let mut buffer = [0u8; 32];
+/// let mut channel = ScopedChannel::new(channels[7].take().unwrap());
+/// // Use the channel with buffer...
+/// # let _ = (&mut buffer, &mut channel);
+///
+/// // The channel drops before the buffer.
+/// ```
+pub struct ScopedChannel {
+    channel: Channel,
+}
+
+impl ScopedChannel {
+    /// Reset the channel when the wrapper drops.
+    pub fn new(channel: Channel) -> Self {
+        Self { channel }
+    }
+
+    /// Release the channel, without resetting it.
+    ///
+    /// If the channel is enabled, it stays enabled.
+    pub fn release(self) -> Channel {
+        let this = core::mem::ManuallyDrop::new(self);
+        // Safety: `this` won't drop, so we take the only copy of the channel.
+        unsafe { core::ptr::read(&this.channel) }
+    }
+}
+
+impl Deref for ScopedChannel {
+    type Target = Channel;
+    fn deref(&self) -> &Self::Target {
+        &self.channel
+    }
+}
+
+impl DerefMut for ScopedChannel {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.channel
+    }
+}
+
+impl Drop for ScopedChannel {
+    fn drop(&mut self) {
+        self.channel.disable();
+        while self.channel.is_active() {}
+        self.channel.clear_complete();
+        self.channel.clear_error();
+        self.channel.set_channel_configuration(Configuration::Off);
+        self.channel.reset();
+    }
+}
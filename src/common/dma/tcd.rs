@@ -0,0 +1,161 @@
+//! Transfer control descriptor snapshots.
+//!
+//! A [`TcdSnapshot`] is a decoded copy of a channel's live transfer control
+//! descriptor (TCD). Print it to compare the hardware's view of a transfer
+//! with the configuration that you meant to write. With the `"defmt"`
+//! feature, the snapshot implements `defmt::Format`.
+
+use super::channel::Channel;
+use crate::ral;
+
+/// A decoded copy of a DMA channel's transfer control descriptor.
+///
+/// Use [`tcd_snapshot`] to read a channel's descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TcdSnapshot {
+    /// The source address.
+    ///
+    /// While a transfer runs, this is the address of the next read.
+    pub source_address: u32,
+    /// The signed offset, in bytes, added to the source address after each read.
+    pub source_offset: i16,
+    /// The size, in bytes, of each source read.
+    pub source_size: u8,
+    /// The source address modulo, or zero for no modulo.
+    pub source_modulo: u8,
+    /// The signed adjustment, in bytes, added to the source address after the
+    /// major loop.
+    pub source_last_address_adjustment: i32,
+    /// The destination address.
+    ///
+    /// While a transfer runs, this is the address of the next write.
+    pub destination_address: u32,
+    /// The signed offset, in bytes, added to the destination address after each write.
+    pub destination_offset: i16,
+    /// The size, in bytes, of each destination write.
+    pub destination_size: u8,
+    /// The destination address modulo, or zero for no modulo.
+    pub destination_modulo: u8,
+    /// The signed adjustment, in bytes, added to the destination address after
+    /// the major loop.
+    ///
+    /// When [`TcdControl::SCATTER_GATHER`] is set, this is the address of the
+    /// next descriptor.
+    pub destination_last_address_adjustment: i32,
+    /// The raw minor loop register, `NBYTES`.
+    ///
+    /// Without minor loop offsets, this is the number of bytes in each minor
+    /// loop. See [`set_minor_loop_offset`](crate::dma::set_minor_loop_offset) for
+    /// the encoding with offsets.
+    pub minor_loop: u32,
+    /// The minor loops remaining in the major loop, `CITER`.
+    pub current_iterations: u16,
+    /// The minor loops in each major loop, `BITER`.
+    pub beginning_iterations: u16,
+    /// The control and status flags.
+    pub control: TcdControl,
+}
+
+bitflags::bitflags! {
+    /// Control and status flags of a transfer control descriptor.
+    ///
+    /// These are the flags in the TCD's `CSR`.
+    pub struct TcdControl : u16 {
+        /// Software requested a start.
+        const START = 1 << 0;
+        /// Interrupt when the major loop completes.
+        const INTERRUPT_MAJOR = 1 << 1;
+        /// Interrupt when the major loop is half complete.
+        const INTERRUPT_HALF = 1 << 2;
+        /// Disable hardware requests when the major loop completes.
+        const DISABLE_ON_COMPLETION = 1 << 3;
+        /// Load the next descriptor when the major loop completes.
+        const SCATTER_GATHER = 1 << 4;
+        /// Link to another channel when the major loop completes.
+        const MAJOR_LINK = 1 << 5;
+        /// The channel is executing.
+        const ACTIVE = 1 << 6;
+        /// The major loop completed.
+        const DONE = 1 << 7;
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TcdControl {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "TcdControl({=u16:#x})", self.bits())
+    }
+}
+
+/// Decode the transfer size and modulo from the source or destination
+/// half of `ATTR`.
+const fn attributes(attr: u16) -> (u8, u8) {
+    (1 << (attr & 0x7), ((attr >> 3) & 0x1F) as u8)
+}
+
+/// Decode the iterations from `CITER` or `BITER`.
+///
+/// When channel linking is enabled, the link channel takes the upper
+/// iteration bits.
+const fn iterations(iter: u16) -> u16 {
+    const ELINK: u16 = 1 << 15;
+    if iter & ELINK != 0 {
+        iter & 0x1FF
+    } else {
+        iter & 0x7FFF
+    }
+}
+
+/// Read and decode the channel's transfer control descriptor.
+///
+/// This reads the live descriptor. If the channel is active, the addresses
+/// and current iterations may change while they're read.
+pub fn tcd_snapshot(channel: &Channel) -> TcdSnapshot {
+    // Safety: static peripheral memory. These are reads of the channel's
+    // own TCD.
+    let dma = unsafe { &*ral::dma::DMA };
+    let tcd = &dma.TCD[channel.channel()];
+    let attr = ral::read_reg!(ral::dma::tcd, tcd, TCD_ATTR);
+    let (source_size, source_modulo) = attributes(attr >> 8);
+    let (destination_size, destination_modulo) = attributes(attr);
+    TcdSnapshot {
+        source_address: ral::read_reg!(ral::dma::tcd, tcd, TCD_SADDR),
+        source_offset: ral::read_reg!(ral::dma::tcd, tcd, TCD_SOFF) as i16,
+        source_size,
+        source_modulo,
+        source_last_address_adjustment: ral::read_reg!(ral::dma::tcd, tcd, TCD_SLAST) as i32,
+        destination_address: ral::read_reg!(ral::dma::tcd, tcd, TCD_DADDR),
+        destination_offset: ral::read_reg!(ral::dma::tcd, tcd, TCD_DOFF) as i16,
+        destination_size,
+        destination_modulo,
+        destination_last_address_adjustment: ral::read_reg!(ral::dma::tcd, tcd, TCD_DLASTSGA)
+            as i32,
+        minor_loop: ral::read_reg!(ral::dma::tcd, tcd, TCD_NBYTES_MLNO),
+        current_iterations: iterations(ral::read_reg!(ral::dma::tcd, tcd, TCD_CITER_ELINKNO)),
+        beginning_iterations: iterations(ral::read_reg!(ral::dma::tcd, tcd, TCD_BITER_ELINKNO)),
+        control: TcdControl::from_bits_truncate(ral::read_reg!(ral::dma::tcd, tcd, TCD_CSR)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attributes, iterations};
+
+    #[test]
+    fn decode_attributes() {
+        assert_eq!(attributes(0), (1, 0));
+        assert_eq!(attributes(1), (2, 0));
+        assert_eq!(attributes(2), (4, 0));
+        assert_eq!(attributes(5 << 3 | 2), (4, 5));
+        assert_eq!(attributes(0x0202 >> 8), (4, 0));
+    }
+
+    #[test]
+    fn decode_iterations() {
+        assert_eq!(iterations(1), 1);
+        assert_eq!(iterations(0x7FFF), 0x7FFF);
+        // ELINK, link channel 3, 17 iterations.
+        assert_eq!(iterations(1 << 15 | 3 << 9 | 17), 17);
+    }
+}
@@ -0,0 +1,307 @@
+//! Checked transfer configurations.
+//!
+//! The channel's configuration methods accept any combination of element
+//! types, minor loop sizes, and addresses. An inconsistent combination, like
+//! a minor loop that isn't a multiple of the element size, or an address
+//! that isn't aligned to its element, silently corrupts data. A
+//! [`TransferConfig`] describes the whole transfer, so it can be checked
+//! before it's written to the channel.
+//!
+//! A transfer control descriptor (TCD) keeps every field from the channel's
+//! previous transfer until it's overwritten. [`configure_transfer`] resets the
+//! TCD before it writes the configuration, so a scatter / gather address, a
+//! channel link, or a modulo from a previous transfer can't leak into the new
+//! one. The HAL's other DMA transfers, like [`fill`](super::fill) and the
+//! peripheral transfers, reset the TCD the same way. If you configure a channel
+//! field by field, call `Channel::reset` first.
+
+use super::{channel::Channel, Element, TcdControl, MAX_TRANSFER_ELEMENTS};
+use crate::ral;
+
+/// The `CSR` fields that [`reset_tcd`] keeps: the major loop interrupts,
+/// and disable on completion.
+const KEPT_CSR_FIELDS: u16 = TcdControl::INTERRUPT_MAJOR.bits()
+    | TcdControl::INTERRUPT_HALF.bits()
+    | TcdControl::DISABLE_ON_COMPLETION.bits();
+
+/// One side of a DMA transfer.
+///
+/// See [`TransferConfig`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferSide<E> {
+    /// The starting address.
+    ///
+    /// Must be aligned to the element size.
+    pub address: *const E,
+    /// The signed offset, in bytes, that's added to the address after
+    /// each element.
+    ///
+    /// Must be a multiple of the element size.
+    pub offset: i16,
+    /// The modulo of a circular buffer, or zero for a linear buffer.
+    ///
+    /// When nonzero, the address must be aligned to `1 << modulo` bytes.
+    pub modulo: u8,
+    /// The signed adjustment, in bytes, that's added to the address after
+    /// the last iteration.
+    ///
+    /// Must be a multiple of the element size.
+    pub last_address_adjustment: i32,
+}
+
+impl<E: Element> TransferSide<E> {
+    /// A linear buffer that starts at `address`.
+    ///
+    /// The address increments by one element after each element, and
+    /// it's not adjusted after the last iteration.
+    pub const fn linear(address: *const E) -> Self {
+        Self {
+            address,
+            offset: core::mem::size_of::<E>() as i16,
+            modulo: 0,
+            last_address_adjustment: 0,
+        }
+    }
+
+    /// A hardware register at `address`.
+    ///
+    /// The address never changes.
+    pub const fn hardware(address: *const E) -> Self {
+        Self {
+            address,
+            offset: 0,
+            modulo: 0,
+            last_address_adjustment: 0,
+        }
+    }
+
+    /// Returns `true` if the address, offset, and adjustment are aligned
+    /// to the element, and if the address is aligned to the modulo.
+    fn is_aligned(&self) -> bool {
+        // Element sizes are powers of two.
+        let mask = core::mem::size_of::<E>() - 1;
+        let address = self.address as usize;
+        let modulo_aligned = match self.modulo {
+            0 => true,
+            modulo @ 1..=31 => address & ((1 << modulo) - 1) == 0,
+            _ => false,
+        };
+        address & mask == 0
+            && self.offset as usize & mask == 0
+            && self.last_address_adjustment as usize & mask == 0
+            && modulo_aligned
+    }
+}
+
+/// A DMA transfer configuration.
+///
+/// `TransferConfig` describes the source, the destination, the bytes in each
+/// minor loop, and the number of minor loops. Use [`validate`](Self::validate)
+/// to check the configuration, or [`configure_transfer`] to check it and write
+/// it to a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferConfig<S, D> {
+    /// The source.
+    pub source: TransferSide<S>,
+    /// The destination.
+    pub destination: TransferSide<D>,
+    /// The number of bytes moved in each minor loop.
+    ///
+    /// Must be a multiple of both element sizes.
+    pub minor_loop_bytes: u32,
+    /// The number of minor loops in the transfer.
+    ///
+    /// Must be between one and [`MAX_TRANSFER_ELEMENTS`].
+    pub iterations: u16,
+}
+
+/// Errors when checking a [`TransferConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransferConfigError {
+    /// The minor loop is zero bytes, or it's not a multiple of both
+    /// element sizes.
+    MinorLoopBytes(u32),
+    /// The number of iterations is zero, or larger than [`MAX_TRANSFER_ELEMENTS`].
+    Iterations(u16),
+    /// The source address, offset, or adjustment isn't aligned.
+    SourceAlignment,
+    /// The destination address, offset, or adjustment isn't aligned.
+    DestinationAlignment,
+}
+
+impl<S: Element, D: Element> TransferConfig<S, D> {
+    /// Check the configuration.
+    pub fn validate(&self) -> Result<(), TransferConfigError> {
+        let element_sizes = [core::mem::size_of::<S>(), core::mem::size_of::<D>()];
+        let bytes = self.minor_loop_bytes;
+        if bytes == 0
+            || !element_sizes
+                .iter()
+                .all(|&size| (bytes as usize).is_multiple_of(size))
+        {
+            return Err(TransferConfigError::MinorLoopBytes(bytes));
+        }
+        check_iterations(self.iterations)?;
+        if !self.source.is_aligned() {
+            return Err(TransferConfigError::SourceAlignment);
+        }
+        if !self.destination.is_aligned() {
+            return Err(TransferConfigError::DestinationAlignment);
+        }
+        Ok(())
+    }
+}
+
+/// Check the number of major loop iterations.
+pub(crate) fn check_iterations(iterations: u16) -> Result<(), TransferConfigError> {
+    if (1..=MAX_TRANSFER_ELEMENTS).contains(&usize::from(iterations)) {
+        Ok(())
+    } else {
+        Err(TransferConfigError::Iterations(iterations))
+    }
+}
+
+/// Reset the channel's transfer control descriptor.
+///
+/// This keeps the channel's major loop interrupts, and whether it's disabled
+/// on completion. Call this before describing a new transfer, so that fields
+/// from the previous transfer, like a minor loop offset, don't apply to the
+/// new transfer.
+pub(crate) fn reset_tcd(channel: &mut Channel) {
+    // Safety: static peripheral memory. The channel is exclusively borrowed,
+    // so nothing else writes its TCD.
+    let dma = unsafe { &*ral::dma::DMA };
+    let tcd = &dma.TCD[channel.channel()];
+    let csr = ral::read_reg!(ral::dma::tcd, tcd, TCD_CSR) & KEPT_CSR_FIELDS;
+    channel.reset();
+    ral::write_reg!(ral::dma::tcd, tcd, TCD_CSR, csr);
+}
+
+/// Check a transfer configuration, then write it to the channel.
+///
+/// This resets the channel's transfer control descriptor, then sets the source
+/// and destination addresses, offsets, attributes, and last address
+/// adjustments, the minor loop bytes, and the transfer iterations. The reset
+/// clears minor loop offsets, channel links, and scatter / gather. It doesn't
+/// change the channel's DMAMUX configuration, its interrupts, or whether it's
+/// disabled on completion.
+///
+/// Returns an error, without changing the channel, if the configuration isn't
+/// valid.
+///
+/// # Safety
+///
+/// The checks make sure that the configuration is consistent, not that the
+/// memory is valid. Once configured, a hardware request or a software start
+/// may begin the transfer. Make sure that the source and destination stay
+/// valid, and that the transfer stays within them, until the transfer
+/// completes or you reconfigure the channel.
+pub unsafe fn configure_transfer<S: Element, D: Element>(
+    channel: &mut Channel,
+    config: &TransferConfig<S, D>,
+) -> Result<(), TransferConfigError> {
+    config.validate()?;
+    reset_tcd(channel);
+
+    // Safety: caller upholds the memory requirements. The configuration
+    // is consistent for the element sizes.
+    unsafe {
+        channel.set_source_address(config.source.address);
+        channel.set_source_offset(config.source.offset);
+        channel.set_source_attributes::<S>(config.source.modulo);
+        channel.set_source_last_address_adjustment(config.source.last_address_adjustment);
+        channel.set_destination_address(config.destination.address);
+        channel.set_destination_offset(config.destination.offset);
+        channel.set_destination_attributes::<D>(config.destination.modulo);
+        channel.set_destination_last_address_adjustment(config.destination.last_address_adjustment);
+        channel.set_minor_loop_bytes(config.minor_loop_bytes);
+        channel.set_transfer_iterations(config.iterations);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransferConfig, TransferConfigError, TransferSide};
+
+    #[repr(align(8))]
+    struct Aligned([u8; 16]);
+    static BUFFER: Aligned = Aligned([0; 16]);
+
+    fn config(minor_loop_bytes: u32, iterations: u16) -> TransferConfig<u32, u16> {
+        TransferConfig {
+            source: TransferSide::linear(BUFFER.0.as_ptr().cast()),
+            destination: TransferSide::hardware(BUFFER.0.as_ptr().cast()),
+            minor_loop_bytes,
+            iterations,
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(config(4, 1).validate(), Ok(()));
+        assert_eq!(config(8, 4).validate(), Ok(()));
+    }
+
+    #[test]
+    fn minor_loop_bytes() {
+        assert_eq!(
+            config(0, 1).validate(),
+            Err(TransferConfigError::MinorLoopBytes(0))
+        );
+        assert_eq!(
+            config(2, 1).validate(),
+            Err(TransferConfigError::MinorLoopBytes(2))
+        );
+        assert_eq!(
+            config(6, 1).validate(),
+            Err(TransferConfigError::MinorLoopBytes(6))
+        );
+    }
+
+    #[test]
+    fn iterations() {
+        assert_eq!(
+            config(4, 0).validate(),
+            Err(TransferConfigError::Iterations(0))
+        );
+        assert_eq!(
+            config(4, 1 << 15).validate(),
+            Err(TransferConfigError::Iterations(1 << 15))
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        let mut misaligned = config(4, 1);
+        misaligned.source.address = BUFFER.0[2..].as_ptr().cast();
+        assert_eq!(
+            misaligned.validate(),
+            Err(TransferConfigError::SourceAlignment)
+        );
+
+        let mut misaligned = config(4, 1);
+        misaligned.source.last_address_adjustment = -2;
+        assert_eq!(
+            misaligned.validate(),
+            Err(TransferConfigError::SourceAlignment)
+        );
+
+        let mut misaligned = config(4, 1);
+        misaligned.destination.offset = 1;
+        assert_eq!(
+            misaligned.validate(),
+            Err(TransferConfigError::DestinationAlignment)
+        );
+
+        let mut circular = config(4, 1);
+        circular.destination.modulo = 3;
+        assert_eq!(circular.validate(), Ok(()));
+        circular.destination.address = BUFFER.0[4..].as_ptr().cast();
+        assert_eq!(
+            circular.validate(),
+            Err(TransferConfigError::DestinationAlignment)
+        );
+    }
+}
@@ -58,8 +58,9 @@
 //! output_a.set_turn_off(&sm2, i16::MAX / 2);
 //! // Output B generates the same duty cycle as A
 //! // with a lagging phase shift of 5000 counts.
-//! output_b.set_turn_on(&sm2, output_a.turn_on(&sm2) + 5000);
-//! output_b.set_turn_off(&sm2, output_a.turn_off(&sm2) + 5000);
+//! output_b.set_turn_on(&sm2, output_a.turn_on(&sm2));
+//! output_b.set_turn_off(&sm2, output_a.turn_off(&sm2));
+//! pwm.set_phase_shift(&mut sm2, flexpwm::Channel::B, 5000);
 //!
 //! // Enable the PWM output.
 //! output_a.set_output_enable(&mut pwm, true);
@@ -70,6 +71,61 @@
 //! sm2.set_running(&mut pwm, true);
 //! # Some(())}();
 //! ```
+//!
+//! To reduce this setup, describe the submodule settings with a [`SubmoduleConfig`],
+//! then apply them with [`Pwm::configure`]. To update a submodule's period and
+//! edges together, write all of its value registers with [`Pwm::load_values`].
+//!
+//! # Counting
+//!
+//! A submodule counter only counts up. It starts at the initial count, `SMINIT`,
+//! and counts until it compares with the full reload value, VAL1. On the next
+//! clock, it loads the initial count, and starts another cycle. So the PWM
+//! period is `VAL1 - SMINIT + 1` counts. The half reload value, VAL0, marks
+//! the middle of the cycle.
+//!
+//! A center-aligned PWM, like the one set by [`Pwm::set_center_aligned`],
+//! still counts up. It starts at `SMINIT = -(period / 2)`, passes the half
+//! reload value, VAL0, at zero, and counts until VAL1 = `SMINIT + period - 1`.
+//! For an even period, that's `period / 2 - 1`. The cycle is symmetric about
+//! VAL0, so it acts like the up-down counter of a triangle wave, where the
+//! first half of the cycle is the rising half, and the second half is the
+//! falling half. Use [`Pwm::count_direction`] to learn which half the counter
+//! is in. For example, sample an ADC near the count peak, when the counter is
+//! near VAL0.
+//!
+//! # Channel X
+//!
+//! Besides the A and B outputs, each submodule has a third output, X. Channel X
+//! reuses the reload value registers: it turns on when the counter compares with
+//! the half reload value, VAL0, and it turns off when the counter compares with
+//! the full reload value, VAL1. So [`turn_on()`] and [`turn_off()`] return VAL0
+//! and VAL1 for [`Channel::X`], and changing X's turn off value changes the
+//! period. X is high for `VAL1 - VAL0` counts each period.
+//!
+//! X doesn't take part in the A / B pair. [`PairOperation`], deadtime, and the
+//! output source selection don't affect X, so X stays independent when A and B
+//! are complementary. That makes it a fit for an enable or brake line next to a
+//! half bridge. X has its own output enable, mask, and fault disable map; pass
+//! [`Channel::X`] to those APIs. There is no [`Output`] for X pins; configure the
+//! pad's alternate function yourself, and make sure the submodule isn't using X
+//! for input capture.
+//!
+//! # Faults
+//!
+//! Fault inputs disable outputs in hardware, without software intervention. Use
+//! [`Pwm::set_fault_disable_map`] to select the faults that disable each output,
+//! [`Pwm::set_fault_recovery`] to decide when outputs re-enable, and
+//! [`Pwm::set_fault_filter`] to reject glitches on the fault inputs.
+//!
+//! To drive gates safely from startup, create the PWM with [`new_safe`]. It
+//! holds the A and B outputs at software controlled levels until you select
+//! the generated signal with [`Pwm::set_output_source`].
+//!
+//! # Fractional delay
+//!
+//! Submodules can delay the PWM period and output edges by fractions of a PWM
+//! clock cycle. See [`Submodule::set_fractional_value`] for more information.
 
 mod output;
 mod ral;
@@ -89,6 +145,11 @@ pub struct Pwm<const N: u8> {
     pwm: pwm::Instance<N>,
 }
 
+// Safety: methods that take `&self` only read registers, or store
+// write-one-to-set / clear bits in the byte-accessible half of MCTRL.
+// Those stores don't race with each other.
+unsafe impl<const N: u8> Sync for Pwm<N> {}
+
 bitflags::bitflags! {
     /// Bitmask for representing submodules.
     ///
@@ -109,17 +170,24 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// Bitmask for representing fault inputs.
+    pub struct FaultMask : u8 {
+        /// Fault input 0.
+        const FAULT0 = 1 << 0;
+        /// Fault input 1.
+        const FAULT1 = 1 << 1;
+        /// Fault input 2.
+        const FAULT2 = 1 << 2;
+        /// Fault input 3.
+        const FAULT3 = 1 << 3;
+    }
+}
+
 impl<const N: u8> Pwm<N> {
     /// The peripheral instance.
     pub const N: u8 = N;
 
-    // TODO: MCTRL should be byte accessible (unlike other PWM modules, which are explicitly
-    // documented as "not bye accessible"). If we could load and store directly from the low
-    // byte -- where LDOK and CLDOK reside -- we might be able to drop the &mut receiver on
-    // the LDOK methods. This requires us to re-define the MCTRL register into two halves.
-    // Ideally, this happens in the RAL, but it could also happen in our custom RAL module.
-    // Any solution needs to account for the differences between the 1010 and all other chips.
-
     /// Read the `LDOK` bits.
     ///
     /// Note that the hardware will deassert `LDOK` after the values are loaded.
@@ -130,14 +198,22 @@ impl<const N: u8> Pwm<N> {
     /// Set `LDOK` for zero or more submodules.
     ///
     /// A *high bit* indicates which `LDOK` bit(s) will be *set*.
-    pub fn set_load_ok(&mut self, mask: Mask) {
-        crate::ral::modify_reg!(crate::ral::pwm, self.pwm, MCTRL, LDOK: mask.bits() as u16);
+    ///
+    /// This only needs a shared reference. It stores one byte to the low half of
+    /// `MCTRL`, which doesn't affect the other submodules' `LDOK` bits, or any
+    /// other field. So separate contexts can load values for their own submodules.
+    pub fn set_load_ok(&self, mask: Mask) {
+        let mctrl = self::ral::master_control_low(&self.pwm);
+        crate::ral::write_reg!(self::ral, mctrl, MCTRL_LOW, LDOK: mask.bits());
     }
     /// Clear `LDOK` for zero or more submodules.
     ///
     /// A *high bit* indicates which `LDOK` bit(s) will be *cleared*.
-    pub fn clear_load_ok(&mut self, mask: Mask) {
-        crate::ral::modify_reg!(crate::ral::pwm, self.pwm, MCTRL, CLDOK: mask.bits() as u16);
+    ///
+    /// Like [`set_load_ok`](Self::set_load_ok), this only needs a shared reference.
+    pub fn clear_load_ok(&self, mask: Mask) {
+        let mctrl = self::ral::master_control_low(&self.pwm);
+        crate::ral::write_reg!(self::ral, mctrl, MCTRL_LOW, CLDOK: mask.bits());
     }
     /// Read the `RUN` bit(s).
     pub fn run(&self) -> Mask {
@@ -156,6 +232,7 @@ impl<const N: u8> Pwm<N> {
         let mask = match channel {
             Channel::A => crate::ral::read_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMA_EN),
             Channel::B => crate::ral::read_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMB_EN),
+            Channel::X => crate::ral::read_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMX_EN),
         };
         Mask::from_bits_truncate(mask as u8)
     }
@@ -167,9 +244,429 @@ impl<const N: u8> Pwm<N> {
         match channel {
             Channel::A => crate::ral::modify_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMA_EN: mask),
             Channel::B => crate::ral::modify_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMB_EN: mask),
+            Channel::X => crate::ral::modify_reg!(crate::ral::pwm, self.pwm, OUTEN, PWMX_EN: mask),
+        }
+    }
+
+    /// Apply a configuration to a submodule.
+    ///
+    /// This clears the submodule's `LDOK` bit, then writes every setting in
+    /// `config`, including the initial count and the full reload value. It
+    /// doesn't change the submodule's run state or its output enables. When
+    /// `configure` returns, set your compare values, then set `LDOK` to load
+    /// the buffered values.
+    ///
+    /// Returns an error, without changing the submodule, if the
+    /// configuration isn't valid for this submodule.
+    pub fn configure<const M: u8>(
+        &mut self,
+        sm: &mut Submodule<N, M>,
+        config: &SubmoduleConfig,
+    ) -> Result<(), ConfigError> {
+        config.validate(M)?;
+
+        sm.clear_load_ok(self);
+        sm.set_debug_enable(config.debug_enable);
+        sm.set_wait_enable(config.wait_enable);
+        sm.set_clock_select(config.clock_select);
+        sm.set_prescaler(config.prescaler);
+        sm.set_pair_operation(config.pair_operation);
+        sm.set_load_mode(config.load_mode);
+        sm.set_load_frequency(config.load_frequency);
+        sm.set_initial_count(self, config.initial_count);
+        sm.set_value(FULL_RELOAD_VALUE_REGISTER, config.full_reload_value);
+        Ok(())
+    }
+
+    /// Stop a submodule, and return its outputs and counts to a known state.
+    ///
+    /// This clears the submodule's `RUN` bit, so the counter stops and the
+    /// outputs hold their levels. It then disables the submodule's A, B, and X
+    /// outputs, clears all status flags, and clears `LDOK`. Finally, it zeroes
+    /// the initial count and all six value registers. The other submodules
+    /// keep running, and their outputs don't change.
+    ///
+    /// This doesn't change the submodule's configuration or its interrupt
+    /// enables. The value registers are buffered. Reconfigure the submodule,
+    /// then set `LDOK` to load the new values before you start it again.
+    pub fn reset_submodule<const M: u8>(&mut self, sm: &mut Submodule<N, M>) {
+        sm.set_running(self, false);
+        for channel in [Channel::A, Channel::B, Channel::X] {
+            sm.set_output_enable(self, channel, false);
+        }
+        sm.clear_status(Status::all());
+        sm.clear_load_ok(self);
+        sm.set_initial_count(self, 0);
+        for value_register in [
+            ValueRegister::Val0,
+            ValueRegister::Val1,
+            ValueRegister::Val2,
+            ValueRegister::Val3,
+            ValueRegister::Val4,
+            ValueRegister::Val5,
+        ] {
+            sm.set_value(value_register, 0);
         }
     }
 
+    /// Interrupt once per PWM period.
+    ///
+    /// This enables only the full reload compare interrupt,
+    /// [`Interrupts::COMPARE_VAL1`], and it disables all other submodule
+    /// interrupts. VAL1 compares once per period, so the interrupt fires at
+    /// the same point in every period. It clears any stale `COMPARE_VAL1`
+    /// status before enabling the interrupt.
+    ///
+    /// The status flag stays set until you clear it. In your interrupt
+    /// handler, clear [`Status::COMPARE_VAL1`], or the interrupt fires again
+    /// as soon as the handler returns.
+    ///
+    /// ```no_run
+    /// use imxrt_hal::flexpwm::{self, Status};
+    /// use imxrt_ral::pwm::PWM1;
+    ///
+    /// let (mut pwm, (mut sm0, _, _, _)) = flexpwm::new(unsafe { PWM1::instance() });
+    /// pwm.enable_period_interrupt(&sm0);
+    ///
+    /// // In the interrupt handler...
+    /// if sm0.status().contains(Status::COMPARE_VAL1) {
+    ///     sm0.clear_status(Status::COMPARE_VAL1);
+    ///     // Once per period...
+    /// }
+    /// ```
+    pub fn enable_period_interrupt<const M: u8>(&mut self, sm: &Submodule<N, M>) {
+        sm.clear_status(Status::COMPARE_VAL1);
+        sm.set_interrupts(Interrupts::COMPARE_VAL1);
+    }
+
+    /// Set a center-aligned period for a submodule.
+    ///
+    /// The submodule counts `period` counts per PWM cycle, centered on zero.
+    /// This sets the initial count to `-(period / 2)`, and the full reload
+    /// value so that each cycle is exactly `period` counts. It sets the half
+    /// reload value, VAL0, to zero, the middle of the cycle.
+    ///
+    /// After this call, a pulse with turn on `-width / 2` and turn off
+    /// `width / 2` is centered within the PWM cycle. The pulse is symmetric
+    /// about the half reload point, so half cycle reloads happen in the
+    /// middle of the pulse, and full reloads happen in the middle of the
+    /// off time. To reload on half cycles, use [`LoadMode::reload_half`]
+    /// or [`LoadMode::reload_both`].
+    ///
+    /// Like [`configure`](Self::configure), this clears `LDOK`, and it buffers
+    /// its values. Set `LDOK` to load the values.
+    ///
+    /// `period` is signed, like the value registers.
+    ///
+    /// Returns [`ConfigError::Period`], without changing the submodule, if
+    /// `period` is less than 2.
+    pub fn set_center_aligned<const M: u8>(
+        &mut self,
+        sm: &mut Submodule<N, M>,
+        period: i16,
+    ) -> Result<(), ConfigError> {
+        let (initial_count, half_reload_value, full_reload_value) =
+            center_aligned_period(period).ok_or(ConfigError::Period)?;
+        sm.clear_load_ok(self);
+        sm.set_initial_count(self, initial_count);
+        sm.set_value(HALF_RELOAD_VALUE_REGISTER, half_reload_value);
+        sm.set_value(FULL_RELOAD_VALUE_REGISTER, full_reload_value);
+        Ok(())
+    }
+
+    /// Shift a channel's pulse by `shift` counts.
+    ///
+    /// This moves both the turn on and turn off values by `shift`, so the
+    /// duty cycle doesn't change. A positive shift delays the pulse, and a
+    /// negative shift advances it. Edges that move past the end of the PWM
+    /// period wrap around to its start, and vice versa. After wrapping, the
+    /// turn off value may be less than the turn on value; the pulse then
+    /// spans the end of one period and the start of the next.
+    ///
+    /// The shift is relative to the channel's current edges, and it's computed
+    /// from the submodule's initial count and full reload value. Set the period,
+    /// then the edges, before shifting. Edges outside of the period never compare,
+    /// so they don't move.
+    ///
+    /// Like [`configure`](Self::configure), this clears `LDOK`, and it buffers
+    /// its values. Set `LDOK` to load the values.
+    ///
+    /// Channel X's edges are the reload values, so moving them would change
+    /// the period. This call has no effect for channel X.
+    pub fn set_phase_shift<const M: u8>(
+        &mut self,
+        sm: &mut Submodule<N, M>,
+        channel: Channel,
+        shift: i16,
+    ) {
+        if channel == Channel::X {
+            return;
+        }
+        let (init, modulo) = (sm.initial_count(), sm.value(FULL_RELOAD_VALUE_REGISTER));
+        sm.clear_load_ok(self);
+        for value_register in [turn_on(channel), turn_off(channel)] {
+            let value = shift_compare(init, modulo, sm.value(value_register), shift.into());
+            sm.set_value(value_register, value);
+        }
+    }
+
+    /// Delay a submodule's waveform by a fraction of its period.
+    ///
+    /// `fraction` is the delay in 1/65536ths of the PWM period. To interleave
+    /// four submodules that have the same configuration, like for a four-phase
+    /// converter, delay them by `0`, `0x4000`, `0x8000`, and `0xC000`. Then
+    /// start them together with [`set_run`](Self::set_run).
+    ///
+    /// This offsets the submodule's initial count and its full reload value,
+    /// so that the counter takes longer to reach each compare value. The period
+    /// doesn't change. Compare values that fall outside of the offset period
+    /// wrap around, so every edge keeps its position within the period. If
+    /// offsetting the counter would leave the range of `i16` values, this
+    /// shifts the compare values instead; the waveform is the same.
+    ///
+    /// The delay is relative to the submodule's current timing. Call this once,
+    /// after you set the period and compare values. Compare values outside of the
+    /// period aren't moved, and they may compare after the offset.
+    ///
+    /// Like [`configure`](Self::configure), this clears `LDOK`, and it buffers
+    /// its values. Set `LDOK` to load the values.
+    pub fn set_submodule_phase<const M: u8>(&mut self, sm: &mut Submodule<N, M>, fraction: u16) {
+        let (init, modulo) = (sm.initial_count(), sm.value(FULL_RELOAD_VALUE_REGISTER));
+        let Some((offset, shift)) = submodule_phase(init, modulo, fraction) else {
+            return;
+        };
+        sm.clear_load_ok(self);
+        for value_register in [
+            HALF_RELOAD_VALUE_REGISTER,
+            turn_on(Channel::A),
+            turn_off(Channel::A),
+            turn_on(Channel::B),
+            turn_off(Channel::B),
+        ] {
+            let value = offset_compare(init, modulo, sm.value(value_register), offset, shift);
+            sm.set_value(value_register, value);
+        }
+        sm.set_initial_count(self, (i32::from(init) + offset) as i16);
+        sm.set_value(
+            FULL_RELOAD_VALUE_REGISTER,
+            (i32::from(modulo) + offset) as i16,
+        );
+    }
+
+    /// Write all six of a submodule's value registers, then set its `LDOK`.
+    ///
+    /// This clears the submodule's `LDOK` before it writes the values, so
+    /// the submodule can't load some new values and some old values. The
+    /// submodule loads all values at its next reload opportunity. Use this
+    /// to update a waveform's period and edges together.
+    ///
+    /// Like [`set_load_ok`](Self::set_load_ok), this only needs a shared
+    /// reference.
+    pub fn load_values<const M: u8>(&self, sm: &Submodule<N, M>, values: &ValueRegisters) {
+        sm.clear_load_ok(self);
+        sm.set_values(values);
+        sm.set_load_ok(self);
+    }
+
+    /// Read back the state of a submodule's output.
+    ///
+    /// The state combines the output's pin level with everything that
+    /// decides whether the timer drives the pin: the output enable, the
+    /// output mask, the output source, and faults. Use it to learn why an
+    /// output isn't toggling.
+    pub fn output_state<const M: u8>(&self, sm: &Submodule<N, M>, channel: Channel) -> OutputState {
+        let (level, fault_map) = match channel {
+            Channel::A => (
+                crate::ral::read_reg!(self::ral, sm, SMOCTRL, PWMA_IN),
+                crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0A),
+            ),
+            Channel::B => (
+                crate::ral::read_reg!(self::ral, sm, SMOCTRL, PWMB_IN),
+                crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0B),
+            ),
+            Channel::X => (
+                crate::ral::read_reg!(self::ral, sm, SMOCTRL, PWMX_IN),
+                crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0X),
+            ),
+        };
+        let masked = match channel {
+            Channel::A => crate::ral::read_reg!(crate::ral::pwm, self.pwm, MASK, MASKA),
+            Channel::B => crate::ral::read_reg!(crate::ral::pwm, self.pwm, MASK, MASKB),
+            Channel::X => crate::ral::read_reg!(crate::ral::pwm, self.pwm, MASK, MASKX),
+        };
+        let faults = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FSTS0, FFLAG);
+
+        OutputState {
+            level: level != 0,
+            output_enable: self
+                .output_enable(channel)
+                .intersects(Submodule::<N, M>::MASK),
+            masked: masked & (1 << M) != 0,
+            source: output_source(
+                channel,
+                M,
+                crate::ral::read_reg!(crate::ral::pwm, self.pwm, DTSRCSEL),
+                crate::ral::read_reg!(crate::ral::pwm, self.pwm, SWCOUT),
+            ),
+            faulted: fault_map & faults != 0,
+            running: sm.is_running(self),
+            load_pending: sm.load_ok(self),
+        }
+    }
+
+    /// Set the glitch filter for the fault inputs.
+    ///
+    /// The filter applies to all fault inputs of this PWM peripheral. A
+    /// fault input must hold its level for [`count`](FaultFilter::count)
+    /// consecutive samples before the PWM sees the change. The filter samples
+    /// on the IPG clock, not the prescaled PWM clock, so the prescaler doesn't
+    /// affect the filter.
+    pub fn set_fault_filter(&mut self, filter: FaultFilter) {
+        crate::ral::modify_reg!(crate::ral::pwm, self.pwm, FFILT0,
+            FILT_PER: filter.period as u16,
+            FILT_CNT: filter.count_field()
+        );
+    }
+
+    /// Returns the fault input glitch filter.
+    pub fn fault_filter(&self) -> FaultFilter {
+        let (period, count) =
+            crate::ral::read_reg!(crate::ral::pwm, self.pwm, FFILT0, FILT_PER, FILT_CNT);
+        FaultFilter {
+            period: period as u8,
+            count: count as u8 + FaultFilter::MIN_COUNT,
+        }
+    }
+
+    /// Select the fault inputs that disable a submodule's output.
+    ///
+    /// When any of `faults` is active, the hardware immediately disables the
+    /// output. See [`set_fault_recovery`](Self::set_fault_recovery) to control
+    /// when the output re-enables.
+    pub fn set_fault_disable_map<const M: u8>(
+        &mut self,
+        sm: &mut Submodule<N, M>,
+        channel: Channel,
+        faults: FaultMask,
+    ) {
+        let faults = faults.bits() as u16;
+        match channel {
+            Channel::A => crate::ral::modify_reg!(self::ral, sm, SMDISMAP0, DIS0A: faults),
+            Channel::B => crate::ral::modify_reg!(self::ral, sm, SMDISMAP0, DIS0B: faults),
+            Channel::X => crate::ral::modify_reg!(self::ral, sm, SMDISMAP0, DIS0X: faults),
+        }
+    }
+
+    /// Returns the fault inputs that disable a submodule's output.
+    pub fn fault_disable_map<const M: u8>(
+        &self,
+        sm: &Submodule<N, M>,
+        channel: Channel,
+    ) -> FaultMask {
+        let faults = match channel {
+            Channel::A => crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0A),
+            Channel::B => crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0B),
+            Channel::X => crate::ral::read_reg!(self::ral, sm, SMDISMAP0, DIS0X),
+        };
+        FaultMask::from_bits_truncate(faults as u8)
+    }
+
+    /// Set how outputs recover from one or more faults.
+    ///
+    /// `recovery` applies to each fault input in `faults`. Other fault inputs
+    /// keep their recovery.
+    pub fn set_fault_recovery(&mut self, faults: FaultMask, recovery: FaultRecovery) {
+        let (automatic, safe, full, half) = match recovery {
+            FaultRecovery::Manual => (false, true, true, false),
+            FaultRecovery::AtFullCycle => (true, false, true, false),
+            FaultRecovery::AtHalfCycle => (true, false, true, true),
+        };
+        let faults = faults.bits() as u16;
+
+        let (fauto, fsafe) = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FCTRL0, FAUTO, FSAFE);
+        crate::ral::modify_reg!(crate::ral::pwm, self.pwm, FCTRL0,
+            FAUTO: update_bits(fauto, faults, automatic),
+            FSAFE: update_bits(fsafe, faults, safe)
+        );
+
+        // Don't clear any fault flags.
+        let (ffull, fhalf) = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FSTS0, FFULL, FHALF);
+        crate::ral::write_reg!(crate::ral::pwm, self.pwm, FSTS0,
+            FFULL: update_bits(ffull, faults, full),
+            FHALF: update_bits(fhalf, faults, half)
+        );
+    }
+
+    /// Returns the fault inputs that have detected a fault.
+    ///
+    /// A flag stays set until you [clear it](Self::clear_fault_status), even
+    /// if the fault input is no longer active.
+    pub fn fault_status(&self) -> FaultMask {
+        let fflag = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FSTS0, FFLAG);
+        FaultMask::from_bits_truncate(fflag as u8)
+    }
+
+    /// Clear the fault flags.
+    ///
+    /// With [`FaultRecovery::Manual`], clear the flag to re-enable the outputs.
+    pub fn clear_fault_status(&mut self, faults: FaultMask) {
+        let (ffull, fhalf) = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FSTS0, FFULL, FHALF);
+        crate::ral::write_reg!(crate::ral::pwm, self.pwm, FSTS0,
+            FFLAG: faults.bits() as u16,
+            FFULL: ffull,
+            FHALF: fhalf
+        );
+    }
+
+    /// Returns the fault inputs that are active right now.
+    ///
+    /// Unlike [`fault_status`](Self::fault_status), this reflects the
+    /// filtered fault input, not a latched flag.
+    pub fn active_faults(&self) -> FaultMask {
+        let ffpin = crate::ral::read_reg!(crate::ral::pwm, self.pwm, FSTS0, FFPIN);
+        FaultMask::from_bits_truncate(ffpin as u8)
+    }
+
+    /// Returns the half of the PWM cycle that the submodule counter is in.
+    ///
+    /// The counter only counts up, so this compares the counter with the half
+    /// reload value, VAL0. Before VAL0, the counter is in the
+    /// [`Rising`](Direction::Rising) half; at or after VAL0, the counter is in
+    /// the [`Falling`](Direction::Falling) half. See the [module-level
+    /// documentation](crate::flexpwm#counting) for more information.
+    ///
+    /// This is only meaningful when VAL0 is between the initial count and the
+    /// full reload value.
+    pub fn count_direction<const M: u8>(&self, sm: &Submodule<N, M>) -> Direction {
+        direction(sm.count(), sm.value(HALF_RELOAD_VALUE_REGISTER))
+    }
+
+    /// Select the signal that drives a submodule's output.
+    ///
+    /// The new source takes effect on the submodule's next force event, like
+    /// the one from [`Submodule::force_initialize`]. Use
+    /// [`OutputSource::Software`] to hold the output at a level, and
+    /// [`OutputSource::Generated`] to return to the PWM signal.
+    ///
+    /// Channel X always outputs its generated signal, so this does nothing
+    /// for [`Channel::X`].
+    pub fn set_output_source<const M: u8>(
+        &mut self,
+        _: &Submodule<N, M>,
+        channel: Channel,
+        source: OutputSource,
+    ) {
+        let (dtsrcsel, swcout) = select_output_source(
+            channel,
+            M,
+            source,
+            crate::ral::read_reg!(crate::ral::pwm, self.pwm, DTSRCSEL),
+            crate::ral::read_reg!(crate::ral::pwm, self.pwm, SWCOUT),
+        );
+        crate::ral::write_reg!(crate::ral::pwm, self.pwm, DTSRCSEL, dtsrcsel);
+        crate::ral::write_reg!(crate::ral::pwm, self.pwm, SWCOUT, swcout);
+    }
+
     fn rmw_outen(&mut self, channel: Channel, mask: Mask, enable: bool) {
         let mut outen = self.output_enable(channel);
         outen.set(mask, enable);
@@ -188,10 +685,114 @@ pub fn new<const N: u8>(pwm: pwm::Instance<N>) -> (Pwm<N>, Submodules<N>) {
     (Pwm { pwm }, submodules)
 }
 
+/// The A and B output levels set by [`new_safe`].
+///
+/// Outputs of submodules in the masks are high; the other outputs are low.
+/// The level is the signal that enters the deadtime logic, so output
+/// polarity still applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeLevels {
+    /// Submodules with a high A output.
+    pub high_a: Mask,
+    /// Submodules with a high B output.
+    pub high_b: Mask,
+}
+
+impl SafeLevels {
+    /// All A and B outputs are low.
+    pub const LOW: Self = Self {
+        high_a: Mask::empty(),
+        high_b: Mask::empty(),
+    };
+}
+
+/// Create a PWM peripheral with its submodules, forcing the A and B outputs
+/// to safe levels.
+///
+/// This is like [`new`], except that it also selects the software controlled
+/// output for every A and B output, sets each output to its level in `levels`,
+/// then forces every submodule to initialize. Once you enable an output, it
+/// drives its safe level, not a leftover PWM signal, until you select another
+/// [output source](Pwm::set_output_source).
+///
+/// This doesn't enable any outputs, and it doesn't affect X outputs.
+pub fn new_safe<const N: u8>(pwm: pwm::Instance<N>, levels: SafeLevels) -> (Pwm<N>, Submodules<N>) {
+    let (pwm, mut submodules) = new(pwm);
+
+    let (mut dtsrcsel, mut swcout) = (0, 0);
+    for sm in 0..4 {
+        let mask = Mask::from_bits_truncate(1 << sm);
+        for (channel, high) in [(Channel::A, levels.high_a), (Channel::B, levels.high_b)] {
+            let source = OutputSource::Software(high.contains(mask));
+            (dtsrcsel, swcout) = select_output_source(channel, sm, source, dtsrcsel, swcout);
+        }
+    }
+    crate::ral::write_reg!(crate::ral::pwm, pwm.pwm, DTSRCSEL, dtsrcsel);
+    crate::ral::write_reg!(crate::ral::pwm, pwm.pwm, SWCOUT, swcout);
+
+    submodules.0.force_initialize();
+    submodules.1.force_initialize();
+    submodules.2.force_initialize();
+    submodules.3.force_initialize();
+
+    (pwm, submodules)
+}
+
+/// Submodule interrupts, indexed by PWM instance, then by submodule.
+#[cfg(not(family = "none"))]
+const INTERRUPTS: &[[crate::ral::Interrupt; 4]] = {
+    use crate::ral::Interrupt::*;
+    &[
+        [PWM1_0, PWM1_1, PWM1_2, PWM1_3],
+        #[cfg(not(chip = "imxrt1010"))]
+        [PWM2_0, PWM2_1, PWM2_2, PWM2_3],
+        #[cfg(not(any(chip = "imxrt1010", chip = "imxrt1020")))]
+        [PWM3_0, PWM3_1, PWM3_2, PWM3_3],
+        #[cfg(not(any(chip = "imxrt1010", chip = "imxrt1020")))]
+        [PWM4_0, PWM4_1, PWM4_2, PWM4_3],
+    ]
+};
+
+/// Returns the interrupt for a PWM submodule.
+///
+/// `instance` is the PWM instance, like the '2' in PWM2. On chips with a
+/// single PWM instance, use [`SOLE_INSTANCE`](crate::ral::SOLE_INSTANCE).
+/// `submodule` is the submodule number, 0 through 3.
+///
+/// Each submodule has its own interrupt. Use this to unmask the interrupt,
+/// or to bind an interrupt handler, without looking up the interrupt in the
+/// RAL. If you have a [`Submodule`], you can use its `INTERRUPT` constant.
+///
+/// ```no_run
+/// use imxrt_hal::flexpwm;
+/// use imxrt_ral::Interrupt;
+///
+/// const PWM2_SM3: Interrupt = flexpwm::interrupt_for_submodule(2, 3);
+/// assert_eq!(PWM2_SM3, Interrupt::PWM2_3);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the instance or submodule doesn't exist.
+#[cfg(not(family = "none"))]
+pub const fn interrupt_for_submodule(instance: u8, submodule: u8) -> crate::ral::Interrupt {
+    #[cfg(chip = "imxrt1010")]
+    let index = instance as usize;
+    #[cfg(not(chip = "imxrt1010"))]
+    let index = (instance as usize).wrapping_sub(1);
+    assert!(index < INTERRUPTS.len(), "PWM instance doesn't exist");
+    assert!(submodule < 4, "PWM submodule doesn't exist");
+    INTERRUPTS[index][submodule as usize]
+}
+
 impl<const N: u8, const M: u8> Submodule<N, M> {
     /// The mask for this submodule.
     pub const MASK: Mask = Mask::from_bits_truncate(1 << M);
 
+    /// The interrupt for this submodule.
+    #[cfg(not(family = "none"))]
+    pub const INTERRUPT: crate::ral::Interrupt = interrupt_for_submodule(N, M);
+
     /// Returns the mask for this submodule.
     pub const fn mask(&self) -> Mask {
         Self::MASK
@@ -346,6 +947,16 @@ impl<const N: u8, const M: u8> Submodule<N, M> {
         crate::ral::modify_reg!(self::ral, self, SMCTRL2, CLK_SEL: clock_select as u16);
     }
 
+    /// Force the submodule to initialize.
+    ///
+    /// This selects the submodule's local force signal, then asserts it.
+    /// The counter reloads the initial count, and the outputs take their
+    /// initial values.
+    pub fn force_initialize(&mut self) {
+        crate::ral::modify_reg!(self::ral, self, SMCTRL2, FORCE_SEL: 0, FRCEN: 1);
+        crate::ral::modify_reg!(self::ral, self, SMCTRL2, FORCE: 1);
+    }
+
     /// Returns the load mode.
     pub fn load_mode(&self) -> LoadMode {
         let (immediate, full, half) =
@@ -405,6 +1016,20 @@ impl<const N: u8, const M: u8> Submodule<N, M> {
         crate::ral::write_reg!(self::ral, self, SMINTEN, interrupts.bits());
     }
 
+    /// Read the compares that generate output triggers.
+    pub fn output_triggers(&self) -> OutputTriggers {
+        let triggers = crate::ral::read_reg!(self::ral, self, SMTCTRL, OUT_TRIG_EN);
+        OutputTriggers::from_bits_truncate(triggers)
+    }
+
+    /// Select the compares that generate output triggers.
+    ///
+    /// Output triggers route through the crossbar to other peripherals, like
+    /// the ADC_ETC. They're generated even if the PWM outputs are disabled.
+    pub fn set_output_triggers(&self, triggers: OutputTriggers) {
+        crate::ral::modify_reg!(self::ral, self, SMTCTRL, OUT_TRIG_EN: triggers.bits());
+    }
+
     /// Read one of the six value registers.
     ///
     /// The return indicates the count value that will cause a comparison.
@@ -447,6 +1072,31 @@ impl<const N: u8, const M: u8> Submodule<N, M> {
         }
     }
 
+    /// Read all six value registers.
+    pub fn values(&self) -> ValueRegisters {
+        ValueRegisters {
+            val0: self.value(ValueRegister::Val0),
+            val1: self.value(ValueRegister::Val1),
+            val2: self.value(ValueRegister::Val2),
+            val3: self.value(ValueRegister::Val3),
+            val4: self.value(ValueRegister::Val4),
+            val5: self.value(ValueRegister::Val5),
+        }
+    }
+
+    /// Set all six value registers.
+    ///
+    /// This doesn't touch `LDOK`. To load the values together, see
+    /// [`Pwm::load_values`].
+    pub fn set_values(&self, values: &ValueRegisters) {
+        self.set_value(ValueRegister::Val0, values.val0);
+        self.set_value(ValueRegister::Val1, values.val1);
+        self.set_value(ValueRegister::Val2, values.val2);
+        self.set_value(ValueRegister::Val3, values.val3);
+        self.set_value(ValueRegister::Val4, values.val4);
+        self.set_value(ValueRegister::Val5, values.val5);
+    }
+
     /// Set the turn on compare for a channel.
     ///
     /// This is the same as using [`turn_on()`] to produce a value register, then
@@ -463,18 +1113,181 @@ impl<const N: u8, const M: u8> Submodule<N, M> {
         self.set_value(turn_off(channel), compare);
     }
 
+    /// Returns the deadtime count for a channel.
+    ///
+    /// See [`set_deadtime`](Self::set_deadtime) for more information. Channel X
+    /// has no deadtime, so this returns zero for channel X.
+    pub fn deadtime(&self, channel: Channel) -> u16 {
+        match channel {
+            Channel::A => crate::ral::read_reg!(self::ral, self, SMDTCNT0),
+            Channel::B => crate::ral::read_reg!(self::ral, self, SMDTCNT1),
+            Channel::X => 0,
+        }
+    }
+
+    /// Set the deadtime count for a channel.
+    ///
+    /// When the submodule operates in complementary mode, the hardware delays
+    /// the channel's rising edge by `count` PWM clock cycles. Deadtime counts
+    /// are not affected by the prescaler. Channel X has no deadtime, so this
+    /// has no effect for channel X.
+    pub fn set_deadtime(&mut self, channel: Channel, count: u16) {
+        match channel {
+            Channel::A => crate::ral::write_reg!(self::ral, self, SMDTCNT0, count),
+            Channel::B => crate::ral::write_reg!(self::ral, self, SMDTCNT1, count),
+            Channel::X => {}
+        }
+    }
+
+    /// Set a complementary duty cycle, accounting for deadtime.
+    ///
+    /// `duty` is the time, in counts, that channel A is high during each PWM
+    /// period. The submodule should operate in [complementary mode](PairOperation::Complementary),
+    /// so that channel B is the complement of channel A.
+    ///
+    /// This call computes the channel A turn on and turn off values so that
+    /// A's pulse stays centered in the PWM period, even after the hardware inserts
+    /// the [deadtime](Self::set_deadtime) for both channels. The implementation reads
+    /// the initial count, the full reload value, the prescaler, and the deadtimes
+    /// to compute the edges. Set those values before calling this method.
+    ///
+    /// The duty is clamped so that neither channel has a negative on time.
+    /// A duty of zero holds A low, and a duty that's equal to or larger than
+    /// the PWM period (less both deadtimes) holds B low.
+    ///
+    /// Like other value registers, the compare values are buffered. Set the
+    /// submodule's LDOK bit to load the values.
+    pub fn set_complementary_duty(&self, duty: i16) {
+        let divider = self.prescaler().divider();
+        let edges = complementary_edges(
+            self.initial_count(),
+            self.value(FULL_RELOAD_VALUE_REGISTER),
+            self.deadtime(Channel::A) as u32 / divider,
+            self.deadtime(Channel::B) as u32 / divider,
+            duty,
+        );
+        self.set_turn_on(Channel::A, edges.0);
+        self.set_turn_off(Channel::A, edges.1);
+    }
+
+    /// Returns `true` if the fractional delay logic is enabled for the PWM
+    /// period and both channels.
+    pub fn fractional_delay_enable(&self) -> bool {
+        let (en1, en23, en45) =
+            crate::ral::read_reg!(self::ral, self, SMFRCTRL, FRAC1_EN, FRAC23_EN, FRAC45_EN);
+        #[cfg(family = "imxrt10xx")]
+        let powered = crate::ral::read_reg!(self::ral, self, SMFRCTRL, FRAC_PU == 1);
+        #[cfg(not(family = "imxrt10xx"))]
+        let powered = true;
+        powered && en1 != 0 && en23 != 0 && en45 != 0
+    }
+
+    /// Enable or disable the fractional delay logic.
+    ///
+    /// When enabled, the fractional values written with
+    /// [`set_fractional_value()`](Self::set_fractional_value) delay the
+    /// PWM period and the edges of both channels. When disabled, the
+    /// fractional values are ignored.
+    ///
+    /// On 10xx chips, this also powers up the fractional delay circuit when
+    /// enabling, and powers it down when disabling.
+    pub fn set_fractional_delay_enable(&mut self, enable: bool) {
+        let enable = enable as u16;
+        #[cfg(family = "imxrt10xx")]
+        crate::ral::modify_reg!(
+            self::ral,
+            self,
+            SMFRCTRL,
+            FRAC1_EN: enable,
+            FRAC23_EN: enable,
+            FRAC45_EN: enable,
+            FRAC_PU: enable
+        );
+        #[cfg(not(family = "imxrt10xx"))]
+        crate::ral::modify_reg!(
+            self::ral,
+            self,
+            SMFRCTRL,
+            FRAC1_EN: enable,
+            FRAC23_EN: enable,
+            FRAC45_EN: enable
+        );
+    }
+
+    /// Read the fractional part of a value register.
+    ///
+    /// The return is expressed in 1/32 of a PWM clock cycle.
+    ///
+    /// # Panics
+    ///
+    /// `Val0` does not have a fractional value. This call panics
+    /// if `value_register` is `Val0`.
+    pub fn fractional_value(&self, value_register: ValueRegister) -> u8 {
+        use crate::ral::read_reg;
+        let frac = match value_register {
+            ValueRegister::Val0 => panic!("VAL0 has no fractional value"),
+            ValueRegister::Val1 => read_reg!(self::ral, self, SMFRACVAL1, FRACVAL1),
+            ValueRegister::Val2 => read_reg!(self::ral, self, SMFRACVAL2, FRACVAL2),
+            ValueRegister::Val3 => read_reg!(self::ral, self, SMFRACVAL3, FRACVAL3),
+            ValueRegister::Val4 => read_reg!(self::ral, self, SMFRACVAL4, FRACVAL4),
+            ValueRegister::Val5 => read_reg!(self::ral, self, SMFRACVAL5, FRACVAL5),
+        };
+        frac as u8
+    }
+
+    /// Set the fractional part of a value register.
+    ///
+    /// `frac` is expressed in 1/32 of a PWM clock cycle, and it delays the
+    /// comparison of the associated value register. Values larger than
+    /// [`MAX_FRACTIONAL_VALUE`] are clamped. The fractional value takes
+    /// effect when the value registers are loaded, and only if you've enabled
+    /// the [fractional delay logic](Self::set_fractional_delay_enable).
+    ///
+    /// A `Val1` fractional value lengthens the PWM period. `Val2` through `Val5`
+    /// fractional values delay the turn on and turn off edges of channels A and B.
+    ///
+    /// # Panics
+    ///
+    /// `Val0` does not have a fractional value. This call panics
+    /// if `value_register` is `Val0`.
+    pub fn set_fractional_value(&mut self, value_register: ValueRegister, frac: u8) {
+        use crate::ral::write_reg;
+        let frac = frac.min(MAX_FRACTIONAL_VALUE) as u16;
+        match value_register {
+            ValueRegister::Val0 => panic!("VAL0 has no fractional value"),
+            ValueRegister::Val1 => write_reg!(self::ral, self, SMFRACVAL1, FRACVAL1: frac),
+            ValueRegister::Val2 => write_reg!(self::ral, self, SMFRACVAL2, FRACVAL2: frac),
+            ValueRegister::Val3 => write_reg!(self::ral, self, SMFRACVAL3, FRACVAL3: frac),
+            ValueRegister::Val4 => write_reg!(self::ral, self, SMFRACVAL4, FRACVAL4: frac),
+            ValueRegister::Val5 => write_reg!(self::ral, self, SMFRACVAL5, FRACVAL5: frac),
+        }
+    }
+
+    /// Set the fractional delay for a channel's turn off edge.
+    ///
+    /// This is the same as using [`turn_off()`] to produce a value register, then
+    /// calling [`set_fractional_value()`](Self::set_fractional_value) with that result.
+    /// Use this to place the channel's falling edge with finer resolution than
+    /// the integer compare.
+    pub fn set_fractional(&mut self, channel: Channel, frac: u8) {
+        self.set_fractional_value(turn_off(channel), frac);
+    }
+
     /// Returns `true` if this submodule's `LDOK` bit is set.
     pub fn load_ok(&self, pwm: &Pwm<N>) -> bool {
         pwm.load_ok().intersects(Self::MASK)
     }
 
     /// Set the `LDOK` bit for this submodule.
-    pub fn set_load_ok(&self, pwm: &mut Pwm<N>) {
+    ///
+    /// This doesn't affect the other submodules, so it only needs
+    /// a shared reference to the PWM.
+    pub fn set_load_ok(&self, pwm: &Pwm<N>) {
         pwm.set_load_ok(Self::MASK);
     }
 
     /// Clear the `LDOK` bit for this submodule.
-    pub fn clear_load_ok(&self, pwm: &mut Pwm<N>) {
+    pub fn clear_load_ok(&self, pwm: &Pwm<N>) {
         pwm.clear_load_ok(Self::MASK);
     }
 
@@ -501,6 +1314,106 @@ impl<const N: u8, const M: u8> Submodule<N, M> {
     }
 }
 
+/// Computes the initial count, half reload value, and full reload value
+/// for a center-aligned period.
+///
+/// Returns `None` if the period is too short.
+const fn center_aligned_period(period: i16) -> Option<(i16, i16, i16)> {
+    if period < 2 {
+        return None;
+    }
+    let init = -(period / 2);
+    Some((init, 0, init + (period - 1)))
+}
+
+/// Decide the cycle half from the counter and the half reload value.
+const fn direction(count: i16, half_reload_value: i16) -> Direction {
+    if count < half_reload_value {
+        Direction::Rising
+    } else {
+        Direction::Falling
+    }
+}
+
+/// Moves a compare value by `shift` counts, wrapping it within the period.
+///
+/// `init` and `modulo` describe the PWM period. Values outside of the period
+/// are returned unchanged.
+fn shift_compare(init: i16, modulo: i16, value: i16, shift: i32) -> i16 {
+    if !(init..=modulo).contains(&value) {
+        return value;
+    }
+    let (init, period) = (i32::from(init), i32::from(modulo) - i32::from(init) + 1);
+    (init + (i32::from(value) - init + shift).rem_euclid(period)) as i16
+}
+
+/// Moves a compare value into a period that's offset by `offset` counts, then
+/// shifts it by `shift` counts.
+///
+/// `init` and `modulo` describe the period before the offset. The compare value
+/// keeps its position within the period. Values outside of the period are
+/// returned unchanged.
+fn offset_compare(init: i16, modulo: i16, value: i16, offset: i32, shift: i32) -> i16 {
+    if !(init..=modulo).contains(&value) {
+        return value;
+    }
+    (i32::from(shift_compare(init, modulo, value, shift - offset)) + offset) as i16
+}
+
+/// Computes how to delay a submodule by `fraction` / 65536 of its period.
+///
+/// `init` and `modulo` describe the PWM period. Returns the offset for the
+/// initial count and full reload value, and the shift for the compare values,
+/// in that order. Returns `None` if the period is empty.
+fn submodule_phase(init: i16, modulo: i16, fraction: u16) -> Option<(i32, i32)> {
+    let (init, modulo) = (i32::from(init), i32::from(modulo));
+    let period = modulo - init + 1;
+    if period <= 0 {
+        return None;
+    }
+    let delay = ((period as u32 * u32::from(fraction)) >> 16) as i32;
+
+    // Moving the period down by `delay` delays every edge by `delay`. So does
+    // moving it up by `period - delay`. If neither fits in an i16, keep the
+    // period, and shift the compare values.
+    let i16_range = i32::from(i16::MIN)..=i32::from(i16::MAX);
+    if i16_range.contains(&(init - delay)) {
+        Some((-delay, 0))
+    } else if i16_range.contains(&(modulo + period - delay)) {
+        Some((period - delay, 0))
+    } else {
+        Some((0, delay))
+    }
+}
+
+/// Computes the turn on and turn off values for channel A in complementary mode.
+///
+/// `init` and `modulo` describe the PWM period. `dt_a` and `dt_b` are the deadtimes,
+/// in counts, inserted before the rising edge of each channel. Returns the
+/// turn on and turn off values, in that order.
+fn complementary_edges(init: i16, modulo: i16, dt_a: u32, dt_b: u32, duty: i16) -> (i16, i16) {
+    let (init, modulo) = (i32::from(init), i32::from(modulo));
+    let period = (modulo - init + 1).max(0);
+    let (dt_a, dt_b) = (
+        dt_a.min(period as u32) as i32,
+        dt_b.min(period as u32) as i32,
+    );
+
+    // The hardware delays A's rising edge by its deadtime, and B needs
+    // its own deadtime before it can turn on.
+    let max_duty = (period - dt_a - dt_b).max(0);
+    let duty = i32::from(duty).clamp(0, max_duty);
+
+    // A is high from (on + dt_a) until off.
+    let center = init + period / 2;
+    let on = (center - duty / 2 - dt_a).max(init);
+    let off = (on + dt_a + duty).min(modulo);
+    (on as i16, off as i16)
+}
+
+/// The largest fractional value, in 1/32 of a PWM clock cycle.
+pub const MAX_FRACTIONAL_VALUE: u8 = 31;
+
 /// PWM clock prescaler.
 ///
 /// Affects all timing, except for the glitch filters.
@@ -603,6 +1516,258 @@ impl LoadMode {
     }
 }
 
+/// A glitch filter for the fault inputs.
+///
+/// Use [`Pwm::set_fault_filter`] to apply the filter. The filter samples
+/// the fault inputs every `period` IPG clock cycles. The default filter is
+/// [`DISABLED`](Self::DISABLED).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultFilter {
+    /// The sample period, in IPG clock cycles.
+    ///
+    /// Zero disables the filter.
+    pub period: u8,
+    /// The number of consecutive, equal samples that change the fault input.
+    ///
+    /// The implementation clamps this between [`MIN_COUNT`](Self::MIN_COUNT)
+    /// and [`MAX_COUNT`](Self::MAX_COUNT).
+    pub count: u8,
+}
+
+impl FaultFilter {
+    /// The fewest samples supported by the filter.
+    pub const MIN_COUNT: u8 = 3;
+    /// The most samples supported by the filter.
+    pub const MAX_COUNT: u8 = 10;
+
+    /// The fault inputs aren't filtered.
+    pub const DISABLED: Self = Self {
+        period: 0,
+        count: Self::MIN_COUNT,
+    };
+
+    /// Returns the `FILT_CNT` field value.
+    const fn count_field(&self) -> u16 {
+        let count = if self.count < Self::MIN_COUNT {
+            Self::MIN_COUNT
+        } else if self.count > Self::MAX_COUNT {
+            Self::MAX_COUNT
+        } else {
+            self.count
+        };
+        (count - Self::MIN_COUNT) as u16
+    }
+
+    /// Returns the filter's latency, in IPG clock cycles.
+    ///
+    /// This is how long a fault input takes to reach the PWM.
+    /// When the filter is disabled, the latency is three IPG
+    /// clock cycles.
+    pub const fn latency_cycles(&self) -> u32 {
+        (self.count_field() as u32 + 4) * self.period as u32 + 3
+    }
+}
+
+impl Default for FaultFilter {
+    fn default() -> Self {
+        Self::DISABLED
+    }
+}
+
+/// How outputs recover after a fault.
+///
+/// Use [`Pwm::set_fault_recovery`] to select the recovery for each fault
+/// input. In every case, a fault disables the outputs immediately, and the
+/// outputs stay disabled while the fault input is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultRecovery {
+    /// Re-enable the outputs once software clears the fault flag.
+    ///
+    /// After you [clear the flag](Pwm::clear_fault_status), and the fault
+    /// input is inactive, the outputs re-enable at the start of the next
+    /// full cycle.
+    Manual,
+    /// Re-enable the outputs at the start of the next full cycle after
+    /// the fault input is inactive.
+    ///
+    /// You don't need to clear the fault flag.
+    AtFullCycle,
+    /// Re-enable the outputs at the start of the next half, or full, cycle
+    /// after the fault input is inactive.
+    ///
+    /// You don't need to clear the fault flag.
+    AtHalfCycle,
+}
+
+/// Returns `bits` with the `mask` bits set or cleared.
+const fn update_bits(bits: u16, mask: u16, set: bool) -> u16 {
+    if set {
+        bits | mask
+    } else {
+        bits & !mask
+    }
+}
+
+/// An error when applying a [`SubmoduleConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// Submodule 0 cannot use its own clock.
+    ///
+    /// Select [`ClockSelect::Submodule0`] for submodules 1 through 3.
+    Submodule0Clock,
+    /// The load mode is a reload cycle, yet neither `full`
+    /// nor `half` is set.
+    LoadMode,
+    /// The load frequency is not between 1 and 16.
+    LoadFrequency,
+    /// The full reload value is not greater than the initial count.
+    Period,
+}
+
+/// A submodule configuration.
+///
+/// Use the builder methods to change the defaults, then apply the
+/// configuration with [`Pwm::configure`]. The default configuration
+///
+/// - runs from the IPG clock, divided by 1.
+/// - operates channels A and B independently.
+/// - reloads buffered values on every full cycle.
+/// - keeps running in debug and wait modes.
+/// - counts over the full range of `i16` values.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use hal::flexpwm;
+///
+/// # || -> Result<(), flexpwm::ConfigError> {
+/// const CONFIG: flexpwm::SubmoduleConfig = flexpwm::SubmoduleConfig::new()
+///     .prescaler(flexpwm::Prescaler::Prescaler8)
+///     .period(-1000, 999);
+///
+/// let pwm2 = unsafe { ral::pwm::PWM2::instance() };
+/// let (mut pwm, (_, _, mut sm2, _)) = flexpwm::new(pwm2);
+/// pwm.configure(&mut sm2, &CONFIG)?;
+///
+/// sm2.set_turn_on(flexpwm::Channel::A, -500);
+/// sm2.set_turn_off(flexpwm::Channel::A, 500);
+/// sm2.set_load_ok(&mut pwm);
+/// sm2.set_running(&mut pwm, true);
+/// # Ok(()) }();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmoduleConfig {
+    clock_select: ClockSelect,
+    prescaler: Prescaler,
+    pair_operation: PairOperation,
+    load_mode: LoadMode,
+    load_frequency: u16,
+    debug_enable: bool,
+    wait_enable: bool,
+    initial_count: i16,
+    full_reload_value: i16,
+}
+
+impl SubmoduleConfig {
+    /// Create a configuration with the default settings.
+    pub const fn new() -> Self {
+        Self {
+            clock_select: ClockSelect::Ipg,
+            prescaler: Prescaler::Prescaler1,
+            pair_operation: PairOperation::Independent,
+            load_mode: LoadMode::reload_full(),
+            load_frequency: 1,
+            debug_enable: true,
+            wait_enable: true,
+            initial_count: i16::MIN,
+            full_reload_value: i16::MAX,
+        }
+    }
+
+    /// Set the clock selection.
+    pub const fn clock_select(mut self, clock_select: ClockSelect) -> Self {
+        self.clock_select = clock_select;
+        self
+    }
+
+    /// Set the clock prescaler.
+    pub const fn prescaler(mut self, prescaler: Prescaler) -> Self {
+        self.prescaler = prescaler;
+        self
+    }
+
+    /// Set the pair operation.
+    pub const fn pair_operation(mut self, pair_operation: PairOperation) -> Self {
+        self.pair_operation = pair_operation;
+        self
+    }
+
+    /// Set the load mode.
+    pub const fn load_mode(mut self, load_mode: LoadMode) -> Self {
+        self.load_mode = load_mode;
+        self
+    }
+
+    /// Set the load frequency, between 1 and 16.
+    ///
+    /// See [`Submodule::load_frequency`] for more information.
+    pub const fn load_frequency(mut self, load_frequency: u16) -> Self {
+        self.load_frequency = load_frequency;
+        self
+    }
+
+    /// Keep running (`true`) or stop (`false`) in debug mode.
+    pub const fn debug_enable(mut self, enable: bool) -> Self {
+        self.debug_enable = enable;
+        self
+    }
+
+    /// Keep running (`true`) or stop (`false`) in wait mode.
+    pub const fn wait_enable(mut self, enable: bool) -> Self {
+        self.wait_enable = enable;
+        self
+    }
+
+    /// Set the PWM period.
+    ///
+    /// The counter starts at `initial_count`, and counts up to
+    /// `full_reload_value`.
+    pub const fn period(mut self, initial_count: i16, full_reload_value: i16) -> Self {
+        self.initial_count = initial_count;
+        self.full_reload_value = full_reload_value;
+        self
+    }
+
+    /// Check that the configuration is valid for submodule `m`.
+    const fn validate(&self, m: u8) -> Result<(), ConfigError> {
+        if m == 0 && matches!(self.clock_select, ClockSelect::Submodule0) {
+            return Err(ConfigError::Submodule0Clock);
+        }
+        if let LoadMode::ReloadCycle {
+            full: false,
+            half: false,
+        } = self.load_mode
+        {
+            return Err(ConfigError::LoadMode);
+        }
+        if self.load_frequency < 1 || self.load_frequency > 16 {
+            return Err(ConfigError::LoadFrequency);
+        }
+        if self.full_reload_value <= self.initial_count {
+            return Err(ConfigError::Period);
+        }
+        Ok(())
+    }
+}
+
+impl Default for SubmoduleConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 bitflags::bitflags! {
     /// Status register flags.
     pub struct Status : u16 {
@@ -668,6 +1833,28 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// Output trigger enables.
+    ///
+    /// Each flag pulses a submodule output trigger when its value register
+    /// compares. VAL0, VAL2, and VAL4 drive `PWM_OUT_TRIG0`; VAL1, VAL3, and VAL5
+    /// drive `PWM_OUT_TRIG1`.
+    pub struct OutputTriggers : u16 {
+        /// VAL5 compare drives `PWM_OUT_TRIG1`.
+        const VAL5 = 1 << 5;
+        /// VAL4 compare drives `PWM_OUT_TRIG0`.
+        const VAL4 = 1 << 4;
+        /// VAL3 compare drives `PWM_OUT_TRIG1`.
+        const VAL3 = 1 << 3;
+        /// VAL2 compare drives `PWM_OUT_TRIG0`.
+        const VAL2 = 1 << 2;
+        /// VAL1 compare drives `PWM_OUT_TRIG1`.
+        const VAL1 = 1 << 1;
+        /// VAL0 compare drives `PWM_OUT_TRIG0`.
+        const VAL0 = 1 << 0;
+    }
+}
+
 /// PWM value registers.
 ///
 /// These value registers describe when PWM counters reset, and when outputs
@@ -675,9 +1862,11 @@ bitflags::bitflags! {
 /// const functions to describe these values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValueRegister {
-    /// The [`HALF_RELOAD_VALUE_REGISTER`].
+    /// The [`HALF_RELOAD_VALUE_REGISTER`], and the [`turn_on()`] register
+    /// for [`Channel::X`].
     Val0,
-    /// The [`FULL_RELOAD_VALUE_REGISTER`].
+    /// The [`FULL_RELOAD_VALUE_REGISTER`], and the [`turn_off()`] register
+    /// for [`Channel::X`].
     Val1,
     /// The [`turn_on()`] register for [`Channel::A`].
     Val2,
@@ -689,6 +1878,65 @@ pub enum ValueRegister {
     Val5,
 }
 
+/// The values of all six value registers.
+///
+/// Use [`Submodule::values`] to read a submodule's value registers, and
+/// [`Pwm::load_values`] to write them together, then load them with one
+/// `LDOK`.
+///
+/// ```
+/// use imxrt_hal::flexpwm::{turn_off, turn_on, Channel, ValueRegister, ValueRegisters};
+///
+/// let mut values = ValueRegisters::default();
+/// values.set(ValueRegister::Val1, 999);
+/// values.set(turn_on(Channel::A), 100);
+/// values.set(turn_off(Channel::A), 600);
+/// assert_eq!(values.val2, 100);
+/// assert_eq!(values.get(turn_off(Channel::A)), 600);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValueRegisters {
+    /// The value of [`ValueRegister::Val0`].
+    pub val0: i16,
+    /// The value of [`ValueRegister::Val1`].
+    pub val1: i16,
+    /// The value of [`ValueRegister::Val2`].
+    pub val2: i16,
+    /// The value of [`ValueRegister::Val3`].
+    pub val3: i16,
+    /// The value of [`ValueRegister::Val4`].
+    pub val4: i16,
+    /// The value of [`ValueRegister::Val5`].
+    pub val5: i16,
+}
+
+impl ValueRegisters {
+    /// Returns the value of one value register.
+    pub const fn get(&self, value_register: ValueRegister) -> i16 {
+        match value_register {
+            ValueRegister::Val0 => self.val0,
+            ValueRegister::Val1 => self.val1,
+            ValueRegister::Val2 => self.val2,
+            ValueRegister::Val3 => self.val3,
+            ValueRegister::Val4 => self.val4,
+            ValueRegister::Val5 => self.val5,
+        }
+    }
+
+    /// Set the value of one value register.
+    pub fn set(&mut self, value_register: ValueRegister, value: i16) {
+        let field = match value_register {
+            ValueRegister::Val0 => &mut self.val0,
+            ValueRegister::Val1 => &mut self.val1,
+            ValueRegister::Val2 => &mut self.val2,
+            ValueRegister::Val3 => &mut self.val3,
+            ValueRegister::Val4 => &mut self.val4,
+            ValueRegister::Val5 => &mut self.val5,
+        };
+        *field = value;
+    }
+}
+
 /// The full reload value register.
 ///
 /// When this register compares to the counter value, the counter
@@ -708,6 +1956,7 @@ pub const fn turn_on(channel: Channel) -> ValueRegister {
     match channel {
         Channel::A => ValueRegister::Val2,
         Channel::B => ValueRegister::Val4,
+        Channel::X => ValueRegister::Val0,
     }
 }
 
@@ -719,6 +1968,7 @@ pub const fn turn_off(channel: Channel) -> ValueRegister {
     match channel {
         Channel::A => ValueRegister::Val3,
         Channel::B => ValueRegister::Val5,
+        Channel::X => ValueRegister::Val1,
     }
 }
 
@@ -729,4 +1979,402 @@ pub enum Channel {
     A,
     /// Channel B.
     B,
+    /// Channel X.
+    ///
+    /// Channel X turns on at the half reload value, and it turns off at the
+    /// full reload value. See the [module-level documentation](crate::flexpwm#channel-x)
+    /// for more information.
+    X,
+}
+
+/// The half of the PWM cycle that a counter is in.
+///
+/// See [`Pwm::count_direction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// The first half, from the initial count up to the half reload value.
+    ///
+    /// In a center-aligned PWM, the counter is counting toward the peak.
+    Rising,
+    /// The second half, from the half reload value up to the full reload value.
+    ///
+    /// In a center-aligned PWM, the counter is counting away from the peak.
+    Falling,
+}
+
+/// The signal that drives a PWM output.
+///
+/// See [`OutputState::source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputSource {
+    /// The submodule's generated PWM signal.
+    Generated,
+    /// The inverse of the submodule's generated PWM signal.
+    Inverted,
+    /// The software controlled output, with its level.
+    ///
+    /// The output takes this level after a force event.
+    Software(bool),
+    /// The submodule's external input.
+    External,
+}
+
+/// A snapshot of a PWM output's state.
+///
+/// Use [`Pwm::output_state`] to read the state. The timer drives the pin
+/// when the output is enabled, unmasked, and not faulted, and when the
+/// submodule is running. See [`is_driven`](Self::is_driven).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputState {
+    /// The pin level; `true` is high.
+    pub level: bool,
+    /// The output is enabled.
+    ///
+    /// When disabled, the output doesn't drive the pin.
+    pub output_enable: bool,
+    /// The output is masked.
+    ///
+    /// A masked output is driven inactive.
+    pub masked: bool,
+    /// The signal that drives the output.
+    pub source: OutputSource,
+    /// A fault input that disables this output is active.
+    ///
+    /// The output is disabled until the fault clears.
+    pub faulted: bool,
+    /// The submodule is running.
+    pub running: bool,
+    /// The submodule's `LDOK` bit is set.
+    ///
+    /// The submodule still holds buffered values that it hasn't loaded.
+    /// If this stays `true`, the submodule may not be running, or it may
+    /// not reach a reload opportunity.
+    pub load_pending: bool,
+}
+
+impl OutputState {
+    /// Returns `true` if the timer drives the output pin.
+    pub const fn is_driven(&self) -> bool {
+        self.output_enable && !self.masked && !self.faulted && self.running
+    }
+}
+
+/// Decode an output's source from the `DTSRCSEL` and `SWCOUT` registers.
+fn output_source(channel: Channel, sm: u8, dtsrcsel: u16, swcout: u16) -> OutputSource {
+    // Channel A is PWM23, and channel B is PWM45. PWM45 occupies the
+    // lower bits of each submodule's field. Channel X bypasses the
+    // deadtime logic, so it's always the generated signal.
+    let pair = match channel {
+        Channel::A => 1,
+        Channel::B => 0,
+        Channel::X => return OutputSource::Generated,
+    };
+    let sel = (dtsrcsel >> (4 * sm + 2 * pair)) & 0b11;
+    match sel {
+        0 => OutputSource::Generated,
+        1 => OutputSource::Inverted,
+        2 => OutputSource::Software((swcout >> (2 * sm + pair)) & 1 != 0),
+        _ => OutputSource::External,
+    }
+}
+
+/// Encode an output's source into the `DTSRCSEL` and `SWCOUT` registers.
+///
+/// The inverse of [`output_source`]. Returns the registers unchanged for
+/// channel X.
+fn select_output_source(
+    channel: Channel,
+    sm: u8,
+    source: OutputSource,
+    dtsrcsel: u16,
+    swcout: u16,
+) -> (u16, u16) {
+    let pair = match channel {
+        Channel::A => 1,
+        Channel::B => 0,
+        Channel::X => return (dtsrcsel, swcout),
+    };
+    let (sel, level) = match source {
+        OutputSource::Generated => (0, None),
+        OutputSource::Inverted => (1, None),
+        OutputSource::Software(level) => (2, Some(level)),
+        OutputSource::External => (3, None),
+    };
+    let shift = 4 * sm + 2 * pair;
+    let dtsrcsel = (dtsrcsel & !(0b11 << shift)) | (sel << shift);
+    let bit = 1 << (2 * sm + pair);
+    let swcout = match level {
+        Some(true) => swcout | bit,
+        Some(false) => swcout & !bit,
+        None => swcout,
+    };
+    (dtsrcsel, swcout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        center_aligned_period, complementary_edges, direction, offset_compare, output_source,
+        shift_compare, submodule_phase, Channel, ClockSelect, ConfigError, Direction, FaultFilter,
+        LoadMode, OutputSource, SubmoduleConfig, ValueRegister, FULL_RELOAD_VALUE_REGISTER,
+        HALF_RELOAD_VALUE_REGISTER,
+    };
+
+    #[test]
+    fn value_registers() {
+        use super::ValueRegisters;
+        let registers = [
+            ValueRegister::Val0,
+            ValueRegister::Val1,
+            ValueRegister::Val2,
+            ValueRegister::Val3,
+            ValueRegister::Val4,
+            ValueRegister::Val5,
+        ];
+        let mut values = ValueRegisters::default();
+        for (idx, register) in registers.into_iter().enumerate() {
+            values.set(register, idx as i16 * 10 - 20);
+        }
+        assert_eq!(
+            values,
+            ValueRegisters {
+                val0: -20,
+                val1: -10,
+                val2: 0,
+                val3: 10,
+                val4: 20,
+                val5: 30,
+            }
+        );
+        for (idx, register) in registers.into_iter().enumerate() {
+            assert_eq!(values.get(register), idx as i16 * 10 - 20);
+        }
+    }
+
+    #[test]
+    fn select_output_source() {
+        use super::select_output_source;
+        let sources = [
+            OutputSource::Generated,
+            OutputSource::Inverted,
+            OutputSource::Software(true),
+            OutputSource::Software(false),
+            OutputSource::External,
+        ];
+        for sm in 0..4 {
+            for channel in [Channel::A, Channel::B] {
+                for source in sources {
+                    for (dtsrcsel, swcout) in [(0, 0), (0xFFFF, 0xFF), (0xAAAA, 0x55)] {
+                        let (dtsrcsel, swcout) =
+                            select_output_source(channel, sm, source, dtsrcsel, swcout);
+                        assert_eq!(output_source(channel, sm, dtsrcsel, swcout), source);
+                    }
+                }
+            }
+        }
+        // Other outputs don't change.
+        assert_eq!(
+            select_output_source(Channel::A, 1, OutputSource::Software(true), 0, 0),
+            (0b10 << 6, 1 << 3)
+        );
+        assert_eq!(
+            select_output_source(Channel::B, 3, OutputSource::Generated, 0xFFFF, 0xFF),
+            (0xCFFF, 0xFF)
+        );
+        assert_eq!(
+            select_output_source(Channel::X, 0, OutputSource::External, 0x1234, 0x56),
+            (0x1234, 0x56)
+        );
+    }
+
+    #[test]
+    fn channel_value_registers() {
+        use super::{turn_off, turn_on};
+        assert_eq!(turn_on(Channel::A), ValueRegister::Val2);
+        assert_eq!(turn_off(Channel::A), ValueRegister::Val3);
+        assert_eq!(turn_on(Channel::B), ValueRegister::Val4);
+        assert_eq!(turn_off(Channel::B), ValueRegister::Val5);
+        assert_eq!(turn_on(Channel::X), HALF_RELOAD_VALUE_REGISTER);
+        assert_eq!(turn_off(Channel::X), FULL_RELOAD_VALUE_REGISTER);
+    }
+
+    #[test]
+    fn count_direction() {
+        // Center aligned, period 100.
+        let (init, half, modulo) = center_aligned_period(100).unwrap();
+        assert_eq!(direction(init, half), Direction::Rising);
+        assert_eq!(direction(-1, half), Direction::Rising);
+        assert_eq!(direction(0, half), Direction::Falling);
+        assert_eq!(direction(modulo, half), Direction::Falling);
+    }
+
+    #[test]
+    fn update_bits() {
+        assert_eq!(super::update_bits(0b0101, 0b0011, true), 0b0111);
+        assert_eq!(super::update_bits(0b0101, 0b0011, false), 0b0100);
+    }
+
+    #[test]
+    fn fault_filter() {
+        assert_eq!(FaultFilter::DISABLED.latency_cycles(), 3);
+        let filter = FaultFilter {
+            period: 10,
+            count: 5,
+        };
+        assert_eq!(filter.count_field(), 2);
+        assert_eq!(filter.latency_cycles(), 63);
+
+        let clamped = FaultFilter {
+            period: 1,
+            count: 0,
+        };
+        assert_eq!(clamped.count_field(), 0);
+        let clamped = FaultFilter {
+            period: 1,
+            count: 42,
+        };
+        assert_eq!(clamped.count_field(), 7);
+    }
+
+    #[test]
+    fn output_sources() {
+        assert_eq!(output_source(Channel::A, 0, 0, 0), OutputSource::Generated);
+        // SM0SEL23 = 1
+        assert_eq!(
+            output_source(Channel::A, 0, 0b0100, 0),
+            OutputSource::Inverted
+        );
+        // SM0SEL45 = 1 doesn't affect channel A.
+        assert_eq!(
+            output_source(Channel::A, 0, 0b0001, 0),
+            OutputSource::Generated
+        );
+        // SM2SEL45 = 2, SM2OUT45 = 1
+        assert_eq!(
+            output_source(Channel::B, 2, 0b10 << 8, 1 << 4),
+            OutputSource::Software(true)
+        );
+        // SM2SEL45 = 2, SM2OUT23 = 1
+        assert_eq!(
+            output_source(Channel::B, 2, 0b10 << 8, 1 << 5),
+            OutputSource::Software(false)
+        );
+        // SM3SEL23 = 3
+        assert_eq!(
+            output_source(Channel::A, 3, 0b11 << 14, 0),
+            OutputSource::External
+        );
+        assert_eq!(
+            output_source(Channel::X, 1, 0xFFFF, 0xFF),
+            OutputSource::Generated
+        );
+    }
+
+    #[test]
+    fn config_validation() {
+        let config = SubmoduleConfig::new();
+        assert_eq!(config.validate(0), Ok(()));
+
+        let config = config.clock_select(ClockSelect::Submodule0);
+        assert_eq!(config.validate(0), Err(ConfigError::Submodule0Clock));
+        assert_eq!(config.validate(1), Ok(()));
+
+        let config = SubmoduleConfig::new().load_mode(LoadMode::ReloadCycle {
+            full: false,
+            half: false,
+        });
+        assert_eq!(config.validate(1), Err(ConfigError::LoadMode));
+
+        let config = SubmoduleConfig::new().load_frequency(17);
+        assert_eq!(config.validate(1), Err(ConfigError::LoadFrequency));
+
+        let config = SubmoduleConfig::new().period(100, 100);
+        assert_eq!(config.validate(1), Err(ConfigError::Period));
+    }
+
+    #[test]
+    fn center_aligned() {
+        assert_eq!(center_aligned_period(0), None);
+        assert_eq!(center_aligned_period(1), None);
+        assert_eq!(center_aligned_period(2), Some((-1, 0, 0)));
+        assert_eq!(center_aligned_period(1000), Some((-500, 0, 499)));
+        assert_eq!(center_aligned_period(1001), Some((-500, 0, 500)));
+        assert_eq!(center_aligned_period(i16::MAX), Some((-16383, 0, 16383)));
+        assert_eq!(center_aligned_period(-2), None);
+    }
+
+    #[test]
+    fn complementary_no_deadtime() {
+        assert_eq!(complementary_edges(0, 99, 0, 0, 50), (25, 75));
+        assert_eq!(complementary_edges(-50, 49, 0, 0, 50), (-25, 25));
+        assert_eq!(complementary_edges(0, 99, 0, 0, 0), (50, 50));
+        assert_eq!(complementary_edges(0, 99, 0, 0, 100), (0, 99));
+    }
+
+    #[test]
+    fn complementary_deadtime() {
+        // A's visible pulse is [on + dt_a, off), centered at 50.
+        let (on, off) = complementary_edges(0, 99, 4, 4, 50);
+        assert_eq!((on, off), (21, 75));
+        assert_eq!(on + 4 + off, 100);
+    }
+
+    #[test]
+    fn complementary_clamp() {
+        // Duty is limited so that B still has a non-negative on time.
+        assert_eq!(complementary_edges(0, 99, 4, 4, 1000), (0, 96));
+        assert_eq!(complementary_edges(0, 99, 4, 4, -10), (46, 50));
+        assert_eq!(
+            complementary_edges(i16::MIN, i16::MAX, 0, 0, i16::MAX),
+            (-16383, 16384)
+        );
+    }
+
+    #[test]
+    fn phase_shift_wraps() {
+        assert_eq!(shift_compare(0, 99, 25, 10), 35);
+        assert_eq!(shift_compare(0, 99, 95, 10), 5);
+        assert_eq!(shift_compare(0, 99, 5, -10), 95);
+        assert_eq!(shift_compare(0, 99, 5, 1000), 5);
+        // Values outside of the period don't move.
+        assert_eq!(shift_compare(0, 99, 100, 10), 100);
+        assert_eq!(shift_compare(i16::MIN, i16::MAX, i16::MAX, 1), i16::MIN);
+        assert_eq!(shift_compare(i16::MIN, i16::MAX, i16::MIN, -1), i16::MAX);
+        assert_eq!(
+            shift_compare(i16::MIN, i16::MAX, i16::MAX, i32::from(i16::MAX)),
+            -2
+        );
+    }
+
+    #[test]
+    fn submodule_phases() {
+        assert_eq!(submodule_phase(0, -1, 0x4000), None);
+        assert_eq!(submodule_phase(0, 99, 0), Some((0, 0)));
+        assert_eq!(submodule_phase(0, 99, 0x4000), Some((-25, 0)));
+        assert_eq!(submodule_phase(0, 99, 0xC000), Some((-75, 0)));
+        // Moving down leaves the i16 range, so move up.
+        assert_eq!(
+            submodule_phase(i16::MIN, 0, 0x4000),
+            Some((32769 - 8192, 0))
+        );
+        // The period spans all i16 values.
+        assert_eq!(
+            submodule_phase(i16::MIN, i16::MAX, 0x8000),
+            Some((0, 32768))
+        );
+    }
+
+    #[test]
+    fn offset_compares() {
+        // Period moves down by 25; edges keep their positions.
+        assert_eq!(offset_compare(0, 99, 10, -25, 0), 10);
+        assert_eq!(offset_compare(0, 99, 80, -25, 0), -20);
+        assert_eq!(offset_compare(0, 99, 74, -25, 0), 74);
+        // Period moves up.
+        assert_eq!(offset_compare(i16::MIN, 0, i16::MIN, 24577, 0), 1);
+        assert_eq!(offset_compare(i16::MIN, 0, 0, 24577, 0), 0);
+        // Period stays; edges shift.
+        assert_eq!(offset_compare(i16::MIN, i16::MAX, 0, 0, 32768), i16::MIN);
+        assert_eq!(offset_compare(0, 99, 100, -25, 0), 100);
+    }
 }
@@ -191,5 +191,47 @@ pub fn submodules<const N: u8>(pwm: &crate::ral::pwm::Instance<N>) -> Submodules
     )
 }
 
+/// The low byte of the master control register, `MCTRL`.
+///
+/// Unlike the other PWM registers, `MCTRL` is byte accessible. Its low byte
+/// holds `LDOK` and `CLDOK`, which are write one to set / clear; writing zero
+/// has no effect. A byte store changes only the submodules in its mask, and it
+/// doesn't touch `RUN` or `IPOL` in the high byte.
+#[repr(C)]
+pub struct MasterControlLow {
+    /// Bits 7 through 0 of `MCTRL`.
+    pub MCTRL_LOW: RWRegister<u8>,
+}
+
+/// Fields of the low byte of `MCTRL`.
+pub mod MCTRL_LOW {
+    /// Load Okay.
+    pub mod LDOK {
+        pub const offset: u8 = 0;
+        pub const mask: u8 = 0x0f << offset;
+        pub mod R {}
+        pub mod W {}
+        pub mod RW {}
+    }
+    /// Clear Load Okay.
+    pub mod CLDOK {
+        pub const offset: u8 = 4;
+        pub const mask: u8 = 0x0f << offset;
+        pub mod R {}
+        pub mod W {}
+        pub mod RW {}
+    }
+}
+
+/// Returns the low byte of `MCTRL` for PWM `N`.
+///
+/// The offset of `MCTRL` differs between the 1010 and the other chips, so
+/// this takes the register's address from the chip's RAL register block.
+pub fn master_control_low<const N: u8>(pwm: &crate::ral::pwm::Instance<N>) -> &MasterControlLow {
+    // Safety: MCTRL is byte accessible. The core is little endian,
+    // so the low byte is at the register's address.
+    unsafe { &*(core::ptr::addr_of!(pwm.MCTRL) as *const MasterControlLow) }
+}
+
 // Export all submodule fields.
 pub use crate::ral::pwm::sm::*;
@@ -0,0 +1,443 @@
+//! Flexible serial peripheral interface for NOR flash.
+//!
+//! The `FlexSpi` driver reads, erases, and programs a serial NOR flash
+//! that's connected to flash port A1. Typically, this is the flash that
+//! stores your firmware. Use this driver to store persistent data, like
+//! calibration values or settings, in an unused flash sector.
+//!
+//! The driver issues commands through the FlexSPI IP command interface.
+//! It uses a common, single-pad command set that most QSPI NOR flashes
+//! support:
+//!
+//! | Operation      | Command | Address |
+//! | -------------- | ------- | ------- |
+//! | Fast read      | `0x0B`  | 24 bit  |
+//! | Read status    | `0x05`  | -       |
+//! | Write enable   | `0x06`  | -       |
+//! | Sector erase   | `0x20`  | 24 bit  |
+//! | Page program   | `0x02`  | 24 bit  |
+//!
+//! The driver programs these commands into the last five sequences of
+//! the FlexSPI lookup table (LUT), sequences 11 through 15. It overwrites
+//! whatever those sequences held before. It doesn't touch the other
+//! sequences, so the boot ROM's read sequence, used for executing in place,
+//! is preserved. All addresses are offsets
+//! from the start of the flash, not memory-mapped addresses.
+//!
+//! # Executing from RAM
+//!
+//! The processor cannot fetch instructions or data from the flash while
+//! the flash is erasing or programming. If it tries, it will read garbage
+//! or fault. So the driver places the code that erases and programs the
+//! flash in the `.data` section, which your runtime copies to RAM. Make sure
+//! that your runtime places `.data` in memory that can execute instructions,
+//! like ITCM or OCRAM, not DTCM. Consult your runtime's documentation to learn
+//! where it places `.data`.
+//!
+//! The code that calls [`erase_sector`](FlexSpi::erase_sector) or
+//! [`program_page`](FlexSpi::program_page) may execute from flash, since
+//! it waits for the flash to finish. But interrupts must not execute from
+//! flash while the driver erases or programs. Before you call these
+//! functions,
+//!
+//! - disable interrupts, or make sure that all interrupt handlers and the
+//!   vector table are in RAM.
+//! - make sure that any data you're programming is in RAM, not in flash.
+//!
+//! After erasing or programming, the driver invalidates the FlexSPI AHB
+//! read buffers. If the flash region is cacheable, you're responsible
+//! for invalidating the data cache before you read the new contents
+//! through the memory map.
+//!
+//! # Example
+//!
+//! Store a calibration value in the last sector of a 2MiB flash.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//! use hal::flexspi::{FlexSpi, SECTOR_SIZE};
+//!
+//! # || -> Result<(), hal::flexspi::FlexSpiError> {
+//! const CALIBRATION: u32 = 2 * 1024 * 1024 - SECTOR_SIZE as u32;
+//!
+//! let mut flexspi = FlexSpi::new(unsafe { ral::flexspi::FLEXSPI1::instance() });
+//!
+//! // On the stack, not in flash.
+//! let value = [0xDE, 0xAD, 0xBE, 0xEF];
+//! cortex_m::interrupt::free(|_| {
+//!     flexspi.erase_sector(CALIBRATION)?;
+//!     flexspi.program_page(CALIBRATION, &value)
+//! })?;
+//!
+//! let mut calibration = [0; 4];
+//! flexspi.read(CALIBRATION, &mut calibration)?;
+//! # Ok(()) }();
+//! ```
+
+use crate::ral;
+
+/// The size of a flash sector, in bytes.
+///
+/// This is the smallest region that [`erase_sector`](FlexSpi::erase_sector)
+/// erases.
+pub const SECTOR_SIZE: usize = 4096;
+
+/// The size of a flash page, in bytes.
+///
+/// This is the largest region that [`program_page`](FlexSpi::program_page)
+/// programs.
+pub const PAGE_SIZE: usize = 256;
+
+/// The first LUT sequence used by the driver.
+///
+/// The driver overwrites this sequence, and the four sequences that follow,
+/// sequences 11 through 15. Don't use these sequences for anything else.
+pub const LUT_SEQUENCE_START: u32 = 11;
+
+const SEQ_READ: u32 = LUT_SEQUENCE_START;
+const SEQ_READ_STATUS: u32 = LUT_SEQUENCE_START + 1;
+const SEQ_WRITE_ENABLE: u32 = LUT_SEQUENCE_START + 2;
+const SEQ_ERASE_SECTOR: u32 = LUT_SEQUENCE_START + 3;
+const SEQ_PAGE_PROGRAM: u32 = LUT_SEQUENCE_START + 4;
+
+/// LUT instruction opcodes.
+mod opcode {
+    pub const STOP: u16 = 0x00;
+    pub const CMD_SDR: u16 = 0x01;
+    pub const RADDR_SDR: u16 = 0x02;
+    pub const WRITE_SDR: u16 = 0x08;
+    pub const READ_SDR: u16 = 0x09;
+    pub const DUMMY_SDR: u16 = 0x0C;
+}
+
+/// Encode a single-pad LUT instruction.
+const fn instr(opcode: u16, operand: u8) -> u16 {
+    (opcode << 10) | operand as u16
+}
+
+/// Encode two LUT instructions into one LUT word.
+const fn word(first: u16, second: u16) -> u32 {
+    (first as u32) | ((second as u32) << 16)
+}
+
+const STOP: u16 = instr(opcode::STOP, 0);
+
+/// The LUT sequences, in the same order as the sequence numbers.
+const SEQUENCES: [[u32; 4]; 5] = [
+    // Fast read, with 8 dummy cycles.
+    [
+        word(instr(opcode::CMD_SDR, 0x0B), instr(opcode::RADDR_SDR, 24)),
+        word(instr(opcode::DUMMY_SDR, 8), instr(opcode::READ_SDR, 4)),
+        word(STOP, STOP),
+        0,
+    ],
+    // Read status.
+    [
+        word(instr(opcode::CMD_SDR, 0x05), instr(opcode::READ_SDR, 1)),
+        word(STOP, STOP),
+        0,
+        0,
+    ],
+    // Write enable.
+    [word(instr(opcode::CMD_SDR, 0x06), STOP), 0, 0, 0],
+    // Sector erase.
+    [
+        word(instr(opcode::CMD_SDR, 0x20), instr(opcode::RADDR_SDR, 24)),
+        word(STOP, STOP),
+        0,
+        0,
+    ],
+    // Page program.
+    [
+        word(instr(opcode::CMD_SDR, 0x02), instr(opcode::RADDR_SDR, 24)),
+        word(instr(opcode::WRITE_SDR, 4), STOP),
+        0,
+        0,
+    ],
+];
+
+/// The key that unlocks and locks the LUT.
+const LUT_KEY: u32 = 0x5AF0_5AF0;
+
+/// The number of bytes moved in one IP command.
+///
+/// This is the size of the IP RX and TX FIFO data windows.
+const FIFO_BYTES: usize = 128;
+
+/// The write-in-progress bit in the flash status register.
+const STATUS_WIP: u32 = 1 << 0;
+
+/// Errors from the FlexSPI driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlexSpiError {
+    /// The FlexSPI controller reported an error when it
+    /// executed the command.
+    Command,
+    /// The program data would cross a page boundary.
+    PageBoundary,
+    /// The address is beyond the 32-bit address space.
+    Address,
+}
+
+/// A FlexSPI NOR flash driver.
+///
+/// See the [module-level documentation](crate::flexspi) for more information.
+pub struct FlexSpi<const N: u8> {
+    flexspi: ral::flexspi::Instance<N>,
+}
+
+impl<const N: u8> FlexSpi<N> {
+    /// The peripheral instance.
+    pub const N: u8 = N;
+
+    /// Create a FlexSPI NOR flash driver.
+    ///
+    /// This assumes that the boot ROM, or some other software, already
+    /// configured the FlexSPI controller and its clocks for the flash.
+    /// `new` only installs the driver's LUT sequences, overwriting LUT
+    /// sequences 11 through 15.
+    pub fn new(flexspi: ral::flexspi::Instance<N>) -> Self {
+        ral::write_reg!(ral::flexspi, flexspi, LUTKEY, LUT_KEY);
+        ral::write_reg!(ral::flexspi, flexspi, LUTCR, UNLOCK: 1);
+        for (seq, instrs) in (LUT_SEQUENCE_START as usize..).zip(SEQUENCES.iter()) {
+            for (idx, instr) in instrs.iter().enumerate() {
+                ral::write_reg!(ral::flexspi, flexspi, LUT[seq * 4 + idx], *instr);
+            }
+        }
+        ral::write_reg!(ral::flexspi, flexspi, LUTKEY, LUT_KEY);
+        ral::write_reg!(ral::flexspi, flexspi, LUTCR, LOCK: 1);
+
+        // Largest watermarks, so that a whole data window is
+        // available before we read or write it.
+        let watermark = (FIFO_BYTES / 8 - 1) as u32;
+        ral::write_reg!(ral::flexspi, flexspi, IPRXFCR, RXWMRK: watermark);
+        ral::write_reg!(ral::flexspi, flexspi, IPTXFCR, TXWMRK: watermark);
+
+        Self { flexspi }
+    }
+
+    /// Release the FlexSPI peripheral.
+    ///
+    /// This doesn't remove the driver's LUT sequences.
+    pub fn release(self) -> ral::flexspi::Instance<N> {
+        self.flexspi
+    }
+
+    /// Read flash contents, starting at `addr`, into `buffer`.
+    ///
+    /// Unlike erasing or programming, you may read while executing
+    /// from the flash.
+    pub fn read(&mut self, addr: u32, buffer: &mut [u8]) -> Result<(), FlexSpiError> {
+        for (offset, chunk) in (0..).step_by(FIFO_BYTES).zip(buffer.chunks_mut(FIFO_BYTES)) {
+            let addr = addr.checked_add(offset).ok_or(FlexSpiError::Address)?;
+            self.start(SEQ_READ, addr, chunk.len());
+            self.wait_done()?;
+            // Safety: a chunk is no larger than the FIFO.
+            unsafe { self.read_fifo(chunk.as_mut_ptr(), chunk.len()) };
+        }
+        Ok(())
+    }
+
+    // Everything below executes from RAM. These functions, and every function
+    // that they call, are in the same section. They don't use iterators, slice
+    // indexing, or other operations that could call into, or panic from, the
+    // flash. Arithmetic wraps, since a profile may check for overflow. (With
+    // debug assertions, core checks the alignment of each volatile register
+    // access. Those checks can only fail, and call into the flash, for a
+    // misaligned register.)
+
+    /// Erase the sector that contains `addr`.
+    ///
+    /// After erasing, all bytes in the sector read as `0xFF`. This
+    /// blocks until the flash finishes the erase.
+    ///
+    /// This executes from RAM. See the [module-level
+    /// documentation](crate::flexspi) for more information.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    pub fn erase_sector(&mut self, addr: u32) -> Result<(), FlexSpiError> {
+        let addr = addr & !(SECTOR_SIZE as u32 - 1);
+        self.write_enable()?;
+        self.start(SEQ_ERASE_SECTOR, addr, 0);
+        self.wait_done()?;
+        self.wait_ready()?;
+        self.invalidate_ahb_buffers();
+        Ok(())
+    }
+
+    /// Program `data` into flash, starting at `addr`.
+    ///
+    /// Programming can only clear bits; erase the sector before you
+    /// program it. `data` may be smaller than a page, but it may not
+    /// cross a page boundary. If it would, this returns an error
+    /// without programming any data. This blocks until the flash
+    /// finishes programming.
+    ///
+    /// This executes from RAM. See the [module-level
+    /// documentation](crate::flexspi) for more information.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    pub fn program_page(&mut self, addr: u32, data: &[u8]) -> Result<(), FlexSpiError> {
+        let page_offset = addr as usize & (PAGE_SIZE - 1);
+        if data.len() > PAGE_SIZE.wrapping_sub(page_offset) {
+            return Err(FlexSpiError::PageBoundary);
+        }
+        // The data doesn't cross the end of its page, so it doesn't cross
+        // the end of the address space.
+        let mut offset = 0;
+        while offset < data.len() {
+            let remaining = data.len().wrapping_sub(offset);
+            let len = if remaining < FIFO_BYTES {
+                remaining
+            } else {
+                FIFO_BYTES
+            };
+            self.write_enable()?;
+            self.start(SEQ_PAGE_PROGRAM, addr.wrapping_add(offset as u32), len);
+            // Safety: offset + len is within data, and len is no larger
+            // than the FIFO.
+            unsafe { self.write_fifo(data.as_ptr().wrapping_add(offset), len) };
+            self.wait_done()?;
+            self.wait_ready()?;
+            offset = offset.wrapping_add(len);
+        }
+        self.invalidate_ahb_buffers();
+        Ok(())
+    }
+
+    /// Invalidate the AHB read buffers.
+    ///
+    /// The driver calls this after it erases or programs the flash, so
+    /// that memory-mapped reads observe the new contents. This resets the
+    /// FlexSPI state machines, so it executes from RAM.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    pub fn invalidate_ahb_buffers(&mut self) {
+        ral::modify_reg!(ral::flexspi, self.flexspi, MCR0, SWRESET: 1);
+        while ral::read_reg!(ral::flexspi, self.flexspi, MCR0, SWRESET == 1) {}
+    }
+
+    /// Set the write enable latch in the flash.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    fn write_enable(&mut self) -> Result<(), FlexSpiError> {
+        self.start(SEQ_WRITE_ENABLE, 0, 0);
+        self.wait_done()
+    }
+
+    /// Wait for the flash to finish an erase or program operation.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    fn wait_ready(&mut self) -> Result<(), FlexSpiError> {
+        loop {
+            let mut status: u8 = 0;
+            self.start(SEQ_READ_STATUS, 0, 1);
+            self.wait_done()?;
+            // Safety: one byte is no larger than the FIFO.
+            unsafe { self.read_fifo(&mut status, 1) };
+            if status as u32 & STATUS_WIP == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Start an IP command that executes `seq`, moving `len` bytes.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    fn start(&mut self, seq: u32, addr: u32, len: usize) {
+        loop {
+            let (arbiter, sequencer) =
+                ral::read_reg!(ral::flexspi, self.flexspi, STS0, ARBIDLE, SEQIDLE);
+            if arbiter != 0 && sequencer != 0 {
+                break;
+            }
+        }
+
+        ral::write_reg!(ral::flexspi, self.flexspi, INTR, IPCMDDONE: 1, IPCMDERR: 1, IPCMDGE: 1, IPRXWA: 1, IPTXWE: 1);
+        ral::modify_reg!(ral::flexspi, self.flexspi, IPRXFCR, CLRIPRXF: 1);
+        ral::modify_reg!(ral::flexspi, self.flexspi, IPTXFCR, CLRIPTXF: 1);
+
+        ral::write_reg!(ral::flexspi, self.flexspi, IPCR0, addr);
+        ral::write_reg!(ral::flexspi, self.flexspi, IPCR1, ISEQID: seq, ISEQNUM: 0, IDATSZ: len as u32);
+        ral::write_reg!(ral::flexspi, self.flexspi, IPCMD, TRG: 1);
+    }
+
+    /// Wait for the IP command to finish.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    fn wait_done(&mut self) -> Result<(), FlexSpiError> {
+        loop {
+            let (done, error, grant_error) = ral::read_reg!(
+                ral::flexspi,
+                self.flexspi,
+                INTR,
+                IPCMDDONE,
+                IPCMDERR,
+                IPCMDGE
+            );
+            if error != 0 || grant_error != 0 {
+                ral::write_reg!(ral::flexspi, self.flexspi, INTR, IPCMDERR: 1, IPCMDGE: 1);
+                return Err(FlexSpiError::Command);
+            }
+            if done != 0 {
+                ral::write_reg!(ral::flexspi, self.flexspi, INTR, IPCMDDONE: 1);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Copy `len` bytes of received data out of the RX FIFO into `buffer`.
+    ///
+    /// # Safety
+    ///
+    /// The command must be done. `buffer` must be valid for `len` bytes of
+    /// writes, and `len` cannot be larger than the FIFO.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    unsafe fn read_fifo(&mut self, buffer: *mut u8, len: usize) {
+        let rfdr = self.flexspi.RFDR.as_ptr();
+        let mut word = 0;
+        let mut idx = 0;
+        while idx < len {
+            if idx & 3 == 0 {
+                // Safety: the FIFO has a register for every four bytes.
+                word = unsafe { (*rfdr.wrapping_add(idx >> 2)).read() };
+            }
+            // Safety: idx is less than len.
+            unsafe { *buffer.wrapping_add(idx) = word.wrapping_shr((idx as u32 & 3) << 3) as u8 };
+            idx = idx.wrapping_add(1);
+        }
+        ral::write_reg!(ral::flexspi, self.flexspi, INTR, IPRXWA: 1);
+    }
+
+    /// Copy `len` bytes from `data` into the TX FIFO.
+    ///
+    /// # Safety
+    ///
+    /// The command must be started. `data` must be valid for `len` bytes of
+    /// reads, and `len` cannot be larger than the FIFO.
+    #[inline(never)]
+    #[link_section = ".data.imxrt_hal.flexspi"]
+    unsafe fn write_fifo(&mut self, data: *const u8, len: usize) {
+        while ral::read_reg!(ral::flexspi, self.flexspi, INTR, IPTXWE == 0) {}
+        let tfdr = self.flexspi.TFDR.as_ptr();
+        let mut word = u32::MAX;
+        let mut idx = 0;
+        while idx < len {
+            let shift = (idx as u32 & 3) << 3;
+            // Safety: idx is less than len.
+            let byte = unsafe { *data.wrapping_add(idx) };
+            word = word & !0xFFu32.wrapping_shl(shift) | (byte as u32).wrapping_shl(shift);
+            let next = idx.wrapping_add(1);
+            if next & 3 == 0 || next == len {
+                // Safety: the FIFO has a register for every four bytes.
+                unsafe { (*tfdr.wrapping_add(idx >> 2)).write(word) };
+                word = u32::MAX;
+            }
+            idx = next;
+        }
+        ral::write_reg!(ral::flexspi, self.flexspi, INTR, IPTXWE: 1);
+    }
+}
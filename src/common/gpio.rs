@@ -77,6 +77,87 @@ impl<const N: u8> Port<N> {
         Input::new(pin, self.register_block(), P::OFFSET)
     }
 
+    /// Set and clear multiple GPIO outputs.
+    ///
+    /// Each bit in `set` and `clear` corresponds to a GPIO in this port.
+    /// Outputs in `clear` go low, then outputs in `set` go high. If a bit is
+    /// in both masks, the output goes high.
+    ///
+    /// This uses the hardware's set and clear registers, so it doesn't race with
+    /// other [`Output`]s that share the port. There is a short delay between
+    /// clearing and setting outputs. Bits that don't correspond to outputs
+    /// have no effect on pins.
+    pub fn write_mask(&mut self, set: u32, clear: u32) {
+        ral::write_reg!(ral::gpio, self.gpio, DR_CLEAR, clear);
+        ral::write_reg!(ral::gpio, self.gpio, DR_SET, set);
+    }
+
+    /// Toggle multiple GPIO outputs.
+    ///
+    /// Each bit in `toggle` corresponds to a GPIO in this port. All outputs
+    /// in the mask change at the same time.
+    pub fn toggle_mask(&mut self, toggle: u32) {
+        ral::write_reg!(ral::gpio, self.gpio, DR_TOGGLE, toggle);
+    }
+
+    /// Read the pad state of every GPIO in this port.
+    ///
+    /// Bit `n` is set if the pad for the GPIO at offset `n` is high.
+    /// Use this to sample a parallel input in one read.
+    pub fn read_port(&self) -> u32 {
+        ral::read_reg!(ral::gpio, self.gpio, PSR)
+    }
+
+    /// Returns the GPIOs with pending interrupts.
+    ///
+    /// Bit `n` is set if the GPIO at offset `n` triggered, and its interrupt
+    /// is enabled. GPIOs that triggered without an enabled interrupt aren't
+    /// included.
+    pub fn pending_interrupts(&self) -> u32 {
+        ral::read_reg!(ral::gpio, self.gpio, ISR) & ral::read_reg!(ral::gpio, self.gpio, IMR)
+    }
+
+    /// Returns an iterator over the offsets of GPIOs with pending interrupts.
+    ///
+    /// The iterator captures the pending interrupts when you call this method.
+    /// It doesn't clear the interrupts. Compare each offset with the
+    /// [`offset`](Input::offset) of your inputs.
+    ///
+    /// ```no_run
+    /// use imxrt_hal::gpio::Port;
+    /// use imxrt_ral::gpio::GPIO2;
+    ///
+    /// # let button_a = unsafe { imxrt_iomuxc::imxrt1060::gpio_b0::GPIO_B0_04::new() };
+    /// # let button_b = unsafe { imxrt_iomuxc::imxrt1060::gpio_b0::GPIO_B0_05::new() };
+    /// let mut gpio2 = Port::new(unsafe { GPIO2::instance() });
+    /// let button_a = gpio2.input(button_a);
+    /// let button_b = gpio2.input(button_b);
+    /// // Enable interrupts...
+    ///
+    /// // In the shared GPIO2 interrupt handler...
+    /// let pending = gpio2.pending();
+    /// gpio2.clear_interrupt(pending.mask());
+    /// for offset in pending {
+    ///     if offset == button_a.offset() {
+    ///         // Handle button A...
+    ///     } else if offset == button_b.offset() {
+    ///         // Handle button B...
+    ///     }
+    /// }
+    /// ```
+    pub fn pending(&self) -> PendingInterrupts {
+        PendingInterrupts(self.pending_interrupts())
+    }
+
+    /// Clear the interrupt flags of multiple GPIOs.
+    ///
+    /// Each bit in `mask` corresponds to a GPIO in this port. Clearing a flag
+    /// doesn't disable the interrupt. This doesn't race with [`Input`]s that
+    /// share the port.
+    pub fn clear_interrupt(&self, mask: u32) {
+        ral::write_reg!(ral::gpio, self.gpio, ISR, mask);
+    }
+
     /// Enable or disable GPIO input interrupts.
     ///
     /// Specify `None` to disable interrupts. Or, provide a trigger
@@ -119,6 +200,40 @@ impl<const N: u8> Port<N> {
     }
 }
 
+/// An iterator over the offsets of GPIOs with pending interrupts.
+///
+/// Use [`Port::pending`] to create this iterator. It produces offsets from
+/// lowest to highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingInterrupts(u32);
+
+impl PendingInterrupts {
+    /// Returns the remaining offsets as a mask.
+    ///
+    /// Bit `n` is set if the iterator has yet to produce offset `n`.
+    pub const fn mask(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Iterator for PendingInterrupts {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let offset = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(offset)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PendingInterrupts {}
+
 /// An output GPIO.
 pub struct Output<P> {
     pin: P,
@@ -256,6 +371,14 @@ impl<P> Input<P> {
         (self.offset % 16) * 2
     }
 
+    /// Returns the GPIO's offset within its port.
+    ///
+    /// Bit `offset` represents this GPIO in port-wide masks, like
+    /// [`Port::pending_interrupts`].
+    pub const fn offset(&self) -> u32 {
+        self.offset
+    }
+
     /// Returns `true` if the GPIO is set high.
     pub fn is_set(&self) -> bool {
         ral::read_reg!(ral::gpio, self.gpio, PSR) & self.mask() != 0
@@ -412,3 +535,22 @@ impl<P> eh1::digital::InputPin for Input<P> {
         Ok(!Input::is_set(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PendingInterrupts;
+
+    #[test]
+    fn pending_interrupts() {
+        let pending = PendingInterrupts(1 << 31 | 1 << 4 | 1);
+        assert_eq!(pending.len(), 3);
+        let mut offsets = pending;
+        assert_eq!(offsets.mask(), 1 << 31 | 1 << 4 | 1);
+        assert_eq!(offsets.next(), Some(0));
+        assert_eq!(offsets.next(), Some(4));
+        assert_eq!(offsets.mask(), 1 << 31);
+        assert_eq!(offsets.next(), Some(31));
+        assert_eq!(offsets.next(), None);
+        assert_eq!(PendingInterrupts(0).next(), None);
+    }
+}
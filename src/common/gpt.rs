@@ -94,11 +94,19 @@
 //! gpt.clear_elapsed(OCR);
 //! ```
 //!
+//! # Input capture
+//!
+//! Each GPT has two **input capture registers (ICR)**. When the GPT sees the
+//! selected edge on a capture pin, it copies the counter into the ICR, and
+//! signals the capture through a status flag. Select the edges with
+//! [`set_input_capture()`](Gpt::set_input_capture). You're responsible for
+//! configuring the capture pin.
+//!
+//! Use [`measure_frequency()`](Gpt::measure_frequency) to measure the frequency
+//! of a pulse train, like the output of a flow meter.
+//!
 //! # TODO
 //!
-//! - Input capture. Each GPT can capture the value of the counter
-//!   when a pin state changes. When the pin state changes, the
-//!   GPT can generate an interrupt.
 //! - Output generation. When one of the three comparison registers
 //!   match the counter, the GPT can generate a signal on an output
 //!   pin.
@@ -151,6 +159,30 @@ pub enum OutputCompareRegister {
     OCR3 = 2,
 }
 
+/// An input capture register (ICR).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(usize)]
+pub enum InputCaptureRegister {
+    /// The first input capture register.
+    ICR1 = 0,
+    /// The second input capture register.
+    ICR2 = 1,
+}
+
+/// The input edges that trigger a capture.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum InputCapture {
+    /// Capture is disabled.
+    Disabled = 0,
+    /// Capture on rising edges.
+    RisingEdge = 1,
+    /// Capture on falling edges.
+    FallingEdge = 2,
+    /// Capture on both edges.
+    BothEdges = 3,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Possible modes of the GPT.
 pub enum Mode {
@@ -354,6 +386,113 @@ impl<const N: u8> Gpt<N> {
         }
     }
 
+    /// Select the edges that trigger a capture into `icr`.
+    pub fn set_input_capture(&mut self, icr: InputCaptureRegister, capture: InputCapture) {
+        match icr {
+            InputCaptureRegister::ICR1 => {
+                ral::modify_reg!(ral::gpt, self.gpt, CR, IM1: capture as u32)
+            }
+            InputCaptureRegister::ICR2 => {
+                ral::modify_reg!(ral::gpt, self.gpt, CR, IM2: capture as u32)
+            }
+        }
+    }
+
+    /// Returns the edges that trigger a capture into `icr`.
+    pub fn input_capture(&self, icr: InputCaptureRegister) -> InputCapture {
+        let im = match icr {
+            InputCaptureRegister::ICR1 => ral::read_reg!(ral::gpt, self.gpt, CR, IM1),
+            InputCaptureRegister::ICR2 => ral::read_reg!(ral::gpt, self.gpt, CR, IM2),
+        };
+        match im {
+            0 => InputCapture::Disabled,
+            1 => InputCapture::RisingEdge,
+            2 => InputCapture::FallingEdge,
+            3 => InputCapture::BothEdges,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Enable / disable an interrupt when `icr` captures the counter.
+    pub fn set_input_interrupt_on_capture(&mut self, icr: InputCaptureRegister, intr: bool) {
+        match icr {
+            InputCaptureRegister::ICR1 => {
+                ral::modify_reg!(ral::gpt, self.gpt, IR, IF1IE: intr as u32)
+            }
+            InputCaptureRegister::ICR2 => {
+                ral::modify_reg!(ral::gpt, self.gpt, IR, IF2IE: intr as u32)
+            }
+        }
+    }
+
+    /// Returns `true` if a capture into `icr` generates an interrupt.
+    pub fn is_input_interrupt_on_capture(&self, icr: InputCaptureRegister) -> bool {
+        match icr {
+            InputCaptureRegister::ICR1 => ral::read_reg!(ral::gpt, self.gpt, IR, IF1IE == 1),
+            InputCaptureRegister::ICR2 => ral::read_reg!(ral::gpt, self.gpt, IR, IF2IE == 1),
+        }
+    }
+
+    /// Returns the counter value captured by `icr`.
+    pub fn input_capture_count(&self, icr: InputCaptureRegister) -> u32 {
+        ral::read_reg!(ral::gpt, self.gpt, ICR[icr as usize])
+    }
+
+    /// Returns `true` if `icr` captured the counter.
+    pub fn is_captured(&self, icr: InputCaptureRegister) -> bool {
+        match icr {
+            InputCaptureRegister::ICR1 => ral::read_reg!(ral::gpt, self.gpt, SR, IF1 == 1),
+            InputCaptureRegister::ICR2 => ral::read_reg!(ral::gpt, self.gpt, SR, IF2 == 1),
+        }
+    }
+
+    /// Clear the captured flag.
+    pub fn clear_captured(&self, icr: InputCaptureRegister) {
+        match icr {
+            InputCaptureRegister::ICR1 => ral::write_reg!(ral::gpt, self.gpt, SR, IF1: 1),
+            InputCaptureRegister::ICR2 => ral::write_reg!(ral::gpt, self.gpt, SR, IF2: 1),
+        }
+    }
+
+    /// Measure the frequency (Hz) of the edges captured by `icr`.
+    ///
+    /// This blocks for a gate period of `gate_ms` milliseconds. During the gate,
+    /// it records every capture. The frequency is the number of captured edges,
+    /// less one, divided by the time between the first and last capture. `clock_hz`
+    /// is the GPT counter frequency, after the divider.
+    ///
+    /// Returns zero if there are fewer than two captures in the gate period, like
+    /// when there are no pulses. To measure slow signals, use a gate period that's
+    /// longer than two signal periods.
+    ///
+    /// Before you call this, select the capture edges with
+    /// [`set_input_capture()`](Self::set_input_capture), use the
+    /// [free-running mode](Mode::FreeRunning), and enable the GPT. The gate period
+    /// must be shorter than the time it takes the counter to wrap. This polls the
+    /// capture flag, so it can't count edges that are faster than the polling
+    /// loop. If the capture generates an interrupt, your interrupt handler may
+    /// take captures from this call.
+    pub fn measure_frequency(
+        &mut self,
+        icr: InputCaptureRegister,
+        clock_hz: u32,
+        gate_ms: u32,
+    ) -> u32 {
+        let gate_ticks = gate_ticks(clock_hz, gate_ms);
+        let mut captures = Captures::default();
+
+        self.clear_captured(icr);
+        let start = self.count();
+        while self.count().wrapping_sub(start) < gate_ticks {
+            if self.is_captured(icr) {
+                let count = self.input_capture_count(icr);
+                self.clear_captured(icr);
+                captures.record(count);
+            }
+        }
+        captures.frequency(clock_hz)
+    }
+
     /// Enable / disable an interrupt when the GPT counter rolls over from `u32::max_value()` to
     /// `0`.
     ///
@@ -404,3 +543,71 @@ impl<const N: u8> Gpt<N> {
         self.gpt
     }
 }
+
+/// Returns the number of counter ticks in a gate of `gate_ms` milliseconds.
+///
+/// Saturates at `u32::MAX`.
+fn gate_ticks(clock_hz: u32, gate_ms: u32) -> u32 {
+    let ticks = clock_hz as u64 * gate_ms as u64 / 1000;
+    ticks.min(u32::MAX as u64) as u32
+}
+
+/// Captured edges during a frequency measurement.
+#[derive(Debug, Default)]
+struct Captures {
+    edges: u32,
+    first: u32,
+    last: u32,
+}
+
+impl Captures {
+    fn record(&mut self, count: u32) {
+        if self.edges == 0 {
+            self.first = count;
+        }
+        self.last = count;
+        self.edges += 1;
+    }
+
+    /// Returns the frequency of the captured edges, rounded to the nearest Hz.
+    fn frequency(&self, clock_hz: u32) -> u32 {
+        let elapsed = self.last.wrapping_sub(self.first) as u64;
+        if self.edges < 2 || elapsed == 0 {
+            return 0;
+        }
+        let periods = (self.edges - 1) as u64;
+        ((periods * clock_hz as u64 + elapsed / 2) / elapsed) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gate_ticks, Captures};
+
+    #[test]
+    fn gate() {
+        assert_eq!(gate_ticks(1_000_000, 100), 100_000);
+        assert_eq!(gate_ticks(24_000_000, 1_000_000), u32::MAX);
+    }
+
+    #[test]
+    fn frequency() {
+        let mut captures = Captures::default();
+        assert_eq!(captures.frequency(1_000_000), 0);
+
+        captures.record(500);
+        assert_eq!(captures.frequency(1_000_000), 0);
+
+        // 100Hz, with a 1MHz counter.
+        for count in 1..=10 {
+            captures.record(500 + count * 10_000);
+        }
+        assert_eq!(captures.frequency(1_000_000), 100);
+
+        // Counter wraps between captures.
+        let mut captures = Captures::default();
+        captures.record(u32::MAX - 99);
+        captures.record(0);
+        assert_eq!(captures.frequency(1_000), 10);
+    }
+}
@@ -0,0 +1,303 @@
+//! Peripheral interrupts.
+//!
+//! The functions in this module return the [`Interrupt`] for a peripheral
+//! instance on the selected chip. Use them to unmask interrupts in the NVIC,
+//! or to bind RTIC tasks, without looking up interrupt names in the RAL.
+//!
+//! `instance` is the peripheral instance, like the '2' in LPUART2. If a chip
+//! has a single instance of a peripheral, use
+//! [`SOLE_INSTANCE`](crate::ral::SOLE_INSTANCE).
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral::Interrupt;
+//!
+//! const CONSOLE: Interrupt = hal::interrupt::lpuart(2);
+//! assert_eq!(CONSOLE, Interrupt::LPUART2);
+//!
+//! unsafe { cortex_m::peripheral::NVIC::unmask(CONSOLE) };
+//! ```
+//!
+//! Each function panics if the instance doesn't exist on the selected chip.
+//! In a constant, that's a compile-time error.
+
+use crate::ral::Interrupt::{self, *};
+
+/// Returns the index of `instance` in an interrupt table.
+///
+/// Tables for peripherals with a sole instance have one entry, at
+/// [`SOLE_INSTANCE`](crate::ral::SOLE_INSTANCE). Otherwise, instances
+/// start at one.
+const fn index(instance: u8, sole: bool) -> usize {
+    if sole {
+        (instance as usize).wrapping_sub(crate::ral::SOLE_INSTANCE as usize)
+    } else {
+        (instance as usize).wrapping_sub(1)
+    }
+}
+
+const LPUART: &[Interrupt] = &[
+    LPUART1,
+    LPUART2,
+    LPUART3,
+    LPUART4,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPUART5,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPUART6,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPUART7,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPUART8,
+    #[cfg(family = "imxrt11xx")]
+    LPUART9,
+    #[cfg(family = "imxrt11xx")]
+    LPUART10,
+    #[cfg(family = "imxrt11xx")]
+    LPUART11,
+    #[cfg(family = "imxrt11xx")]
+    LPUART12,
+];
+
+/// Returns the interrupt for an LPUART instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn lpuart(instance: u8) -> Interrupt {
+    let index = index(instance, false);
+    assert!(index < LPUART.len(), "LPUART instance doesn't exist");
+    LPUART[index]
+}
+
+const LPSPI: &[Interrupt] = &[
+    LPSPI1,
+    LPSPI2,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPSPI3,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPSPI4,
+    #[cfg(family = "imxrt11xx")]
+    LPSPI5,
+    #[cfg(family = "imxrt11xx")]
+    LPSPI6,
+];
+
+/// Returns the interrupt for an LPSPI instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn lpspi(instance: u8) -> Interrupt {
+    let index = index(instance, false);
+    assert!(index < LPSPI.len(), "LPSPI instance doesn't exist");
+    LPSPI[index]
+}
+
+const LPI2C: &[Interrupt] = &[
+    LPI2C1,
+    LPI2C2,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPI2C3,
+    #[cfg(not(chip = "imxrt1010"))]
+    LPI2C4,
+    #[cfg(family = "imxrt11xx")]
+    LPI2C5,
+    #[cfg(family = "imxrt11xx")]
+    LPI2C6,
+];
+
+/// Returns the interrupt for an LPI2C instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn lpi2c(instance: u8) -> Interrupt {
+    let index = index(instance, false);
+    assert!(index < LPI2C.len(), "LPI2C instance doesn't exist");
+    LPI2C[index]
+}
+
+const GPT: &[Interrupt] = &[
+    GPT1,
+    GPT2,
+    #[cfg(family = "imxrt11xx")]
+    GPT3,
+    #[cfg(family = "imxrt11xx")]
+    GPT4,
+    #[cfg(family = "imxrt11xx")]
+    GPT5,
+    #[cfg(family = "imxrt11xx")]
+    GPT6,
+];
+
+/// Returns the interrupt for a GPT instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn gpt(instance: u8) -> Interrupt {
+    let index = index(instance, false);
+    assert!(index < GPT.len(), "GPT instance doesn't exist");
+    GPT[index]
+}
+
+#[cfg(family = "imxrt10xx")]
+const PIT_SOLE: bool = true;
+#[cfg(family = "imxrt10xx")]
+const PIT: &[Interrupt] = &[Interrupt::PIT];
+#[cfg(family = "imxrt11xx")]
+const PIT_SOLE: bool = false;
+#[cfg(family = "imxrt11xx")]
+const PIT: &[Interrupt] = &[PIT1, PIT2];
+
+/// Returns the interrupt for a PIT instance.
+///
+/// All channels of a PIT share this interrupt.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn pit(instance: u8) -> Interrupt {
+    let index = index(instance, PIT_SOLE);
+    assert!(index < PIT.len(), "PIT instance doesn't exist");
+    PIT[index]
+}
+
+const ADC_SOLE: bool = cfg!(chip = "imxrt1010");
+const ADC: &[Interrupt] = &[
+    ADC1,
+    #[cfg(not(chip = "imxrt1010"))]
+    ADC2,
+];
+
+/// Returns the interrupt for an ADC instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn adc(instance: u8) -> Interrupt {
+    let index = index(instance, ADC_SOLE);
+    assert!(index < ADC.len(), "ADC instance doesn't exist");
+    ADC[index]
+}
+
+const USB_SOLE: bool = cfg!(any(chip = "imxrt1010", chip = "imxrt1020"));
+const USB: &[Interrupt] = &[
+    USB_OTG1,
+    #[cfg(not(any(chip = "imxrt1010", chip = "imxrt1020")))]
+    USB_OTG2,
+];
+
+/// Returns the interrupt for a USB instance.
+///
+/// # Panics
+///
+/// Panics if the instance doesn't exist.
+pub const fn usb(instance: u8) -> Interrupt {
+    let index = index(instance, USB_SOLE);
+    assert!(index < USB.len(), "USB instance doesn't exist");
+    USB[index]
+}
+
+/// Returns the interrupt for a PWM submodule.
+///
+/// This is the same as [`flexpwm::interrupt_for_submodule`](crate::flexpwm::interrupt_for_submodule).
+///
+/// # Panics
+///
+/// Panics if the instance or submodule doesn't exist.
+pub const fn flexpwm(instance: u8, submodule: u8) -> Interrupt {
+    crate::flexpwm::interrupt_for_submodule(instance, submodule)
+}
+
+#[cfg(chip = "imxrt1010")]
+const DMA: &[Interrupt] = &[
+    DMA0, DMA1, DMA2, DMA3, DMA4, DMA5, DMA6, DMA7, DMA8, DMA9, DMA10, DMA11, DMA12, DMA13, DMA14,
+    DMA15,
+];
+#[cfg(not(chip = "imxrt1010"))]
+const DMA: &[Interrupt] = &[
+    DMA0_DMA16,
+    DMA1_DMA17,
+    DMA2_DMA18,
+    DMA3_DMA19,
+    DMA4_DMA20,
+    DMA5_DMA21,
+    DMA6_DMA22,
+    DMA7_DMA23,
+    DMA8_DMA24,
+    DMA9_DMA25,
+    DMA10_DMA26,
+    DMA11_DMA27,
+    DMA12_DMA28,
+    DMA13_DMA29,
+    DMA14_DMA30,
+    DMA15_DMA31,
+];
+
+/// Returns the interrupt for a DMA channel.
+///
+/// On chips with 32 DMA channels, channels `n` and `n + 16` share an
+/// interrupt.
+///
+/// # Panics
+///
+/// Panics if the channel doesn't exist.
+pub const fn dma(channel: usize) -> Interrupt {
+    assert!(
+        channel < crate::dma::CHANNEL_COUNT,
+        "DMA channel doesn't exist"
+    );
+    DMA[channel % DMA.len()]
+}
+
+/// The interrupt for DMA errors.
+pub const DMA_ERROR: Interrupt = Interrupt::DMA_ERROR;
+
+#[cfg(test)]
+mod tests {
+    use super::Interrupt;
+
+    #[test]
+    fn instances() {
+        assert_eq!(super::lpuart(1), Interrupt::LPUART1);
+        assert_eq!(super::lpuart(4), Interrupt::LPUART4);
+        assert_eq!(super::lpspi(2), Interrupt::LPSPI2);
+        assert_eq!(super::lpi2c(1), Interrupt::LPI2C1);
+        assert_eq!(super::gpt(2), Interrupt::GPT2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_instance_zero() {
+        super::lpuart(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_instance() {
+        super::lpi2c(7);
+    }
+
+    #[cfg(chip = "imxrt1060")]
+    #[test]
+    fn imxrt1060() {
+        assert_eq!(super::lpuart(8), Interrupt::LPUART8);
+        assert_eq!(super::pit(crate::ral::SOLE_INSTANCE), Interrupt::PIT);
+        assert_eq!(super::adc(2), Interrupt::ADC2);
+        assert_eq!(super::usb(2), Interrupt::USB_OTG2);
+        assert_eq!(super::dma(3), Interrupt::DMA3_DMA19);
+        assert_eq!(super::dma(19), Interrupt::DMA3_DMA19);
+        assert_eq!(super::flexpwm(2, 3), Interrupt::PWM2_3);
+    }
+
+    #[cfg(chip = "imxrt1010")]
+    #[test]
+    fn imxrt1010() {
+        use crate::ral::SOLE_INSTANCE;
+        assert_eq!(super::adc(SOLE_INSTANCE), Interrupt::ADC1);
+        assert_eq!(super::usb(SOLE_INSTANCE), Interrupt::USB_OTG1);
+        assert_eq!(super::dma(15), Interrupt::DMA15);
+    }
+}
@@ -0,0 +1,372 @@
+//! Keypad port.
+//!
+//! The keypad port, [`Kpp`], scans a matrix keypad of up to eight rows and
+//! eight columns. Rows are inputs with pull-ups, and columns are open-drain
+//! outputs. A [`scan`](Kpp::scan) drives one column low at a time, and reads
+//! which rows are low. The result is a [`KeyState`], a bitmap of pressed keys.
+//!
+//! Mechanical keys bounce. Feed scans into a [`Debouncer`] to get clean
+//! key down and key up [`KeyEvent`]s. You provide the debouncer with a
+//! millisecond timestamp from your own tick source.
+//!
+//! # Interrupts
+//!
+//! Between scans, the KPP drives all columns low. A key press pulls its row
+//! low, which sets the [`KEY_DEPRESS`](Status::KEY_DEPRESS) status. Use the
+//! key depress interrupt to learn that you should start scanning. Then scan
+//! from a timer, feeding the debouncer, until the debouncer reports that all
+//! keys are released. Then re-enable the key depress interrupt.
+//!
+//! # Example
+//!
+//! Scan a 4x4 keypad on rows 0 through 3, and columns 0 through 3.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::kpp::{Debouncer, KeyEvent, Kpp};
+//!
+//! # fn now_ms() -> u32 { 0 }
+//! let mut kpp = Kpp::new(unsafe { ral::kpp::KPP::instance() }, 0x0F, 0x0F);
+//! let mut debouncer = Debouncer::new(20);
+//!
+//! loop {
+//!     let scan = kpp.scan();
+//!     for event in debouncer.update(now_ms(), scan) {
+//!         match event {
+//!             KeyEvent::Down(key) => { /* key.row, key.column pressed */ }
+//!             KeyEvent::Up(key) => { /* key.row, key.column released */ }
+//!         }
+//!     }
+//!     # break;
+//! }
+//! ```
+
+use crate::ral;
+
+/// The keypad port.
+pub struct Kpp {
+    kpp: ral::kpp::KPP,
+    rows: u8,
+    columns: u8,
+    scan_delay_cycles: u32,
+}
+
+bitflags::bitflags! {
+    /// Keypad status flags.
+    pub struct Status : u16 {
+        /// A key was pressed.
+        const KEY_DEPRESS = 1 << 0;
+        /// All keys were released.
+        const KEY_RELEASE = 1 << 1;
+    }
+}
+
+bitflags::bitflags! {
+    /// Keypad interrupts.
+    pub struct Interrupts : u16 {
+        /// Interrupt when a key is pressed.
+        const KEY_DEPRESS = 1 << 8;
+        /// Interrupt when all keys are released.
+        const KEY_RELEASE = 1 << 9;
+    }
+}
+
+/// The default delay between driving a column and reading the rows.
+pub const DEFAULT_SCAN_DELAY_CYCLES: u32 = 600;
+
+impl Kpp {
+    /// Use the KPP with a set of rows and columns.
+    ///
+    /// `rows` and `columns` are bitmasks; bit `n` enables row or column `n`.
+    /// You're responsible for configuring the pads. When `new` returns, the
+    /// KPP drives all columns low, so that it can detect key presses.
+    /// Interrupts are disabled.
+    pub fn new(kpp: ral::kpp::KPP, rows: u8, columns: u8) -> Self {
+        ral::write_reg!(ral::kpp, kpp, KPCR, KRE: rows as u16, KCO: columns as u16);
+        ral::write_reg!(ral::kpp, kpp, KPDR, KCD: 0);
+        ral::write_reg!(ral::kpp, kpp, KDDR, KRDD: 0, KCDD: columns as u16);
+        ral::write_reg!(ral::kpp, kpp, KPSR, KPKD: 1, KPKR: 1, KDSC: 1, KRSS: 1);
+        Self {
+            kpp,
+            rows,
+            columns,
+            scan_delay_cycles: DEFAULT_SCAN_DELAY_CYCLES,
+        }
+    }
+
+    /// Release the KPP instance.
+    pub fn release(self) -> ral::kpp::KPP {
+        self.kpp
+    }
+
+    /// Set the delay, in CPU clock cycles, between driving a column and
+    /// reading the rows.
+    ///
+    /// The delay lets the row lines settle. Increase the delay if scans
+    /// report keys in the wrong column. The default is
+    /// [`DEFAULT_SCAN_DELAY_CYCLES`].
+    pub fn set_scan_delay(&mut self, cycles: u32) {
+        self.scan_delay_cycles = cycles;
+    }
+
+    /// Scan the keypad, and return the pressed keys.
+    ///
+    /// The scan doesn't debounce keys. After the scan, the KPP drives all
+    /// columns low, and resets its key depress and key release detection.
+    pub fn scan(&mut self) -> KeyState {
+        let mut state = KeyState::empty();
+        for column in 0..8 {
+            if self.columns & (1 << column) == 0 {
+                continue;
+            }
+            ral::write_reg!(ral::kpp, self.kpp, KPDR, KCD: !(1u16 << column) & 0xFF);
+            cortex_m::asm::delay(self.scan_delay_cycles);
+            let rows = !ral::read_reg!(ral::kpp, self.kpp, KPDR, KRD) as u8 & self.rows;
+            state.0 |= (rows as u64) << (column * 8);
+        }
+        ral::write_reg!(ral::kpp, self.kpp, KPDR, KCD: 0);
+        ral::modify_reg!(ral::kpp, self.kpp, KPSR, KPKD: 0, KPKR: 0, KDSC: 1, KRSS: 1);
+        state
+    }
+
+    /// Returns the status flags.
+    pub fn status(&self) -> Status {
+        Status::from_bits_truncate(ral::read_reg!(ral::kpp, self.kpp, KPSR))
+    }
+
+    /// Clear the status flags.
+    pub fn clear_status(&mut self, status: Status) {
+        let status = status & Status::all();
+        ral::modify_reg!(ral::kpp, self.kpp, KPSR, |kpsr| {
+            (kpsr & !Status::all().bits()) | status.bits()
+        });
+    }
+
+    /// Returns the enabled interrupts.
+    pub fn interrupts(&self) -> Interrupts {
+        Interrupts::from_bits_truncate(ral::read_reg!(ral::kpp, self.kpp, KPSR))
+    }
+
+    /// Set the enabled interrupts.
+    pub fn set_interrupts(&mut self, interrupts: Interrupts) {
+        ral::modify_reg!(ral::kpp, self.kpp, KPSR, |kpsr| {
+            (kpsr & !(Interrupts::all().bits() | Status::all().bits())) | interrupts.bits()
+        });
+    }
+}
+
+/// A key in the keypad matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// The key's row, 0 through 7.
+    pub row: u8,
+    /// The key's column, 0 through 7.
+    pub column: u8,
+}
+
+impl Key {
+    const fn bit(self) -> u64 {
+        1 << ((self.column & 7) * 8 + (self.row & 7))
+    }
+
+    const fn from_bit(bit: u32) -> Self {
+        Self {
+            row: (bit % 8) as u8,
+            column: (bit / 8) as u8,
+        }
+    }
+}
+
+/// A bitmap of pressed keys.
+///
+/// Bit `column * 8 + row` is set when the key at that row and column
+/// is pressed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeyState(u64);
+
+impl KeyState {
+    /// No keys are pressed.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Create a key state from its bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the bits of this key state.
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if no keys are pressed.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if `key` is pressed.
+    pub const fn is_pressed(&self, key: Key) -> bool {
+        self.0 & key.bit() != 0
+    }
+
+    /// Returns an iterator over the pressed keys.
+    pub fn keys(&self) -> impl Iterator<Item = Key> {
+        let mut bits = self.0;
+        core::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(Key::from_bit(bit))
+        })
+    }
+}
+
+/// A debounced key event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEvent {
+    /// The key was pressed.
+    Down(Key),
+    /// The key was released.
+    Up(Key),
+}
+
+/// Debounces keypad scans.
+///
+/// A key change is accepted once the scans agree for the debounce time.
+/// Timestamps are in milliseconds, from any tick source that you choose.
+/// They may wrap.
+#[derive(Debug, Clone, Copy)]
+pub struct Debouncer {
+    debounce_ms: u32,
+    stable: KeyState,
+    pending: KeyState,
+    since_ms: u32,
+}
+
+impl Debouncer {
+    /// Create a debouncer that accepts changes after `debounce_ms` milliseconds.
+    pub const fn new(debounce_ms: u32) -> Self {
+        Self {
+            debounce_ms,
+            stable: KeyState::empty(),
+            pending: KeyState::empty(),
+            since_ms: 0,
+        }
+    }
+
+    /// Returns the debounced key state.
+    pub const fn state(&self) -> KeyState {
+        self.stable
+    }
+
+    /// Returns `true` if no keys are pressed, and no changes are pending.
+    ///
+    /// When the debouncer is idle, you may stop scanning, and wait for
+    /// a key depress interrupt.
+    pub const fn is_idle(&self) -> bool {
+        self.stable.is_empty() && self.pending.is_empty()
+    }
+
+    /// Update the debouncer with a scan taken at `now_ms`.
+    ///
+    /// Returns the keys that changed. The iterator is empty if the
+    /// debounced state didn't change.
+    pub fn update(&mut self, now_ms: u32, scan: KeyState) -> Events {
+        if scan != self.pending {
+            self.pending = scan;
+            self.since_ms = now_ms;
+        }
+        let mut events = Events {
+            pressed: 0,
+            released: 0,
+        };
+        if self.pending != self.stable && now_ms.wrapping_sub(self.since_ms) >= self.debounce_ms {
+            events.pressed = self.pending.0 & !self.stable.0;
+            events.released = self.stable.0 & !self.pending.0;
+            self.stable = self.pending;
+        }
+        events
+    }
+}
+
+/// Key events from a [`Debouncer`].
+///
+/// The iterator produces all key down events, then all key up events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Events {
+    pressed: u64,
+    released: u64,
+}
+
+impl Iterator for Events {
+    type Item = KeyEvent;
+    fn next(&mut self) -> Option<KeyEvent> {
+        if self.pressed != 0 {
+            let bit = self.pressed.trailing_zeros();
+            self.pressed &= self.pressed - 1;
+            Some(KeyEvent::Down(Key::from_bit(bit)))
+        } else if self.released != 0 {
+            let bit = self.released.trailing_zeros();
+            self.released &= self.released - 1;
+            Some(KeyEvent::Up(Key::from_bit(bit)))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Debouncer, Key, KeyEvent, KeyState};
+
+    const A: Key = Key { row: 1, column: 2 };
+    const B: Key = Key { row: 7, column: 7 };
+
+    #[test]
+    fn key_state() {
+        let state = KeyState::from_bits(A.bit() | B.bit());
+        assert!(state.is_pressed(A));
+        assert!(state.is_pressed(B));
+        assert!(!state.is_pressed(Key { row: 0, column: 0 }));
+        let mut keys = state.keys();
+        assert_eq!(keys.next(), Some(A));
+        assert_eq!(keys.next(), Some(B));
+        assert_eq!(keys.next(), None);
+    }
+
+    #[test]
+    fn debounce() {
+        let mut debouncer = Debouncer::new(10);
+        let pressed = KeyState::from_bits(A.bit());
+
+        assert_eq!(debouncer.update(0, pressed).next(), None);
+        // Bounce resets the timer.
+        assert_eq!(debouncer.update(5, KeyState::empty()).next(), None);
+        assert_eq!(debouncer.update(6, pressed).next(), None);
+        assert_eq!(debouncer.update(15, pressed).next(), None);
+        assert!(!debouncer.is_idle());
+
+        let mut events = debouncer.update(16, pressed);
+        assert_eq!(events.next(), Some(KeyEvent::Down(A)));
+        assert_eq!(events.next(), None);
+        assert_eq!(debouncer.update(17, pressed).next(), None);
+
+        // Timestamps wrap.
+        let both = KeyState::from_bits(B.bit());
+        assert_eq!(debouncer.update(u32::MAX - 2, both).next(), None);
+        let events: [_; 2] = {
+            let mut events = debouncer.update(7, both);
+            [events.next(), events.next()]
+        };
+        assert_eq!(events, [Some(KeyEvent::Down(B)), Some(KeyEvent::Up(A))]);
+
+        debouncer.update(20, KeyState::empty());
+        debouncer.update(30, KeyState::empty());
+        assert!(debouncer.is_idle());
+    }
+}
@@ -49,6 +49,11 @@
 //! # Some(()) }();
 //! ```
 //!
+//! # SMBus
+//!
+//! The driver supports SMBus block transfers with packet error checking. See
+//! [`Lpi2c::smbus_block_read`] and [`Lpi2c::smbus_block_write`].
+//!
 //! # Limitations
 //!
 //! This driver supports standard, fast, and fast+ modes. High speed mode is not
@@ -60,6 +65,9 @@ use crate::iomuxc::lpi2c;
 use crate::ral;
 use eh02::blocking::i2c as blocking;
 
+mod smbus;
+pub use smbus::{pec, pec_update, pin_low_timeout, SmbusError, MAX_BLOCK_LEN, SMBUS_TIMEOUT_US};
+
 /// Data direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -410,6 +418,21 @@ impl<'a, const N: u8> Disabled<'a, N> {
 
         watermark
     }
+
+    /// Set the pin low timeout.
+    ///
+    /// If SCL is low for longer than `pin_low * 256` prescaled clock cycles,
+    /// the controller sets [`ControllerStatus::PIN_LOW_TIMEOUT`], and the
+    /// current transfer fails. Use [`pin_low_timeout`] to compute `pin_low`.
+    /// Zero disables the timeout.
+    ///
+    /// Returns the value committed to the hardware. This may be different
+    /// than the supplied `pin_low`, since it's limited to 12 bits.
+    pub fn set_pin_low_timeout(&mut self, pin_low: u16) -> u16 {
+        let pin_low = pin_low.min(0xFFF);
+        ral::write_reg!(ral::lpi2c, self.lpi2c, MCFGR3, PINLOW: pin_low as u32);
+        pin_low
+    }
 }
 
 impl<const N: u8> Drop for Disabled<'_, N> {
@@ -1171,6 +1194,39 @@ impl Timing {
     pub const fn prescaler(&self) -> Prescaler {
         self.prescaler
     }
+    /// Returns the SCL frequency (Hz) for these timings.
+    ///
+    /// `clock_hz` is the LPI2C functional clock frequency. This assumes
+    /// that the SCL rise time is negligible. Slow rise times reduce the
+    /// actual frequency.
+    pub const fn frequency(&self, clock_hz: u32) -> u32 {
+        let config = &self.clock_configuration;
+        let scl_latency = line_latency_cycles(config.filtscl, 0, self.prescaler);
+        let period = config.clkhi as u32 + config.clklo as u32 + 2 + scl_latency as u32;
+        clock_hz / self.prescaler.divider() as u32 / period
+    }
+    /// Returns the error between these timings and `clock_speed`, in percent.
+    ///
+    /// `clock_hz` is the LPI2C functional clock frequency. Use this in a const
+    /// context to reject a clock that can't produce your I2C clock speed.
+    ///
+    /// ```
+    /// use imxrt_hal::lpi2c::{ClockSpeed, Timing};
+    ///
+    /// const LPI2C_CLK_HZ: u32 = 8_000_000;
+    /// const TIMING: Timing = Timing::ideal(LPI2C_CLK_HZ, ClockSpeed::MHz1);
+    /// const _: () = assert!(TIMING.error_percent(LPI2C_CLK_HZ, ClockSpeed::MHz1) < 5.0);
+    /// ```
+    pub const fn error_percent(&self, clock_hz: u32, clock_speed: ClockSpeed) -> f32 {
+        let actual = self.frequency(clock_hz) as f32;
+        let target = clock_speed.frequency() as f32;
+        let error = if actual > target {
+            actual - target
+        } else {
+            target - actual
+        };
+        error * 100.0 / target
+    }
     /// Override the BUSIDLE parameter.
     ///
     /// The minimum BUSIDLE is computed by CLKLO, SETHOLD, and CLKHI. Use
@@ -1214,4 +1270,17 @@ mod tests {
         assert_eq!(timings.clock_configuration.filtscl, 0);
         assert_eq!(timings.clock_configuration.filtsda, 0);
     }
+
+    #[test]
+    fn timing_error() {
+        const MHZ1: Timing = Timing::ideal(8_000_000, ClockSpeed::MHz1);
+        assert_eq!(MHZ1.frequency(8_000_000), 1_000_000);
+        assert_eq!(MHZ1.error_percent(8_000_000, ClockSpeed::MHz1), 0.0);
+
+        // 8MHz / 79 cycles is 1.3% above 100KHz.
+        let khz100 = Timing::ideal(8_000_000, ClockSpeed::KHz100);
+        assert_eq!(khz100.frequency(8_000_000), 101_265);
+        let error = khz100.error_percent(8_000_000, ClockSpeed::KHz100);
+        assert!((1.2..1.3).contains(&error), "{error}");
+    }
 }
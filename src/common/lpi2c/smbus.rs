@@ -0,0 +1,225 @@
+//! SMBus and PMBus extensions.
+//!
+//! SMBus adds three features to I2C:
+//!
+//! - a packet error code (PEC), a CRC-8 over every byte in the transaction,
+//!   including the address bytes. See [`pec`].
+//! - block transfers, where a count byte precedes the data. See
+//!   [`smbus_block_read`](Lpi2c::smbus_block_read) and
+//!   [`smbus_block_write`](Lpi2c::smbus_block_write).
+//! - a clock low timeout, after which devices release the bus. Use
+//!   [`pin_low_timeout`] with
+//!   [`Disabled::set_pin_low_timeout`](super::Disabled::set_pin_low_timeout) to
+//!   detect the timeout. The transfer then fails with
+//!   [`ControllerStatus::PIN_LOW_TIMEOUT`].
+//!
+//! PMBus builds on these SMBus transfers.
+
+use super::{ControllerCommand, ControllerStatus, Lpi2c, Prescaler};
+
+/// The shortest SMBus clock low timeout, in microseconds.
+pub const SMBUS_TIMEOUT_US: u32 = 25_000;
+
+/// The most data bytes in an SMBus block transfer.
+///
+/// This is the SMBus 3 limit. Older devices use, at most, 32 bytes.
+pub const MAX_BLOCK_LEN: usize = 255;
+
+/// Errors from SMBus transfers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SmbusError {
+    /// The controller signaled an error.
+    Controller(ControllerStatus),
+    /// The block is larger than the buffer, or larger than [`MAX_BLOCK_LEN`].
+    ///
+    /// For reads, this is the block length that the device sent.
+    BlockLength(usize),
+    /// The PEC from the device doesn't match the transaction.
+    Pec {
+        /// The PEC computed over the transaction.
+        expected: u8,
+        /// The PEC that the device sent.
+        received: u8,
+    },
+}
+
+impl From<ControllerStatus> for SmbusError {
+    fn from(status: ControllerStatus) -> Self {
+        Self::Controller(status)
+    }
+}
+
+/// Continue computing a packet error code.
+///
+/// `crc` is the PEC computed over all previous bytes, or zero to start a
+/// new PEC.
+pub const fn pec_update(mut crc: u8, bytes: &[u8]) -> u8 {
+    let mut idx = 0;
+    while idx < bytes.len() {
+        crc ^= bytes[idx];
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        idx += 1;
+    }
+    crc
+}
+
+/// Compute the packet error code for `bytes`.
+///
+/// The PEC is a CRC-8 with polynomial `x^8 + x^2 + x + 1`. It covers every
+/// byte in the transaction, including the address bytes with their read / write
+/// bit.
+pub const fn pec(bytes: &[u8]) -> u8 {
+    pec_update(0, bytes)
+}
+
+/// Returns the `PINLOW` value that detects a pin held low for at least
+/// `timeout_us` microseconds.
+///
+/// `clock_hz` is the LPI2C functional clock, and `prescaler` is the prescaler
+/// in your [`Timing`](super::Timing). Rounds up, and saturates at the largest
+/// `PINLOW` value. Use [`SMBUS_TIMEOUT_US`] for the SMBus timeout.
+pub const fn pin_low_timeout(clock_hz: u32, prescaler: Prescaler, timeout_us: u32) -> u16 {
+    let cycles = (clock_hz / prescaler.divider() as u32) as u64 * timeout_us as u64;
+    let pinlow = cycles.div_ceil(1_000_000 * 256);
+    if pinlow > 0xFFF {
+        0xFFF
+    } else {
+        pinlow as u16
+    }
+}
+
+impl<P, const N: u8> Lpi2c<P, N> {
+    /// Prepare an SMBus transfer with the device at `address`, and send `command`.
+    fn smbus_start(&mut self, address: u8, command: u8) -> Result<(), ControllerStatus> {
+        self.check_busy()?;
+        self.clear_fifo();
+        self.clear_controller_status(ControllerStatus::W1C);
+        self.smbus_transmit(ControllerCommand::write(address))?;
+        self.smbus_transmit(ControllerCommand::Transmit { byte: command })
+    }
+
+    /// Wait for transmit FIFO space, then enqueue `command`.
+    fn smbus_transmit(&mut self, command: ControllerCommand) -> Result<(), ControllerStatus> {
+        self.wait_for_transmit()?;
+        self.enqueue_controller_command(command);
+        Ok(())
+    }
+
+    /// Send a STOP, and wait for the controller to finish.
+    fn smbus_stop(&mut self) -> Result<(), ControllerStatus> {
+        self.smbus_transmit(ControllerCommand::Stop)?;
+        self.wait_for_end_of_packet()?;
+        self.wait_for_controller_idle()
+    }
+
+    /// Perform an SMBus block read, with PEC, from the device at `address`.
+    ///
+    /// This sends `command`, then reads the device's block length, the block,
+    /// and the PEC. The block lands at the start of `buffer`. Returns the
+    /// block length after checking the PEC. The device must support PEC.
+    ///
+    /// If the device's block is larger than `buffer`, this stops the
+    /// transaction and returns [`SmbusError::BlockLength`].
+    pub fn smbus_block_read(
+        &mut self,
+        address: u8,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<usize, SmbusError> {
+        self.smbus_start(address, command)?;
+        let read = ControllerCommand::read(address);
+        self.smbus_transmit(read)?;
+        self.smbus_transmit(ControllerCommand::Receive { count: 1 })?;
+        let count = self.wait_for_data()?;
+        let len = usize::from(count);
+        if len > buffer.len() {
+            self.smbus_stop()?;
+            return Err(SmbusError::BlockLength(len));
+        }
+
+        let block = &mut buffer[..len];
+        if !block.is_empty() {
+            self.smbus_transmit(ControllerCommand::Receive { count })?;
+            for slot in block.iter_mut() {
+                *slot = self.wait_for_data()?;
+            }
+        }
+        self.smbus_transmit(ControllerCommand::Receive { count: 1 })?;
+        let received = self.wait_for_data()?;
+        self.smbus_stop()?;
+
+        let header = [address << 1, command, (address << 1) | 1, count];
+        let expected = pec_update(pec(&header), block);
+        if expected != received {
+            return Err(SmbusError::Pec { expected, received });
+        }
+        Ok(len)
+    }
+
+    /// Perform an SMBus block write, with PEC, to the device at `address`.
+    ///
+    /// This sends `command`, the block length, `block`, and the PEC.
+    ///
+    /// Returns [`SmbusError::BlockLength`], without touching the bus, if `block`
+    /// is larger than [`MAX_BLOCK_LEN`].
+    pub fn smbus_block_write(
+        &mut self,
+        address: u8,
+        command: u8,
+        block: &[u8],
+    ) -> Result<(), SmbusError> {
+        if block.len() > MAX_BLOCK_LEN {
+            return Err(SmbusError::BlockLength(block.len()));
+        }
+        let count = block.len() as u8;
+        self.smbus_start(address, command)?;
+        self.smbus_transmit(ControllerCommand::Transmit { byte: count })?;
+        for &byte in block {
+            self.smbus_transmit(ControllerCommand::Transmit { byte })?;
+        }
+        let byte = pec_update(pec(&[address << 1, command, count]), block);
+        self.smbus_transmit(ControllerCommand::Transmit { byte })?;
+        self.smbus_stop()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pec, pec_update, pin_low_timeout, Prescaler, SMBUS_TIMEOUT_US};
+
+    #[test]
+    fn packet_error_code() {
+        // CRC-8/SMBUS check value.
+        assert_eq!(pec(b"123456789"), 0xF4);
+        assert_eq!(pec(&[]), 0);
+        assert_eq!(pec_update(pec(b"1234"), b"56789"), 0xF4);
+    }
+
+    #[test]
+    fn pin_low_timeouts() {
+        // 24MHz, no prescaler: 600,000 cycles, 2344 units of 256.
+        assert_eq!(
+            pin_low_timeout(24_000_000, Prescaler::Prescaler1, SMBUS_TIMEOUT_US),
+            2344
+        );
+        assert_eq!(
+            pin_low_timeout(24_000_000, Prescaler::Prescaler8, SMBUS_TIMEOUT_US),
+            293
+        );
+        assert_eq!(
+            pin_low_timeout(60_000_000, Prescaler::Prescaler1, SMBUS_TIMEOUT_US),
+            0xFFF
+        );
+        assert_eq!(pin_low_timeout(24_000_000, Prescaler::Prescaler1, 0), 0);
+    }
+}
@@ -131,11 +131,17 @@ pub enum LpspiError {
     /// 4095 bits.
     FrameSize,
     /// FIFO error in the given direction.
+    ///
+    /// A transmit error indicates that the transmit FIFO underran.
+    /// A receive error indicates that the receive FIFO overflowed,
+    /// and received data was lost.
     Fifo(Direction),
     /// Bus is busy at the start of a transfer.
     Busy,
     /// Caller provided no data.
     NoData,
+    /// Received data matched one or both of the match fields.
+    DataMatch,
 }
 
 /// An LPSPI transaction definition.
@@ -246,6 +252,13 @@ pub struct Transaction {
     /// `Transaction`, one that had [`continuous`](Self::continuous) set.
     /// The default value is `false`.
     pub continuing: bool,
+    /// The SPI mode for this transaction.
+    ///
+    /// If `Some`, the transaction uses this mode instead of the mode
+    /// set with [`Lpspi::set_mode`]. Use this to talk to devices that
+    /// need different modes on the same bus. The default is `None`; the
+    /// transaction uses the driver's mode.
+    pub mode: Option<Mode>,
 
     frame_size: u16,
 }
@@ -305,6 +318,7 @@ impl Transaction {
                 frame_size: frame_size - 1,
                 continuing: false,
                 continuous: false,
+                mode: None,
             })
         } else {
             Err(LpspiError::FrameSize)
@@ -312,31 +326,32 @@ impl Transaction {
     }
 }
 
+/// Returns the TCR `(CPOL, CPHA)` field values for an SPI mode.
+fn clock_mode_fields(mode: Mode) -> (u32, u32) {
+    use ral::lpspi::TCR::{CPHA, CPOL};
+    let cpol = if mode.polarity == Polarity::IdleHigh {
+        CPOL::RW::CPOL_1
+    } else {
+        CPOL::RW::CPOL_0
+    };
+    let cpha = if mode.phase == Phase::CaptureOnSecondTransition {
+        CPHA::RW::CPHA_1
+    } else {
+        CPHA::RW::CPHA_0
+    };
+    (cpol, cpha)
+}
+
 /// Sets the clock speed parameters.
 ///
 /// This should only happen when the LPSPI peripheral is disabled.
 fn set_spi_clock(source_clock_hz: u32, spi_clock_hz: u32, reg: &ral::lpspi::RegisterBlock) {
-    // Round up, so we always get a resulting SPI clock that is
-    // equal or less than the requested frequency.
-    let half_div =
-        u32::try_from(1 + u64::from(source_clock_hz - 1) / (u64::from(spi_clock_hz) * 2)).unwrap();
-
-    // Make sure SCKDIV is between 0 and 255
-    // For some reason SCK starts to misbehave in between frames
-    // if half_div is less than 3.
-    let half_div = half_div.clamp(3, 128);
-    // Because half_div is in range [3,128], sckdiv is in range [4, 254].
-    let sckdiv = 2 * (half_div - 1);
-
+    let timing = ClockConfigs::compute(source_clock_hz, spi_clock_hz);
     ral::write_reg!(ral::lpspi, reg, CCR,
-        // Delay between two clock transitions of two consecutive transfers
-        // is exactly sckdiv/2, which causes the transfer to be seamless.
-        DBT: half_div - 1,
-        // Add one sckdiv/2 setup and hold time before and after the transfer,
-        // to make sure the signal is stable at sample time
-        PCSSCK: half_div - 1,
-        SCKPCS: half_div - 1,
-        SCKDIV: sckdiv
+        DBT: timing.dbt as u32,
+        PCSSCK: timing.pcssck as u32,
+        SCKPCS: timing.sckpcs as u32,
+        SCKDIV: timing.sckdiv as u32
     );
 }
 
@@ -372,6 +387,80 @@ pub struct ClockConfigs {
     pub sckdiv: u8,
 }
 
+impl ClockConfigs {
+    /// Computes the clock configurations used by [`set_clock_hz`](Disabled::set_clock_hz).
+    ///
+    /// `source_clock_hz` is the LPSPI peripheral clock speed, and `spi_clock_hz`
+    /// is the intended SCK frequency. The resulting SCK frequency is equal to,
+    /// or less than, `spi_clock_hz`. The SCK frequency is between 1/4 and 1/256
+    /// of the peripheral clock.
+    ///
+    /// This function can run at compile time.
+    ///
+    /// ```
+    /// use imxrt_hal::lpspi::ClockConfigs;
+    ///
+    /// const LPSPI_CLK_HZ: u32 = 132_000_000;
+    /// const CONFIGS: ClockConfigs = ClockConfigs::compute(LPSPI_CLK_HZ, 10_000_000);
+    /// const _: () = assert!(CONFIGS.error_percent(LPSPI_CLK_HZ, 10_000_000) < 10.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spi_clock_hz` is zero.
+    pub const fn compute(source_clock_hz: u32, spi_clock_hz: u32) -> Self {
+        // Round up, so we always get a resulting SPI clock that is
+        // equal or less than the requested frequency.
+        let half_div =
+            1 + (source_clock_hz.saturating_sub(1) as u64 / (spi_clock_hz as u64 * 2)) as u32;
+
+        // Make sure SCKDIV is between 0 and 255
+        // For some reason SCK starts to misbehave in between frames
+        // if half_div is less than 3.
+        let half_div = if half_div < 3 {
+            3
+        } else if half_div > 128 {
+            128
+        } else {
+            half_div
+        };
+        // Because half_div is in range [3,128], sckdiv is in range [4, 254].
+        let sckdiv = 2 * (half_div - 1);
+
+        ClockConfigs {
+            // Add one sckdiv/2 setup and hold time before and after the transfer,
+            // to make sure the signal is stable at sample time
+            sckpcs: (half_div - 1) as u8,
+            pcssck: (half_div - 1) as u8,
+            // Delay between two clock transitions of two consecutive transfers
+            // is exactly sckdiv/2, which causes the transfer to be seamless.
+            dbt: (half_div - 1) as u8,
+            sckdiv: sckdiv as u8,
+        }
+    }
+
+    /// Returns the SCK frequency (Hz) for these configurations.
+    ///
+    /// `source_clock_hz` is the LPSPI peripheral clock speed.
+    pub const fn frequency(&self, source_clock_hz: u32) -> u32 {
+        source_clock_hz / (self.sckdiv as u32 + 2)
+    }
+
+    /// Returns the error between the SCK frequency and `spi_clock_hz`, in percent.
+    ///
+    /// `source_clock_hz` is the LPSPI peripheral clock speed.
+    pub const fn error_percent(&self, source_clock_hz: u32, spi_clock_hz: u32) -> f32 {
+        let actual = source_clock_hz as f32 / (self.sckdiv as u32 + 2) as f32;
+        let target = spi_clock_hz as f32;
+        let error = if actual > target {
+            actual - target
+        } else {
+            target - actual
+        };
+        error * 100.0 / target
+    }
+}
+
 /// An LPSPI driver.
 ///
 /// The driver exposes low-level methods for coordinating
@@ -534,6 +623,22 @@ impl<P, const N: u8> Lpspi<P, N> {
         (self.lpspi, self.pins)
     }
 
+    /// Disable the LPSPI, then release the SPI driver components.
+    ///
+    /// This disables DMA and all interrupts, then waits for the LPSPI to finish
+    /// its current transfer and disable itself. The other configurations, like
+    /// the clock configuration, don't change. Use this to hand the LPSPI to
+    /// another driver, or to change its clock, without the LPSPI signaling DMA
+    /// requests or interrupts.
+    pub fn release_disabled(mut self) -> (ral::lpspi::Instance<N>, P) {
+        self.disable_dma_transmit();
+        self.disable_dma_receive();
+        self.set_interrupts(Interrupts::empty());
+        self.set_enable(false);
+        while self.is_enabled() {}
+        self.release()
+    }
+
     /// Returns the bit order configuration.
     ///
     /// See notes in [`set_bit_order`](Lpspi::set_bit_order) to
@@ -576,6 +681,31 @@ impl<P, const N: u8> Lpspi<P, N> {
         ral::write_reg!(ral::lpspi, self.lpspi, SR, flags.bits());
     }
 
+    /// Decode and clear an error from the status flags.
+    ///
+    /// Returns `None` if there is no transmit error, receive error, or data match.
+    /// If more than one condition is flagged, this returns (and clears) a receive
+    /// error first, then a transmit error, then a data match. Call this
+    /// method again to observe the remaining conditions.
+    ///
+    /// This does not recover the driver from the error. Before you take a receive
+    /// error, you should empty the receive FIFO. Before you take a transmit
+    /// error, you should end the transfer.
+    pub fn take_error(&mut self) -> Option<LpspiError> {
+        let status = self.status();
+        let (flag, error) = if status.intersects(Status::RECEIVE_ERROR) {
+            (Status::RECEIVE_ERROR, LpspiError::Fifo(Direction::Rx))
+        } else if status.intersects(Status::TRANSMIT_ERROR) {
+            (Status::TRANSMIT_ERROR, LpspiError::Fifo(Direction::Tx))
+        } else if status.intersects(Status::DATA_MATCH) {
+            (Status::DATA_MATCH, LpspiError::DataMatch)
+        } else {
+            return None;
+        };
+        self.clear_status(flag);
+        Some(error)
+    }
+
     /// Read the interrupt enable bits.
     pub fn interrupts(&self) -> Interrupts {
         Interrupts::from_bits_truncate(ral::read_reg!(ral::lpspi, self.lpspi, IER))
@@ -728,11 +858,97 @@ impl<P, const N: u8> Lpspi<P, N> {
     /// Set the SPI mode for the peripheral.
     ///
     /// This only affects the next transfer; ongoing transfers
-    /// will not be influenced.
+    /// will not be influenced. You may change the mode between
+    /// transfers, even while the peripheral is enabled.
+    ///
+    /// To override the mode for a single transaction, see
+    /// [`Transaction::mode`].
     pub fn set_mode(&mut self, mode: Mode) {
         self.mode = mode;
     }
 
+    /// Returns the SPI mode for the peripheral.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Enable (`true`) or disable (`false`) loopback.
+    ///
+    /// This temporarily disables the peripheral to change the setting.
+    /// See [`Disabled::set_loopback`] for more information.
+    pub fn set_loopback(&mut self, enable: bool) {
+        self.disabled(|spi| spi.set_loopback(enable));
+    }
+
+    /// Returns `true` if loopback is enabled.
+    pub fn is_loopback(&self) -> bool {
+        ral::read_reg!(ral::lpspi, self.lpspi, CFGR1, PINCFG == PINCFG_2)
+    }
+
+    /// Enable (`true`) or disable (`false`) three-wire mode.
+    ///
+    /// This temporarily disables the peripheral to change the setting.
+    /// See [`Disabled::set_three_wire`] for more information.
+    pub fn set_three_wire(&mut self, enable: bool) {
+        self.disabled(|spi| spi.set_three_wire(enable));
+    }
+
+    /// Returns `true` if three-wire mode is enabled.
+    pub fn is_three_wire(&self) -> bool {
+        ral::read_reg!(ral::lpspi, self.lpspi, CFGR1, PINCFG == PINCFG_1)
+    }
+
+    /// Write `write`, then read into `read`, within one transaction.
+    ///
+    /// Chip select stays asserted for the whole exchange. The LPSPI sends
+    /// every byte of `write`, discarding the received data. It then receives
+    /// `read.len()` bytes without driving its data output. Use this in
+    /// [three-wire mode](Self::set_three_wire) to send a command, then
+    /// read the device's response on the same data line. It also works with
+    /// separate data lines.
+    ///
+    /// Returns [`LpspiError::FrameSize`] if either buffer is empty, or if
+    /// either buffer is too large for one transaction.
+    pub fn write_then_read(&mut self, write: &[u8], read: &mut [u8]) -> Result<(), LpspiError> {
+        let bit_order = self.bit_order();
+
+        let mut command = Transaction::new_words(write)?;
+        command.bit_order = bit_order;
+        command.receive_data_mask = true;
+        command.continuous = true;
+
+        let mut response = Transaction::new_words(read)?;
+        response.bit_order = bit_order;
+        response.transmit_data_mask = true;
+        response.continuous = true;
+        response.continuing = true;
+
+        // Ends the continuous transfer, de-asserting chip select.
+        let mut end = Transaction::new(8)?;
+        end.bit_order = bit_order;
+
+        let (write_words, read_words) = (word_count(write), word_count(read));
+        let result = crate::spin_on(async {
+            self.spin_for_fifo_space().await?;
+            self.enqueue_transaction(&command);
+            self.spin_transmit(TransmitBuffer::new(write), write_words)
+                .await?;
+
+            self.spin_for_fifo_space().await?;
+            self.enqueue_transaction(&response);
+            self.spin_receive(ReceiveBuffer::new(read), read_words)
+                .await?;
+
+            self.spin_for_fifo_space().await?;
+            self.enqueue_transaction(&end);
+            Ok(())
+        });
+
+        result.inspect_err(|_| self.recover_from_error())?;
+
+        self.flush()
+    }
+
     /// Place a transaction definition into the transmit FIFO.
     ///
     /// Once this definition is popped from the transmit FIFO, this may
@@ -741,9 +957,10 @@ impl<P, const N: u8> Lpspi<P, N> {
     /// You're responsible for making sure there's space in the transmit
     /// FIFO for this transaction command.
     pub fn enqueue_transaction(&mut self, transaction: &Transaction) {
+        let (cpol, cpha) = clock_mode_fields(transaction.mode.unwrap_or(self.mode));
         ral::write_reg!(ral::lpspi, self.lpspi, TCR,
-            CPOL: if self.mode.polarity == Polarity::IdleHigh { CPOL_1 } else { CPOL_0 },
-            CPHA: if self.mode.phase == Phase::CaptureOnSecondTransition { CPHA_1 } else { CPHA_0 },
+            CPOL: cpol,
+            CPHA: cpha,
             PRESCALE: PRESCALE_0,
             PCS: PCS_0,
             WIDTH: WIDTH_0,
@@ -1213,6 +1430,49 @@ impl<'a, const N: u8> Disabled<'a, N> {
         }
     }
 
+    /// Enable (`true`) or disable (`false`) loopback.
+    ///
+    /// When loopback is enabled, the LPSPI receives its input data from the
+    /// SDO pin, so every received word is the word that the LPSPI transmitted.
+    /// You don't need to connect SDO to SDI. The SDI pin is unused. Use this
+    /// to test the LPSPI, and its DMA transfers, without a peripheral.
+    ///
+    /// The loopback happens at the SDO pin, so SDO must be configured for
+    /// the LPSPI. The LPSPI still drives SDO, SCK, and the chip selects, and
+    /// it asserts the chip selects as usual. If a device is connected, it
+    /// sees every transfer. Select a chip select that isn't connected to a
+    /// device, or disconnect the device, while loopback is enabled.
+    ///
+    /// Loopback applies to the controller. Don't enable loopback while
+    /// acting as a peripheral.
+    #[inline]
+    pub fn set_loopback(&mut self, enable: bool) {
+        self.set_pin_config(ral::lpspi::CFGR1::PINCFG::RW::PINCFG_2, enable);
+    }
+
+    /// Enable (`true`) or disable (`false`) three-wire mode.
+    ///
+    /// In three-wire mode, the SDI pin carries both input and output data;
+    /// the SDO pin is unused. The LPSPI drives SDI while it transmits, and it
+    /// releases SDI when a transaction masks the transmit data. Define
+    /// transactions that either send or receive, not both, or use
+    /// [`Lpspi::write_then_read`].
+    ///
+    /// Three-wire mode and [loopback](Self::set_loopback) are exclusive.
+    /// Enabling one disables the other. Disabling three-wire mode doesn't
+    /// affect loopback, and disabling loopback doesn't affect three-wire mode.
+    #[inline]
+    pub fn set_three_wire(&mut self, enable: bool) {
+        self.set_pin_config(ral::lpspi::CFGR1::PINCFG::RW::PINCFG_1, enable);
+    }
+
+    /// Enable or disable a PINCFG mode.
+    fn set_pin_config(&mut self, mode: u32, enable: bool) {
+        let current = ral::read_reg!(ral::lpspi, self.lpspi, CFGR1, PINCFG);
+        let pincfg = pin_config(current, mode, enable);
+        ral::modify_reg!(ral::lpspi, self.lpspi, CFGR1, PINCFG: pincfg);
+    }
+
     /// Become an LPSPI peripheral.
     ///
     /// By default, the LPSPI driver acts as a controller, driving I/O.
@@ -1445,7 +1705,6 @@ impl<'a, W> ReceiveBuffer<'a, W>
 where
     W: Word,
 {
-    #[cfg(test)] // TODO(mciantyre) remove once needed in non-test code.
     fn new(buffer: &'a mut [W]) -> Self {
         // Safety: pointer offset math meets expectations.
         unsafe { Self::from_raw(buffer.as_mut_ptr(), buffer.len()) }
@@ -1514,10 +1773,63 @@ fn transfer_in_place<W: Word>(buffer: &mut [W]) -> (TransmitBuffer<'_, W>, Recei
     }
 }
 
+/// Returns the PINCFG value that enables, or disables, `mode`.
+///
+/// Disabling a mode only restores the default pin configuration if `mode`
+/// is the current configuration. Otherwise, the current mode stays.
+const fn pin_config(current: u32, mode: u32, enable: bool) -> u32 {
+    use ral::lpspi::CFGR1::PINCFG::RW::PINCFG_0;
+    if enable {
+        mode
+    } else if current == mode {
+        PINCFG_0
+    } else {
+        current
+    }
+}
+
 /// Tests try to approximate the way we'll use TransmitBuffer and ReceiveBuffer
 /// in firmware. Consider running these with miri to evaluate unsafe usages.
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn pin_config() {
+        use super::pin_config;
+        use crate::ral::lpspi::CFGR1::PINCFG::RW::{PINCFG_0, PINCFG_1, PINCFG_2};
+        const THREE_WIRE: u32 = PINCFG_1;
+        const LOOPBACK: u32 = PINCFG_2;
+
+        assert_eq!(pin_config(PINCFG_0, LOOPBACK, true), LOOPBACK);
+        assert_eq!(pin_config(THREE_WIRE, LOOPBACK, true), LOOPBACK);
+        assert_eq!(pin_config(LOOPBACK, LOOPBACK, false), PINCFG_0);
+        // Disabling three-wire mode keeps loopback, and the reverse.
+        assert_eq!(pin_config(LOOPBACK, THREE_WIRE, false), LOOPBACK);
+        assert_eq!(pin_config(THREE_WIRE, LOOPBACK, false), THREE_WIRE);
+        assert_eq!(pin_config(PINCFG_0, THREE_WIRE, false), PINCFG_0);
+    }
+
+    #[test]
+    fn clock_configs() {
+        use super::ClockConfigs;
+
+        const CONFIGS: ClockConfigs = ClockConfigs::compute(132_000_000, 10_000_000);
+        // 132MHz / 10MHz / 2 rounds up to 7.
+        assert_eq!(CONFIGS.sckdiv, 12);
+        assert_eq!(CONFIGS.dbt, 6);
+        assert_eq!(CONFIGS.frequency(132_000_000), 9_428_571);
+        let error = CONFIGS.error_percent(132_000_000, 10_000_000);
+        assert!((5.7..5.8).contains(&error), "{error}");
+
+        // Exact dividers have no error.
+        let exact = ClockConfigs::compute(100_000_000, 5_000_000);
+        assert_eq!(exact.frequency(100_000_000), 5_000_000);
+        assert_eq!(exact.error_percent(100_000_000, 5_000_000), 0.0);
+
+        // Clamped to the fastest and slowest SCK.
+        assert_eq!(ClockConfigs::compute(100_000_000, 100_000_000).sckdiv, 4);
+        assert_eq!(ClockConfigs::compute(100_000_000, 1).sckdiv, 254);
+    }
+
     #[test]
     fn transfer_in_place_interleaved_read_write_u32() {
         const BUFFER: [u32; 9] = [42u32, 43, 44, 45, 46, 47, 48, 49, 50];
@@ -1720,6 +2032,15 @@ mod tests {
         assert_eq!(buffer, [0xDEADBEEF, 0xAD1CAC1D, 0x77777777]);
     }
 
+    #[test]
+    fn clock_mode_fields() {
+        use super::{clock_mode_fields, MODE_0, MODE_1, MODE_2, MODE_3};
+        assert_eq!(clock_mode_fields(MODE_0), (0, 0));
+        assert_eq!(clock_mode_fields(MODE_1), (0, 1));
+        assert_eq!(clock_mode_fields(MODE_2), (1, 0));
+        assert_eq!(clock_mode_fields(MODE_3), (1, 1));
+    }
+
     #[test]
     fn transaction_frame_sizes() {
         assert!(super::Transaction::new_words(&[1u8]).is_ok());
@@ -10,6 +10,8 @@
 //! - DMA transfers and receives
 //! - Non-blocking and blocking implementations of `embedded-hal` serial
 //!   traits.
+//! - RS-485 transceiver direction control. See [`Rs485`].
+//! - baud rate detection. See [`Lpuart::autobaud`].
 //!
 //! # Example
 //!
@@ -63,6 +65,9 @@
 use crate::iomuxc;
 use crate::ral::{self, lpuart::Instance};
 
+mod rs485;
+pub use rs485::{DriverEnable, HardwareRts, Rs485};
+
 /// LPUART pins.
 pub struct Pins<TX, RX>
 where
@@ -176,6 +181,21 @@ impl<P, const N: u8> Lpuart<P, N> {
         (self.lpuart, self.pins)
     }
 
+    /// Disable the LPUART, then release all components of the driver.
+    ///
+    /// This disables DMA, all interrupts, and the transmitter and receiver.
+    /// Data in the FIFOs is lost. The other configurations, like the baud rate,
+    /// don't change. Use this to hand the LPUART to another driver, or to change
+    /// its clock, without the LPUART signaling DMA requests or interrupts.
+    pub fn release_disabled(mut self) -> (Instance<N>, P) {
+        self.disable_dma_transmit();
+        self.disable_dma_receive();
+        self.disable(|uart| uart.set_interrupts(Interrupts::empty()));
+        self.set_enable(Direction::Tx, false);
+        self.set_enable(Direction::Rx, false);
+        self.release()
+    }
+
     /// Borrow the LPUART pins.
     pub fn pins(&self) -> &P {
         &self.pins
@@ -290,11 +310,34 @@ impl<P, const N: u8> Lpuart<P, N> {
     /// Flush data from the FIFO.
     ///
     /// This does not flush anything that's already in the transmit or receive register.
+    /// To wait for the transmitter to send all data, use
+    /// [`wait_transmit_complete`](Self::wait_transmit_complete).
     #[inline]
     pub fn flush_fifo(&mut self, direction: Direction) {
         flush_fifo(&self.lpuart, direction);
     }
 
+    /// Returns `true` if the transmitter is idle.
+    ///
+    /// The transmitter is idle when the transmit FIFO is empty, and the
+    /// last data, preamble, or break character left the shift register.
+    #[inline]
+    pub fn is_transmit_complete(&self) -> bool {
+        self.status().contains(Status::TRANSMIT_COMPLETE)
+    }
+
+    /// Block until the transmitter sends all data.
+    ///
+    /// When this returns, the last stop bit left the shift register. Use
+    /// this before you release a transceiver's driver enable, or change
+    /// a half-duplex line's direction.
+    ///
+    /// If the transmitter is disabled while it's sending data, this may
+    /// never return.
+    pub fn wait_transmit_complete(&self) {
+        while !self.is_transmit_complete() {}
+    }
+
     /// Return the interrupt flags.
     ///
     /// The interrupt flags indicate the reasons that this peripheral may generate an
@@ -378,6 +421,116 @@ impl<P, const N: u8> Lpuart<P, N> {
             Ok(Some(data.into()))
         }
     }
+
+    /// Measure the baud rate of an incoming `'U'` (`0x55`) character.
+    ///
+    /// The LPUART doesn't have a hardware baud rate detector, so this times
+    /// the receive pin's edges with the cycle counter of `delay`. `'U'`
+    /// alternates its bits, so its falling edges are two bits apart, and the
+    /// first and last falling edges are eight bits apart. The sender must
+    /// send the character least significant bit first, with at least eight
+    /// data bits. Ask the device to send a few `'U'` characters, then call
+    /// this.
+    ///
+    /// Returns the `Baud` for the detected rate, computed for the LPUART's
+    /// `source_clock_hz`. This doesn't change the baud rate; commit the
+    /// result with [`set_baud`](Disabled::set_baud). Use
+    /// [`Baud::value`] to learn the detected rate. Returns `None` if
+    /// there's no `'U'` within `timeout_us` microseconds.
+    ///
+    /// The receiver is disabled while measuring. Afterwards, this flushes the
+    /// receive FIFO and clears the status flags, then restores the receiver.
+    /// Interrupts that fire while measuring make the measurement less
+    /// accurate, or make it fail; mask them, if you can.
+    pub fn autobaud<const HZ: u32>(
+        &mut self,
+        delay: &crate::delay::CycleDelay<HZ>,
+        source_clock_hz: u32,
+        timeout_us: u32,
+    ) -> Option<Baud> {
+        let receiving = self.is_enabled(Direction::Rx);
+        self.set_enable(Direction::Rx, false);
+        self.clear_status(Status::RECEIVE_EDGE);
+
+        let timeout = u64::from(timeout_us) * u64::from(HZ) / 1_000_000;
+        let mut elapsed = Elapsed::new(delay.cycles());
+        let mut edges = [0; AUTOBAUD_EDGES];
+        let mut count = 0;
+        let rate = loop {
+            let now = delay.cycles();
+            if self.status().contains(Status::RECEIVE_EDGE) {
+                self.clear_status(Status::RECEIVE_EDGE);
+                edges[count] = now;
+                count += 1;
+                if count == AUTOBAUD_EDGES {
+                    if let Some(rate) = autobaud_rate(&edges, HZ) {
+                        break Some(rate);
+                    }
+                    // Maybe we started in the middle of a character.
+                    edges.copy_within(1.., 0);
+                    count -= 1;
+                }
+            }
+            if elapsed.update(now) >= timeout {
+                break None;
+            }
+        };
+
+        self.flush_fifo(Direction::Rx);
+        self.clear_status(Status::all());
+        self.set_enable(Direction::Rx, receiving);
+        rate.map(|rate| Baud::compute(source_clock_hz, rate))
+    }
+}
+
+/// Counts cycles beyond the range of a 32-bit cycle counter.
+///
+/// Update the count at least once per counter period.
+struct Elapsed {
+    last: u32,
+    cycles: u64,
+}
+
+impl Elapsed {
+    fn new(now: u32) -> Self {
+        Self {
+            last: now,
+            cycles: 0,
+        }
+    }
+
+    /// Returns the cycles elapsed since construction.
+    fn update(&mut self, now: u32) -> u64 {
+        self.cycles += u64::from(now.wrapping_sub(self.last));
+        self.last = now;
+        self.cycles
+    }
+}
+
+/// The number of falling edges in a `'U'` character.
+const AUTOBAUD_EDGES: usize = 5;
+
+/// Compute the baud rate from the times of a `'U'` character's falling edges.
+///
+/// `edges` are timestamps from an `hz` counter. Returns `None` if the edges
+/// aren't evenly spaced.
+fn autobaud_rate(edges: &[u32; AUTOBAUD_EDGES], hz: u32) -> Option<u32> {
+    // Eight bits from the start bit to the last data bit's falling edge.
+    let span = edges[AUTOBAUD_EDGES - 1].wrapping_sub(edges[0]);
+    if span == 0 {
+        return None;
+    }
+    // Each interval is two bits, a quarter of the span. Allow a quarter
+    // of that for jitter.
+    let even = edges.windows(2).all(|pair| {
+        let interval = pair[1].wrapping_sub(pair[0]);
+        (u64::from(interval) * 4).abs_diff(u64::from(span)) <= u64::from(span) / 4
+    });
+    if !even {
+        return None;
+    }
+    let rate = (u64::from(hz) * 8 + u64::from(span) / 2) / u64::from(span);
+    u32::try_from(rate).ok()
 }
 
 fn flush_fifo<const N: u8>(lpuart: &Instance<N>, direction: Direction) {
@@ -496,6 +649,62 @@ impl<'a, const N: u8> Disabled<'a, N> {
         size
     }
 
+    /// Use the RTS output to enable a transceiver's driver.
+    ///
+    /// When `Some`, the hardware asserts RTS before it starts transmitting,
+    /// and deasserts RTS after it sends the last stop bit. The polarity
+    /// describes the asserted level. When `None`, RTS doesn't follow the
+    /// transmitter. The default is `None`.
+    ///
+    /// You're responsible for configuring the RTS pin.
+    pub fn set_transmit_rts(&mut self, polarity: Option<RtsPolarity>) {
+        ral::modify_reg!(ral::lpuart, self.lpuart, MODIR,
+            TXRTSE: polarity.is_some() as u32,
+            TXRTSPOL: polarity.map(|p| p as u32).unwrap_or(0));
+    }
+
+    /// Ignore received data until the receiver sees its address.
+    ///
+    /// `mode` describes how the receiver recognizes an address character.
+    /// When an address character matches `address`, the receiver stores the
+    /// address and the data that follows, and it sets
+    /// [`Status::MATCH1`]. When an address character doesn't match, the
+    /// receiver discards it and the data that follows. Data that's discarded
+    /// doesn't set the receive flags, so it doesn't generate receive interrupts.
+    ///
+    /// [`MatchMode::AddressMark`] switches the LPUART to 9-bit characters
+    /// without parity. To return to 8-bit characters, use [`set_parity`](Self::set_parity).
+    pub fn set_match_wakeup(&mut self, address: u8, mode: MatchMode) {
+        if mode == MatchMode::AddressMark {
+            ral::modify_reg!(ral::lpuart, self.lpuart, CTRL, PE: 0, M: 1);
+        }
+        ral::modify_reg!(ral::lpuart, self.lpuart, MATCH, MA1: match_address(address, mode));
+        ral::modify_reg!(ral::lpuart, self.lpuart, BAUD, MATCFG: mode as u32, MAEN1: 1);
+    }
+
+    /// Set a second address that the receiver accepts.
+    ///
+    /// Use this for a broadcast address, alongside the address you set with
+    /// [`set_match_wakeup`](Self::set_match_wakeup). A match sets
+    /// [`Status::MATCH2`]. Use `None` to disable the second address.
+    pub fn set_second_match_address(&mut self, address: Option<u8>) {
+        let mode = match ral::read_reg!(ral::lpuart, self.lpuart, BAUD, MATCFG) {
+            0 => MatchMode::AddressMark,
+            _ => MatchMode::IdleLine,
+        };
+        let ma2 = address.map(|address| match_address(address, mode));
+        ral::modify_reg!(ral::lpuart, self.lpuart, MATCH, MA2: ma2.unwrap_or(0));
+        ral::modify_reg!(ral::lpuart, self.lpuart, BAUD, MAEN2: ma2.is_some() as u32);
+    }
+
+    /// Receive all data, regardless of the address.
+    ///
+    /// This disables both match addresses. It doesn't change the
+    /// character size.
+    pub fn disable_match_wakeup(&mut self) {
+        ral::modify_reg!(ral::lpuart, self.lpuart, BAUD, MATCFG: 0, MAEN1: 0, MAEN2: 0);
+    }
+
     /// Set the interrupt flags for this LPUART peripheral.
     ///
     /// Use `set_interrupts` to enable or disable interrupt generation for
@@ -514,6 +723,16 @@ impl<'a, const N: u8> Disabled<'a, N> {
     }
 }
 
+/// The asserted level of the RTS output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RtsPolarity {
+    /// RTS is low when asserted.
+    ActiveLow = 0,
+    /// RTS is high when asserted.
+    ActiveHigh = 1,
+}
+
 /// Values specific to the baud rate.
 ///
 /// To compute the values for a given baud rate,
@@ -568,51 +787,126 @@ impl Baud {
         source_clock_hz / (self.sbr * self.osr)
     }
 
+    /// Returns the error between this baud and the intended `baud`, in percent.
+    ///
+    /// `source_clock_hz` is the UART clock frequency (Hz). Use this in a const
+    /// context to reject a clock that can't produce your baud rate.
+    ///
+    /// ```
+    /// use imxrt_hal::lpuart::Baud;
+    ///
+    /// const UART_CLOCK_HZ: u32 = 24_000_000;
+    /// const BAUD: Baud = Baud::compute(UART_CLOCK_HZ, 115200);
+    /// const _: () = assert!(BAUD.error_percent(UART_CLOCK_HZ, 115200) < 1.0);
+    /// ```
+    pub const fn error_percent(self, source_clock_hz: u32, baud: u32) -> f32 {
+        let actual = source_clock_hz as f32 / (self.sbr * self.osr) as f32;
+        let target = baud as f32;
+        let error = if actual > target {
+            actual - target
+        } else {
+            target - actual
+        };
+        error * 100.0 / target
+    }
+
+    /// The smallest oversampling rate.
+    pub const MIN_OSR: u32 = 4;
+    /// The largest oversampling rate.
+    pub const MAX_OSR: u32 = 32;
+    /// The largest baud rate modulo divisor.
+    pub const MAX_SBR: u32 = (1 << 13) - 1;
+
     /// Computes a timings struct that represents a baud rate.
     ///
     /// `source_clock_hz` is the UART clock frequency (Hz). `baud`
     /// is the intended baud rate.
+    ///
+    /// This selects the oversampling rate, and the divisor, that produce
+    /// the smallest baud rate error. When multiple oversampling rates
+    /// produce the same error, this prefers the highest oversampling rate,
+    /// since it samples each bit more often. To select your own oversampling
+    /// rate, use [`compute_with_osr`](Self::compute_with_osr).
     pub const fn compute(source_clock_hz: u32, baud: u32) -> Baud {
-        const fn max(left: u32, right: u32) -> u32 {
-            if left > right {
-                left
-            } else {
-                right
-            }
-        }
-        const fn min(left: u32, right: u32) -> u32 {
-            if left < right {
-                left
-            } else {
-                right
+        let mut best = Baud::compute_with_osr(source_clock_hz, baud, Self::MAX_OSR);
+        let mut osr = Self::MAX_OSR - 1;
+        while osr >= Self::MIN_OSR {
+            let candidate = Baud::compute_with_osr(source_clock_hz, baud, osr);
+            if candidate.is_more_accurate(&best, source_clock_hz, baud) {
+                best = candidate;
             }
+            osr -= 1;
         }
+        best
+    }
 
-        let mut err = u32::MAX;
-        let mut best_osr = 0;
-        let mut best_sbr = 0;
-
-        let mut osr = 8;
-        let mut sbr = 1;
-        while osr <= 32 {
-            while sbr < 8192 {
-                let b = source_clock_hz / (sbr * osr);
-                let e = max(baud, b) - min(baud, b);
-                if e < err {
-                    err = e;
-                    best_osr = osr;
-                    best_sbr = sbr;
-                }
-                sbr += 1;
-            }
-            osr += 1;
+    /// Computes a timings struct that represents a baud rate, using
+    /// the oversampling rate `osr`.
+    ///
+    /// `source_clock_hz` is the UART clock frequency (Hz). `baud` is the
+    /// intended baud rate. This clamps `osr` between [`MIN_OSR`](Self::MIN_OSR)
+    /// and [`MAX_OSR`](Self::MAX_OSR), then selects the divisor that produces
+    /// the smallest baud rate error. A lower oversampling rate reaches higher
+    /// baud rates. A higher oversampling rate samples each bit more often,
+    /// which improves noise immunity.
+    ///
+    /// ```
+    /// use imxrt_hal::lpuart::Baud;
+    ///
+    /// const UART_CLOCK_HZ: u32 = 24_000_000;
+    /// const BAUD: Baud = Baud::compute_with_osr(UART_CLOCK_HZ, 1_000_000, 12);
+    /// assert_eq!(BAUD.value(UART_CLOCK_HZ), 1_000_000);
+    /// ```
+    pub const fn compute_with_osr(source_clock_hz: u32, baud: u32, osr: u32) -> Baud {
+        let osr = if osr < Self::MIN_OSR {
+            Self::MIN_OSR
+        } else if osr > Self::MAX_OSR {
+            Self::MAX_OSR
+        } else {
+            osr
+        };
+        let baud = if baud == 0 { 1 } else { baud };
+
+        // At most source_clock_hz / MIN_OSR, so it fits in a u32.
+        let sbr = (source_clock_hz as u64 / (baud as u64 * osr as u64)) as u32;
+        let low = Baud::with_sbr(osr, sbr);
+        let high = Baud::with_sbr(osr, sbr + 1);
+        if high.is_more_accurate(&low, source_clock_hz, baud) {
+            high
+        } else {
+            low
         }
+    }
+
+    /// Create a baud with a clamped divisor.
+    const fn with_sbr(osr: u32, sbr: u32) -> Baud {
         Baud {
-            osr: best_osr,
-            sbr: best_sbr,
-            bothedge: 4 <= best_osr && best_osr <= 7,
+            osr,
+            sbr: if sbr < 1 {
+                1
+            } else if sbr > Self::MAX_SBR {
+                Self::MAX_SBR
+            } else {
+                sbr
+            },
+            bothedge: osr <= 7,
         }
     }
+
+    /// Returns `true` if this baud is closer to `baud` than `other`.
+    const fn is_more_accurate(&self, other: &Baud, source_clock_hz: u32, baud: u32) -> bool {
+        // The baud error is |source_clock_hz - baud * divisor| / divisor.
+        // Compare the fractions without dividing.
+        const fn error(this: &Baud, source_clock_hz: u32, baud: u32) -> (u128, u128) {
+            let divisor = this.sbr as u128 * this.osr as u128;
+            let ideal = baud as u128 * divisor;
+            let clock = source_clock_hz as u128;
+            (clock.abs_diff(ideal), divisor)
+        }
+        let (this_num, this_den) = error(self, source_clock_hz, baud);
+        let (other_num, other_den) = error(other, source_clock_hz, baud);
+        this_num * other_den < other_num * this_den
+    }
 }
 
 /// Parity bit selection.
@@ -630,6 +924,30 @@ pub enum Parity {
     Odd = 1,
 }
 
+/// How the receiver recognizes an address character.
+///
+/// See [`set_match_wakeup`](crate::lpuart::Disabled::set_match_wakeup).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum MatchMode {
+    /// Characters with the ninth bit set are addresses.
+    ///
+    /// This uses 9-bit characters. The transmitter marks an address by
+    /// setting the ninth bit.
+    AddressMark = 0,
+    /// The first character after an idle line is an address.
+    IdleLine = 1,
+}
+
+/// Returns the `MAn` value that matches `address` in `mode`.
+const fn match_address(address: u8, mode: MatchMode) -> u32 {
+    match mode {
+        // The address mark is part of the comparison.
+        MatchMode::AddressMark => 0x100 | address as u32,
+        MatchMode::IdleLine => address as u32,
+    }
+}
+
 impl Parity {
     /// No parity.
     pub const NONE: Option<Parity> = None;
@@ -673,6 +991,14 @@ bitflags::bitflags! {
         const FRAMING_ERROR = 1 << 25;
         /// Parity error interrupt enable.
         const PARITY_ERROR = 1 << 24;
+        /// Match 1 interrupt enable.
+        ///
+        /// Triggers when the `MATCH1` _status_ bit is high.
+        const MATCH1 = 1 << 15;
+        /// Match 2 interrupt enable.
+        ///
+        /// Triggers when the `MATCH2` _status_ bit is high.
+        const MATCH2 = 1 << 14;
         /// Transmit empty interrupt enable.
         ///
         /// Triggers when the `TRANSMIT_EMPTY` _status_ bit is high.
@@ -751,6 +1077,11 @@ impl From<ReadData> for u8 {
 bitflags::bitflags! {
     /// Status flags.
     pub struct Status : u32 {
+        /// Receive pin active edge.
+        ///
+        /// Set when the receive pin sees a falling edge, or a rising edge
+        /// if the receive pin is inverted.
+        const RECEIVE_EDGE = 1 << 30;
         /// Receiver active flag.
         ///
         /// Set when the receiver detects a start bit. Cleared when
@@ -805,6 +1136,16 @@ bitflags::bitflags! {
         /// This is also available in the read flags. However, setting it here
         /// allows you to clear the noise flag in the status register.
         const PARITY_ERROR = 1 << 16;
+        /// Match 1 flag.
+        ///
+        /// Set when the receiver sees the address that it's waiting for.
+        /// See [`set_match_wakeup`](crate::lpuart::Disabled::set_match_wakeup).
+        const MATCH1 = 1 << 15;
+        /// Match 2 flag.
+        ///
+        /// Set when the receiver sees the second match address. See
+        /// [`set_second_match_address`](crate::lpuart::Disabled::set_second_match_address).
+        const MATCH2 = 1 << 14;
 
         // All flags up to and including bit 13 are marked 'reserved'
         // in the status register. We're using these for other 'status'
@@ -1004,8 +1345,7 @@ impl<P, const N: u8> eio06::Write for Lpuart<P, N> {
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
-        while !self.status().contains(Status::TRANSMIT_COMPLETE) {}
-
+        self.wait_transmit_complete();
         Ok(())
     }
 }
@@ -1043,7 +1383,7 @@ impl<P, const N: u8> eio06::Read for Lpuart<P, N> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Baud, ReadData, ReadFlags, Status};
+    use super::{match_address, Baud, MatchMode, ReadData, ReadFlags, Status};
 
     #[test]
     fn approximate_baud() {
@@ -1066,6 +1406,49 @@ mod tests {
             assert_eq!(BAUD.osr, 8);
         }
         assert!(!BAUD.bothedge);
+
+        // 115384 is 0.16% above 115200.
+        const ERROR: f32 = BAUD.error_percent(UART_CLOCK_HZ, TARGET_BAUD);
+        assert!((0.15..0.17).contains(&ERROR), "{ERROR}");
+    }
+
+    #[test]
+    fn baud_oversampling() {
+        const UART_CLOCK_HZ: u32 = 24_000_000;
+
+        // 6Mbit needs an OSR below 8, and both edge sampling.
+        let baud = Baud::compute(UART_CLOCK_HZ, 6_000_000);
+        assert_eq!((baud.osr, baud.sbr), (4, 1));
+        assert!(baud.bothedge);
+
+        // Prefer the highest OSR that's exact.
+        let baud = Baud::compute(UART_CLOCK_HZ, 1_000_000);
+        assert_eq!((baud.osr, baud.sbr), (24, 1));
+        assert!(!baud.bothedge);
+
+        let baud = Baud::compute_with_osr(UART_CLOCK_HZ, 1_000_000, 8);
+        assert_eq!((baud.osr, baud.sbr), (8, 3));
+        assert_eq!(baud.value(UART_CLOCK_HZ), 1_000_000);
+
+        // Select the closer divisor. 24MHz / (16 * 115200) is 13.02.
+        let baud = Baud::compute_with_osr(UART_CLOCK_HZ, 115200, 16);
+        assert_eq!((baud.osr, baud.sbr), (16, 13));
+        // 24MHz / (16 * 9600) is 156.25.
+        let baud = Baud::compute_with_osr(UART_CLOCK_HZ, 9600, 16);
+        assert_eq!(baud.sbr, 156);
+
+        // baud * osr exceeds u32::MAX.
+        let baud = Baud::compute(UART_CLOCK_HZ, u32::MAX / 4);
+        assert_eq!(baud.sbr, 1);
+
+        // Clamp the OSR, and the SBR.
+        assert_eq!(Baud::compute_with_osr(UART_CLOCK_HZ, 9600, 2).osr, 4);
+        assert_eq!(Baud::compute_with_osr(UART_CLOCK_HZ, 9600, 64).osr, 32);
+        assert_eq!(
+            Baud::compute_with_osr(UART_CLOCK_HZ, 1, 32).sbr,
+            Baud::MAX_SBR
+        );
+        assert_eq!(Baud::compute_with_osr(UART_CLOCK_HZ, 24_000_000, 4).sbr, 1);
     }
 
     #[test]
@@ -1087,9 +1470,9 @@ mod tests {
     fn status_flags() {
         assert_eq!(Status::fifo_mask().bits(), (1 << 13) | (1 << 12));
         assert_eq!(Status::fifo_mask().fifo_bits(), (1 << 17) | (1 << 16));
-        assert_eq!(Status::stat_mask().bits(), 0x01FF_0000);
-        assert_eq!(Status::stat_mask().stat_bits(), 0x01FF_0000);
-        assert_eq!(Status::W1C.bits(), 0x001F_3000);
+        assert_eq!(Status::stat_mask().bits(), 0x41FF_C000);
+        assert_eq!(Status::stat_mask().stat_bits(), 0x41FF_C000);
+        assert_eq!(Status::W1C.bits(), 0x401F_F000);
 
         assert!(Status::from_registers(0, (1 << 17) | (1 << 16))
             .contains(Status::TRANSMIT_OVERFLOW | Status::RECEIVE_UNDERFLOW));
@@ -1097,4 +1480,52 @@ mod tests {
 
         assert!(Status::all().contains(Status::TRANSMIT_EMPTY));
     }
+
+    #[test]
+    fn autobaud_rate() {
+        use super::autobaud_rate;
+        const HZ: u32 = 600_000_000;
+        // 115200 baud is about 5208 cycles per bit.
+        assert_eq!(
+            autobaud_rate(&[0, 10417, 20833, 31250, 41667], HZ),
+            Some(115_199)
+        );
+        assert_eq!(
+            autobaud_rate(&[100, 62600, 125100, 187600, 250100], HZ),
+            Some(19_200)
+        );
+        // The counter wraps.
+        assert_eq!(
+            autobaud_rate(&[u32::MAX - 100, 62399, 124899, 187399, 249899], HZ),
+            Some(19_200)
+        );
+        // Started in the middle of a character.
+        assert_eq!(autobaud_rate(&[0, 20000, 30000, 40000, 50000], HZ), None);
+        assert_eq!(autobaud_rate(&[7; 5], HZ), None);
+    }
+
+    #[test]
+    fn autobaud_long_timeout() {
+        use super::Elapsed;
+        const HZ: u64 = 600_000_000;
+        // Ten seconds exceeds the 32-bit cycle counter's range.
+        let timeout = 10 * HZ;
+        assert!(timeout > u64::from(u32::MAX));
+
+        let mut now = u32::MAX - 5;
+        let mut elapsed = Elapsed::new(now);
+        let mut polls = 0;
+        while elapsed.update(now) < timeout {
+            now = now.wrapping_add(HZ as u32 / 2);
+            polls += 1;
+        }
+        assert_eq!(polls, 20);
+    }
+
+    #[test]
+    fn match_addresses() {
+        assert_eq!(match_address(0x42, MatchMode::AddressMark), 0x142);
+        assert_eq!(match_address(0xFF, MatchMode::AddressMark), 0x1FF);
+        assert_eq!(match_address(0x42, MatchMode::IdleLine), 0x42);
+    }
 }
@@ -0,0 +1,183 @@
+//! RS-485 transceiver direction control.
+
+use super::{Lpuart, RtsPolarity};
+use crate::gpio;
+
+/// Controls a transceiver's driver enable.
+///
+/// [`Rs485`] asserts the driver enable before it transmits, and deasserts
+/// the driver enable after the transmitter sends the last stop bit.
+///
+/// This is implemented for GPIO outputs, which are asserted when high.
+/// Implement this for your own type if your transceiver needs a different
+/// driver enable.
+pub trait DriverEnable {
+    /// Assert (`true`) or deassert (`false`) the driver enable.
+    fn set_driver_enable(&mut self, enable: bool);
+}
+
+impl<P> DriverEnable for gpio::Output<P> {
+    fn set_driver_enable(&mut self, enable: bool) {
+        if enable {
+            self.set();
+        } else {
+            self.clear();
+        }
+    }
+}
+
+/// The LPUART's RTS output drives the driver enable.
+///
+/// Prefer this driver enable if your transceiver's driver enable is
+/// connected to the RTS pin. The hardware asserts RTS before the start
+/// bit, and deasserts RTS after the last stop bit, so there are no glitches
+/// from software delays. Construct this with [`Rs485::with_hardware_rts`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct HardwareRts;
+
+impl DriverEnable for HardwareRts {
+    fn set_driver_enable(&mut self, _: bool) {}
+}
+
+/// An LPUART that drives a half-duplex RS-485 transceiver.
+///
+/// `Rs485` asserts the transceiver's driver enable before it transmits. After
+/// the transmitter sends the last stop bit, it deasserts the driver enable.
+/// Use [`lpuart_mut`](Self::lpuart_mut) to receive data, and to access the rest
+/// of the LPUART API.
+///
+/// You may add delays after asserting, and before deasserting, the driver enable.
+/// The delays are in CPU clock cycles. They're useful for transceivers that need
+/// time to switch directions.
+///
+/// # Example
+///
+/// Use a GPIO to drive the transceiver's driver enable.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use hal::{gpio, lpuart};
+///
+/// let mut gpio1 = gpio::Port::new(unsafe { ral::gpio::GPIO1::instance() });
+/// let de = gpio::Output::without_pin(&mut gpio1, 4);
+/// let lpuart2 = lpuart::Lpuart::without_pins(unsafe { ral::lpuart::LPUART2::instance() });
+///
+/// let mut rs485 = lpuart::Rs485::new(lpuart2, de);
+/// rs485.set_delays(600, 600);
+/// rs485.write_all(b"hello");
+/// ```
+pub struct Rs485<P, const N: u8, DE> {
+    lpuart: Lpuart<P, N>,
+    de: DE,
+    pre_delay_cycles: u32,
+    post_delay_cycles: u32,
+}
+
+impl<P, const N: u8, DE> Rs485<P, N, DE>
+where
+    DE: DriverEnable,
+{
+    /// Control a transceiver with an LPUART and a driver enable.
+    ///
+    /// This deasserts the driver enable.
+    pub fn new(lpuart: Lpuart<P, N>, mut de: DE) -> Self {
+        de.set_driver_enable(false);
+        Self {
+            lpuart,
+            de,
+            pre_delay_cycles: 0,
+            post_delay_cycles: 0,
+        }
+    }
+
+    /// Set the delays around transmissions, in CPU clock cycles.
+    ///
+    /// `pre` is the delay between asserting the driver enable and
+    /// sending the first byte. `post` is the delay between the last stop
+    /// bit and deasserting the driver enable. The defaults are zero.
+    ///
+    /// When you use [`HardwareRts`], the hardware controls the driver
+    /// enable. The delays still happen, but they don't affect the driver
+    /// enable timing.
+    pub fn set_delays(&mut self, pre: u32, post: u32) {
+        self.pre_delay_cycles = pre;
+        self.post_delay_cycles = post;
+    }
+
+    /// Transmit all `bytes`, then block until the transmitter sends
+    /// the last stop bit.
+    ///
+    /// The driver enable is asserted while this call transmits.
+    pub fn write_all(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        self.de.set_driver_enable(true);
+        delay(self.pre_delay_cycles);
+
+        for &byte in bytes {
+            while !self.lpuart.try_write(byte) {}
+        }
+        self.lpuart.wait_transmit_complete();
+
+        delay(self.post_delay_cycles);
+        self.de.set_driver_enable(false);
+    }
+
+    /// Access the LPUART.
+    pub fn lpuart(&self) -> &Lpuart<P, N> {
+        &self.lpuart
+    }
+
+    /// Mutably access the LPUART.
+    ///
+    /// Don't use the LPUART to transmit; the driver enable won't
+    /// follow the transmission. The exception is [`HardwareRts`], where
+    /// the hardware drives the driver enable for any transmission,
+    /// including DMA writes.
+    ///
+    /// If you control the driver enable yourself around a DMA write, note
+    /// that `dma_write` completes before the transmitter is idle. Wait for
+    /// transmit complete, or use `dma_write_flush`, before you deassert
+    /// the driver enable.
+    pub fn lpuart_mut(&mut self) -> &mut Lpuart<P, N> {
+        &mut self.lpuart
+    }
+
+    /// Release the LPUART and driver enable.
+    ///
+    /// The driver enable is deasserted.
+    pub fn release(self) -> (Lpuart<P, N>, DE) {
+        (self.lpuart, self.de)
+    }
+}
+
+impl<P, const N: u8> Rs485<P, N, HardwareRts> {
+    /// Use the LPUART's RTS output as the driver enable.
+    ///
+    /// `polarity` is the level that enables the transceiver's driver.
+    /// You're responsible for configuring the RTS pin.
+    pub fn with_hardware_rts(mut lpuart: Lpuart<P, N>, polarity: RtsPolarity) -> Self {
+        lpuart.disable(|lpuart| lpuart.set_transmit_rts(Some(polarity)));
+        Self::new(lpuart, HardwareRts)
+    }
+
+    /// Release the LPUART.
+    ///
+    /// The LPUART no longer drives RTS during transmissions.
+    pub fn release_hardware_rts(self) -> Lpuart<P, N> {
+        let (mut lpuart, _) = self.release();
+        lpuart.disable(|lpuart| lpuart.set_transmit_rts(None));
+        lpuart
+    }
+}
+
+fn delay(cycles: u32) {
+    if cycles > 0 {
+        cortex_m::asm::delay(cycles);
+    }
+}
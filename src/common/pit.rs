@@ -8,6 +8,11 @@
 //! You can chain channels together by using [`Chained`](Chained).
 //! This doubles the width of the timer.
 //!
+//! Each time channels 0 through 3 elapse, they trigger the DMA channel
+//! with the same number. Use [`dma::periodic_read`](crate::dma::periodic_read)
+//! and [`dma::periodic_write`](crate::dma::periodic_write) to pace a DMA
+//! transfer with a PIT channel.
+//!
 //! # Example
 //!
 //! Note that these examples do not demonstrate how to configure the PIT
@@ -65,6 +70,21 @@ pub type Channels = (Pit0, Pit1, Pit2, Pit3);
 /// A periodic interrupt timer (PIT) channel.
 pub struct Pit<const CHAN: u8> {
     instance: &'static crate::ral::pit::RegisterBlock,
+    mode: Mode,
+}
+
+/// PIT channel operating mode.
+///
+/// See [`Pit::set_mode`] for more information.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The timer restarts from the load value every time it elapses.
+    ///
+    /// This is the hardware's behavior, and it's the default mode.
+    #[default]
+    Repeat,
+    /// The timer is disabled once you clear the elapsed flag.
+    OneShot,
 }
 
 /// Convert the PIT peripheral instances into four timer channels.
@@ -135,9 +155,30 @@ impl<const CHAN: u8> Pit<CHAN> {
         let register_block: &'static _ = unsafe { core::mem::transmute(register_block) };
         Self {
             instance: register_block,
+            mode: Mode::Repeat,
         }
     }
 
+    /// Returns the operating mode.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Set the operating mode.
+    ///
+    /// The hardware always restarts the timer when it elapses. In
+    /// [`OneShot`](Mode::OneShot) mode, [`clear_elapsed()`](Self::clear_elapsed)
+    /// also disables the timer. This means that you don't need to disable a
+    /// one-shot timer in your interrupt handler; clearing the elapsed flag is
+    /// sufficient. Call [`enable()`](Self::enable) to start the next run.
+    ///
+    /// Note that the timer keeps counting from its load value until you clear the
+    /// elapsed flag. If you wait longer than another load value before clearing
+    /// the flag, the timer will have elapsed again.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
     /// Enable (true) or disable (false) interrupt generation.
     pub fn set_interrupt_enable(&mut self, enable: bool) {
         crate::ral::modify_reg!(
@@ -228,7 +269,13 @@ impl<const CHAN: u8> Pit<CHAN> {
     }
 
     /// Clear the elapsed flag.
+    ///
+    /// If the channel is in [`OneShot`](Mode::OneShot) mode, this also
+    /// disables the timer.
     pub fn clear_elapsed(&self) {
+        if self.mode == Mode::OneShot {
+            crate::ral::modify_reg!(crate::ral::pit::timer, &self.instance.TIMER[CHAN as usize], TCTRL, TEN: 0);
+        }
         crate::ral::write_reg!(crate::ral::pit::timer, &self.instance.TIMER[CHAN as usize], TFLG, TIF: 1)
     }
 
@@ -347,6 +394,9 @@ fn chain<const L: u8, const H: u8>(mut low: Pit<L>, mut high: Pit<H>) -> Chained
     low.set_chained(false);
     low.set_interrupt_enable(false);
     high.set_chained(true);
+
+    low.set_mode(Mode::Repeat);
+    high.set_mode(Mode::Repeat);
     Chained { low, high }
 }
 
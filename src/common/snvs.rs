@@ -4,12 +4,28 @@
 //! supports
 //!
 //! - the secure real-time counter (SRTC) in the low-power (LP) domain.
+//! - external tamper detection in the low-power domain. See [`tamper`].
+//! - general purpose registers in the low-power domain. See [`gpr`].
+//! - queries and transitions of the system security monitor (SSM) state
+//!   in the high-power (HP) domain. See [`security_state`].
 
+pub mod gpr;
 mod ral;
 pub mod srtc;
+pub mod tamper;
 
+pub use ral::hp::Core as HpCore;
 pub use ral::lp::Core as LpCore;
 
+use crate::ral::{modify_reg, read_reg};
+
+/// SNVS high-power domain.
+#[non_exhaustive]
+pub struct HighPower {
+    /// Core registers.
+    pub core: ral::hp::Core,
+}
+
 /// SNVS low-power domain.
 #[non_exhaustive]
 pub struct LowPower {
@@ -20,6 +36,8 @@ pub struct LowPower {
     pub core: ral::lp::Core,
     /// Secure real-time counter.
     pub srtc: srtc::Disabled,
+    /// General purpose registers.
+    pub gpr: gpr::Gpr,
 }
 
 /// SNVS components.
@@ -27,6 +45,8 @@ pub struct LowPower {
 /// Use [`new`](crate::snvs::new) to create the components.
 #[non_exhaustive]
 pub struct Snvs {
+    /// Components for the high power domain.
+    pub high_power: HighPower,
     /// Components for the low power domain.
     pub low_power: LowPower,
 }
@@ -35,9 +55,87 @@ pub struct Snvs {
 pub fn new(snvs: crate::ral::snvs::SNVS) -> Snvs {
     let components = ral::new(snvs);
     Snvs {
+        high_power: HighPower {
+            core: components.hp_core,
+        },
         low_power: LowPower {
             core: components.lp_core,
             srtc: srtc::Disabled::new(components.lp_srtc),
+            gpr: gpr::Gpr::new(components.lp_gpr),
         },
     }
 }
+
+/// The state of the system security monitor (SSM).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SecurityState {
+    /// Initializing after power-on.
+    Init = 0b0000,
+    /// A fatal security violation occurred.
+    HardFail = 0b0001,
+    /// A non-fatal security violation occurred.
+    SoftFail = 0b0011,
+    /// Intermediate state between init and check.
+    InitIntermediate = 0b1000,
+    /// Waiting for the boot ROM to authenticate software.
+    Check = 0b1001,
+    /// Running software that isn't authenticated.
+    NonSecure = 0b1011,
+    /// Running authenticated software.
+    Trusted = 0b1101,
+    /// Running authenticated software, in the
+    /// most secure state.
+    Secure = 0b1111,
+}
+
+impl SecurityState {
+    fn from_bits(bits: u32) -> Option<Self> {
+        use SecurityState::*;
+        Some(match bits {
+            0b0000 => Init,
+            0b0001 => HardFail,
+            0b0011 => SoftFail,
+            0b1000 => InitIntermediate,
+            0b1001 => Check,
+            0b1011 => NonSecure,
+            0b1101 => Trusted,
+            0b1111 => Secure,
+            _ => return None,
+        })
+    }
+}
+
+/// Returns the current state of the system security monitor.
+///
+/// Returns `None` if the hardware reports a reserved state.
+pub fn security_state(core: &HpCore) -> Option<SecurityState> {
+    SecurityState::from_bits(read_reg!(ral::hp::core, core, HPSR, SSM_STATE))
+}
+
+/// Request a transition of the system security monitor state.
+///
+/// The hardware only supports these transitions:
+///
+/// - [`Check`](SecurityState::Check) to [`NonSecure`](SecurityState::NonSecure),
+///   when the system isn't configured for secure boot.
+/// - [`Check`](SecurityState::Check) to [`Trusted`](SecurityState::Trusted),
+///   when the system is configured for secure boot.
+/// - [`Trusted`](SecurityState::Trusted) to [`Secure`](SecurityState::Secure),
+///   and back.
+/// - [`SoftFail`](SecurityState::SoftFail) to [`NonSecure`](SecurityState::NonSecure),
+///   unless the transition is disabled.
+///
+/// The request is ignored in any other state. Use [`security_state`]
+/// to learn the new state.
+pub fn transition_security_state(core: &mut HpCore) {
+    modify_reg!(ral::hp::core, core, HPCOMR, SSM_ST: 1);
+}
+
+/// Signal a non-fatal software security violation.
+///
+/// Depending on the security violation policy, this moves the system
+/// security monitor into the [`SoftFail`](SecurityState::SoftFail) state.
+pub fn raise_security_violation(core: &mut HpCore) {
+    modify_reg!(ral::hp::core, core, HPCOMR, SW_SV: 1);
+}
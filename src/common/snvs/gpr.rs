@@ -0,0 +1,69 @@
+//! General purpose registers.
+//!
+//! The SNVS low-power domain has a few general purpose registers. Their
+//! values survive warm resets, including watchdog resets, as long as the
+//! low-power domain has power. Use them to pass a few words of state
+//! across a reboot.
+//!
+//! The number of registers depends on the chip. Use [`Gpr::len`] to
+//! learn how many registers are available.
+//!
+//! # Example
+//!
+//! Count crashes across watchdog resets.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! let hal::snvs::Snvs { low_power: hal::snvs::LowPower {
+//!     mut gpr, ..
+//! }, .. } = hal::snvs::new(unsafe { ral::snvs::SNVS::instance() });
+//!
+//! const CRASH_COUNT: usize = 0;
+//! let crashes = gpr.read(CRASH_COUNT);
+//! gpr.write(CRASH_COUNT, crashes.wrapping_add(1));
+//! ```
+
+use super::ral::lp;
+
+/// SNVS general purpose registers.
+pub struct Gpr {
+    gpr: lp::Gpr,
+}
+
+impl Gpr {
+    pub(super) fn new(gpr: lp::Gpr) -> Self {
+        Self { gpr }
+    }
+
+    /// Returns the number of general purpose registers.
+    pub fn len(&self) -> usize {
+        self.gpr.len()
+    }
+
+    /// Returns `true` if there are no general purpose registers.
+    ///
+    /// This is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.gpr.is_empty()
+    }
+
+    /// Read the general purpose register at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than or equal to [`len`](Self::len).
+    pub fn read(&self, index: usize) -> u32 {
+        self.gpr[index].read()
+    }
+
+    /// Write `value` to the general purpose register at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than or equal to [`len`](Self::len).
+    pub fn write(&mut self, index: usize, value: u32) {
+        self.gpr[index].write(value);
+    }
+}
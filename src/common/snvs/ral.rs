@@ -5,6 +5,58 @@
 
 #![allow(unused, non_snake_case, non_upper_case_globals)] // Compatibility with RAL
 
+/// High power domain.
+pub mod hp {
+    /// Core SNVS registers.
+    pub mod core {
+        use crate::ral::RWRegister;
+
+        /// Taken from the imxrt-ral definitions.
+        #[repr(C)]
+        pub struct RegisterBlock {
+            /// SNVS_HP Lock Register
+            pub HPLR: RWRegister<u32>,
+
+            /// SNVS_HP Command Register
+            pub HPCOMR: RWRegister<u32>,
+
+            /// SNVS_HP Control Register
+            pub HPCR: RWRegister<u32>,
+
+            /// SNVS_HP Security Interrupt Control Register
+            pub HPSICR: RWRegister<u32>,
+
+            /// SNVS_HP Security Violation Control Register
+            pub HPSVCR: RWRegister<u32>,
+
+            /// SNVS_HP Status Register
+            pub HPSR: RWRegister<u32>,
+
+            /// SNVS_HP Security Violation Status Register
+            pub HPSVSR: RWRegister<u32>,
+        }
+
+        pub mod HPLR {
+            pub use crate::ral::snvs::HPLR::*;
+        }
+
+        pub mod HPCOMR {
+            pub use crate::ral::snvs::HPCOMR::*;
+        }
+
+        pub mod HPSR {
+            pub use crate::ral::snvs::HPSR::*;
+        }
+
+        pub mod HPSVSR {
+            pub use crate::ral::snvs::HPSVSR::*;
+        }
+    }
+
+    /// The core SNVS registers.
+    pub type Core = super::Instance<core::RegisterBlock>;
+}
+
 /// Low power domain.
 pub mod lp {
     /// Core SNVS registers.
@@ -43,6 +95,10 @@ pub mod lp {
             pub use crate::ral::snvs::LPCR::*;
         }
 
+        pub mod LPTDCR {
+            pub use crate::ral::snvs::LPTDCR::*;
+        }
+
         pub mod LPSR {
             pub use crate::ral::snvs::LPSR::*;
         }
@@ -74,13 +130,17 @@ pub mod lp {
     pub type Core = super::Instance<core::RegisterBlock>;
     /// SRTC-specific registers.
     pub type Srtc = super::Instance<srtc::RegisterBlock>;
+    /// General purpose registers.
+    ///
+    /// The number of registers depends on the chip.
+    pub type Gpr = super::Instance<[crate::ral::RWRegister<u32>]>;
 }
 
-pub struct Instance<RB>(*const RB);
+pub struct Instance<RB: ?Sized>(*const RB);
 // Safety: OK to send, since pointer points to static peripheral memory.
-unsafe impl<RB> Send for Instance<RB> {}
+unsafe impl<RB: ?Sized> Send for Instance<RB> {}
 
-impl<RB> core::ops::Deref for Instance<RB> {
+impl<RB: ?Sized> core::ops::Deref for Instance<RB> {
     type Target = RB;
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -92,14 +152,18 @@ impl<RB> core::ops::Deref for Instance<RB> {
 
 /// RAL components.
 pub(super) struct Components {
+    pub(super) hp_core: hp::Core,
     pub(super) lp_core: lp::Core,
     pub(super) lp_srtc: lp::Srtc,
+    pub(super) lp_gpr: lp::Gpr,
 }
 
 /// Create SNVS components from the RAL instance.
 pub(super) fn new(snvs: crate::ral::snvs::SNVS) -> Components {
     Components {
+        hp_core: Instance(core::ptr::addr_of!(snvs.HPLR) as *const _),
         lp_core: Instance(core::ptr::addr_of!(snvs.LPLR) as *const _),
         lp_srtc: Instance(core::ptr::addr_of!(snvs.LPSRTCMR) as *const _),
+        lp_gpr: Instance(core::ptr::addr_of!(snvs.LPGPR) as *const [_]),
     }
 }
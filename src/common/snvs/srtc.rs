@@ -1,7 +1,7 @@
 //! Secure real-time clock.
 //!
-//! Basic support only: Supports enabling and setting the clock, but not any of its more
-//! advanced features (alarm, calibration, stop on security violation, etc).
+//! Supports enabling, setting, and calibrating the clock, but not any of its more
+//! advanced features (alarm, stop on security violation, etc).
 //!
 //! The Secure Real-Time Clock continues tracking time until it is specifically disabled or loses
 //! power, even through a system reboot (and potentially a loss of power to the system, if the
@@ -24,6 +24,26 @@
 //! let now = srtc.get();
 //! # Some(())}();
 //! ```
+//!
+//! # Civil time
+//!
+//! When the SRTC counts seconds since the Unix epoch, use [`Srtc::now_civil`] to read the
+//! time as a UTC date and time, and [`Srtc::set_from_civil`] to set the time from a date and
+//! time. This works without any calendar crate. [`CivilTime`] converts to and from Unix
+//! seconds, so you can also use it with [`Srtc::get`] and [`Srtc::set`].
+//!
+//! # Calibration
+//!
+//! A 32.768kHz crystal's frequency error depends on the crystal and on its
+//! temperature. Use [`Srtc::set_calibration`] to add or remove up to 16 counts
+//! every second, about 30.5ppm per count. [`calibration_from_ppm`] converts a
+//! correction in parts per million into calibration counts.
+//!
+//! To compensate a crystal over temperature, describe its correction with a
+//! table of `(temperature, ppm)` points, and use [`compensation_ppm`] to look
+//! up the correction for the current temperature. On chips with a temperature
+//! monitor, `Srtc::auto_calibrate` measures the die temperature and applies the
+//! correction in one call; call it periodically to track the temperature.
 
 use core::fmt;
 
@@ -218,9 +238,365 @@ impl Srtc {
     }
 }
 
+impl Srtc {
+    /// Set the SRTC calibration, in counts per second.
+    ///
+    /// The SRTC adds `counts` to its counter every 32768 ticks. A positive
+    /// value speeds up the clock, and a negative value slows it down. Zero
+    /// disables calibration. `counts` is clamped to
+    /// [`CALIBRATION_MIN`]..=[`CALIBRATION_MAX`].
+    ///
+    /// If the calibration is locked, this has no effect.
+    pub fn set_calibration(&mut self, core: &mut Core, counts: i8) {
+        let counts = counts.clamp(CALIBRATION_MIN, CALIBRATION_MAX);
+        // The value can only change while calibration is disabled.
+        crate::ral::modify_reg!(super::ral::lp::core, core, LPCR, LPCALB_EN: 0);
+        crate::ral::modify_reg!(super::ral::lp::core, core, LPCR, LPCALB_VAL: counts as u32 & 0x1F);
+        if counts != 0 {
+            crate::ral::modify_reg!(super::ral::lp::core, core, LPCR, LPCALB_EN: 1);
+        }
+    }
+
+    /// Returns the SRTC calibration, in counts per second.
+    ///
+    /// Returns zero if calibration is disabled.
+    pub fn calibration(&self, core: &Core) -> i8 {
+        let (enabled, value) =
+            crate::ral::read_reg!(super::ral::lp::core, core, LPCR, LPCALB_EN, LPCALB_VAL);
+        if enabled != 0 {
+            // Sign-extend the 5-bit value.
+            ((value as i8) << 3) >> 3
+        } else {
+            0
+        }
+    }
+}
+
+/// The smallest SRTC calibration, in counts per second.
+pub const CALIBRATION_MIN: i8 = -16;
+/// The largest SRTC calibration, in counts per second.
+pub const CALIBRATION_MAX: i8 = 15;
+
+/// Convert a correction, in parts per million, into SRTC calibration counts.
+///
+/// Each count is 1/32768 of a second per second, about 30.5ppm. The result
+/// rounds to the nearest count, and it's clamped to
+/// [`CALIBRATION_MIN`]..=[`CALIBRATION_MAX`].
+pub const fn calibration_from_ppm(ppm: i32) -> i8 {
+    let scaled = ppm as i64 * 32768;
+    let counts = if scaled < 0 {
+        (scaled - 500_000) / 1_000_000
+    } else {
+        (scaled + 500_000) / 1_000_000
+    };
+    if counts < CALIBRATION_MIN as i64 {
+        CALIBRATION_MIN
+    } else if counts > CALIBRATION_MAX as i64 {
+        CALIBRATION_MAX
+    } else {
+        counts as i8
+    }
+}
+
+/// Look up the correction, in parts per million, for `temperature`.
+///
+/// `table` is a list of `(temperature, ppm)` points, sorted by increasing
+/// temperature. Use the same temperature units as your sensor; the
+/// temperature monitor uses millidegrees Celsius. A positive
+/// correction speeds up the clock.
+///
+/// Between two points, the correction is linearly interpolated. Below the first
+/// point, or above the last point, the correction is the correction of the
+/// nearest point. Returns zero if the table is empty.
+pub fn compensation_ppm(temperature: i32, table: &[(i32, i16)]) -> i16 {
+    let Some(&(first_temp, first_ppm)) = table.first() else {
+        return 0;
+    };
+    if temperature <= first_temp {
+        return first_ppm;
+    }
+    for window in table.windows(2) {
+        let [(low_temp, low_ppm), (high_temp, high_ppm)] = [window[0], window[1]];
+        if temperature <= high_temp {
+            let span = i64::from(high_temp) - i64::from(low_temp);
+            if span <= 0 {
+                return high_ppm;
+            }
+            let offset = i64::from(temperature) - i64::from(low_temp);
+            let delta = i64::from(high_ppm) - i64::from(low_ppm);
+            return (i64::from(low_ppm) + delta * offset / span) as i16;
+        }
+    }
+    table[table.len() - 1].1
+}
+
+impl Srtc {
+    /// Get the current time as a UTC date and time.
+    ///
+    /// This assumes that the SRTC counts seconds since the Unix epoch.
+    pub fn now_civil(&self) -> CivilTime {
+        CivilTime::from_unix(self.get())
+    }
+
+    /// Set the current time from a UTC date and time.
+    ///
+    /// The SRTC then counts seconds since the Unix epoch. This clears
+    /// the sub-second ticks. Returns an error, without changing the SRTC,
+    /// if `time` isn't valid, or if it can't be represented in 32-bit Unix
+    /// seconds.
+    pub fn set_from_civil(
+        &mut self,
+        core: &mut Core,
+        time: &CivilTime,
+    ) -> Result<(), CivilTimeError> {
+        let seconds = time.to_unix()?;
+        self.set(core, seconds, 0);
+        Ok(())
+    }
+}
+
+/// A UTC date and time.
+///
+/// `CivilTime` uses the proleptic Gregorian calendar, and it ignores leap seconds.
+/// It represents times from 1970-01-01 00:00:00 through 2106-02-07 06:28:15, the
+/// range of 32-bit Unix seconds.
+///
+/// ```
+/// use imxrt_hal::snvs::srtc::CivilTime;
+///
+/// let time = CivilTime::from_unix(1_600_000_000);
+/// assert_eq!(
+///     time,
+///     CivilTime { year: 2020, month: 9, day: 13, hour: 12, minute: 26, second: 40 }
+/// );
+/// assert_eq!(time.to_unix(), Ok(1_600_000_000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CivilTime {
+    /// The year, like 2024.
+    pub year: u16,
+    /// The month, 1 through 12.
+    pub month: u8,
+    /// The day of the month, starting at 1.
+    pub day: u8,
+    /// The hour, 0 through 23.
+    pub hour: u8,
+    /// The minute, 0 through 59.
+    pub minute: u8,
+    /// The second, 0 through 59.
+    pub second: u8,
+}
+
+/// An error when converting a [`CivilTime`] to Unix seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CivilTimeError {
+    /// A field is out of range, like month 13, or February 30.
+    Invalid,
+    /// The time is valid, but it's outside the range of 32-bit Unix seconds.
+    OutOfRange,
+}
+
+/// Days between 0000-03-01 and 1970-01-01.
+const UNIX_EPOCH_DAYS: u64 = 719_468;
+/// Days in a 400 year era.
+const DAYS_PER_ERA: u64 = 146_097;
+const SECONDS_PER_DAY: u32 = 86_400;
+
+/// Returns `true` if `year` is a leap year.
+const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// Returns the number of days in the month.
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl CivilTime {
+    /// Convert seconds since the Unix epoch into a date and time.
+    pub const fn from_unix(seconds: u32) -> Self {
+        let days = (seconds / SECONDS_PER_DAY) as u64;
+        let secs = seconds % SECONDS_PER_DAY;
+
+        // Shift the epoch to 0000-03-01, so that leap days end each year.
+        let days = days + UNIX_EPOCH_DAYS;
+        let era = days / DAYS_PER_ERA;
+        let day_of_era = days - era * DAYS_PER_ERA;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + (month <= 2) as u64;
+
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+        }
+    }
+
+    /// Returns `true` if every field is in range.
+    pub const fn is_valid(&self) -> bool {
+        self.month >= 1
+            && self.month <= 12
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Convert the date and time into seconds since the Unix epoch.
+    pub const fn to_unix(&self) -> Result<u32, CivilTimeError> {
+        if !self.is_valid() {
+            return Err(CivilTimeError::Invalid);
+        }
+        if self.year < 1970 {
+            return Err(CivilTimeError::OutOfRange);
+        }
+
+        let year = self.year as u64 - (self.month <= 2) as u64;
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let shifted_month = if self.month > 2 {
+            self.month - 3
+        } else {
+            self.month + 9
+        } as u64;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as u64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * DAYS_PER_ERA + day_of_era - UNIX_EPOCH_DAYS;
+
+        let seconds = days * SECONDS_PER_DAY as u64
+            + self.hour as u64 * 3600
+            + self.minute as u64 * 60
+            + self.second as u64;
+        if seconds > u32::MAX as u64 {
+            Err(CivilTimeError::OutOfRange)
+        } else {
+            Ok(seconds as u32)
+        }
+    }
+}
+
+impl fmt::Display for CivilTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
 impl fmt::Debug for Srtc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SRTC").finish()
         // very basic, just to prevent compile errors if user puts it in a struct
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{calibration_from_ppm, compensation_ppm, CivilTime, CivilTimeError};
+
+    #[test]
+    fn calibration_counts() {
+        assert_eq!(calibration_from_ppm(0), 0);
+        assert_eq!(calibration_from_ppm(15), 0);
+        assert_eq!(calibration_from_ppm(16), 1);
+        assert_eq!(calibration_from_ppm(31), 1);
+        assert_eq!(calibration_from_ppm(-31), -1);
+        assert_eq!(calibration_from_ppm(-61), -2);
+        assert_eq!(calibration_from_ppm(458), 15);
+        assert_eq!(calibration_from_ppm(1_000), 15);
+        assert_eq!(calibration_from_ppm(-1_000), -16);
+        assert_eq!(calibration_from_ppm(i32::MIN), -16);
+    }
+
+    #[test]
+    fn compensation_table() {
+        // A parabolic crystal, turning at 25C.
+        const TABLE: &[(i32, i16)] = &[(-20_000, 69), (0, 21), (25_000, 0), (50_000, 21)];
+        assert_eq!(compensation_ppm(-40_000, TABLE), 69);
+        assert_eq!(compensation_ppm(-20_000, TABLE), 69);
+        assert_eq!(compensation_ppm(-10_000, TABLE), 45);
+        assert_eq!(compensation_ppm(25_000, TABLE), 0);
+        assert_eq!(compensation_ppm(37_500, TABLE), 10);
+        assert_eq!(compensation_ppm(85_000, TABLE), 21);
+        assert_eq!(compensation_ppm(25_000, &[]), 0);
+        assert_eq!(compensation_ppm(0, &[(0, 5), (0, 7)]), 5);
+    }
+
+    const fn civil(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> CivilTime {
+        CivilTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    #[test]
+    fn civil_time_from_unix() {
+        assert_eq!(CivilTime::from_unix(0), civil(1970, 1, 1, 0, 0, 0));
+        assert_eq!(
+            CivilTime::from_unix(951_782_400),
+            civil(2000, 2, 29, 0, 0, 0)
+        );
+        assert_eq!(
+            CivilTime::from_unix(1_709_251_199),
+            civil(2024, 2, 29, 23, 59, 59)
+        );
+        assert_eq!(CivilTime::from_unix(u32::MAX), civil(2106, 2, 7, 6, 28, 15));
+    }
+
+    #[test]
+    fn civil_time_to_unix() {
+        for seconds in [0, 951_782_400, 1_600_000_000, 1_709_251_199, u32::MAX] {
+            assert_eq!(CivilTime::from_unix(seconds).to_unix(), Ok(seconds));
+        }
+        // Walk across days, months, and years.
+        for seconds in (0..u32::MAX - 86_399).step_by(86_399 * 17) {
+            assert_eq!(CivilTime::from_unix(seconds).to_unix(), Ok(seconds));
+        }
+
+        assert_eq!(
+            civil(2021, 2, 29, 0, 0, 0).to_unix(),
+            Err(CivilTimeError::Invalid)
+        );
+        assert_eq!(
+            civil(2021, 13, 1, 0, 0, 0).to_unix(),
+            Err(CivilTimeError::Invalid)
+        );
+        assert_eq!(
+            civil(2021, 1, 1, 24, 0, 0).to_unix(),
+            Err(CivilTimeError::Invalid)
+        );
+        assert_eq!(
+            civil(1969, 12, 31, 23, 59, 59).to_unix(),
+            Err(CivilTimeError::OutOfRange)
+        );
+        assert_eq!(
+            civil(2106, 2, 7, 6, 28, 16).to_unix(),
+            Err(CivilTimeError::OutOfRange)
+        );
+    }
+}
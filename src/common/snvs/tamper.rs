@@ -0,0 +1,123 @@
+//! External tamper detection.
+//!
+//! The SNVS low-power domain watches external tamper inputs. When an
+//! enabled input reaches its active level, the SNVS records the
+//! detection, and signals a low-power security violation. The
+//! detection persists across resets, as long as the low-power domain
+//! has power. Clear the detection once you've handled it.
+//!
+//! # Example
+//!
+//! Enable the external tamper input, active high. Later, check if the
+//! input detected tampering.
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use imxrt_ral as ral;
+//!
+//! use hal::snvs::tamper::{self, Input, Polarity, TamperConfig};
+//!
+//! let hal::snvs::Snvs { low_power: hal::snvs::LowPower {
+//!     mut core, ..
+//! }, .. } = hal::snvs::new(unsafe { ral::snvs::SNVS::instance() });
+//!
+//! tamper::configure(&mut core, Input::External1, TamperConfig {
+//!     enable: true,
+//!     polarity: Polarity::ActiveHigh,
+//! });
+//!
+//! if tamper::is_detected(&core, Input::External1) {
+//!     // Handle the tamper event...
+//!     tamper::clear_detected(&mut core, Input::External1);
+//! }
+//! ```
+
+use super::ral::lp::{self, Core};
+use crate::ral::{modify_reg, read_reg, write_reg};
+
+/// An external tamper input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Input {
+    /// External tamper input 1.
+    External1,
+    /// External tamper input 2.
+    #[cfg(family = "imxrt11xx")]
+    External2,
+}
+
+/// The active level of an external tamper input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Polarity {
+    /// Tampering is detected when the input is low.
+    #[default]
+    ActiveLow,
+    /// Tampering is detected when the input is high.
+    ActiveHigh,
+}
+
+/// External tamper input configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TamperConfig {
+    /// Enable tamper detection on this input.
+    pub enable: bool,
+    /// The input's active level.
+    pub polarity: Polarity,
+}
+
+/// Configure an external tamper input.
+///
+/// Set the polarity before, or at the same time as, you enable the
+/// input. Otherwise, the detector may observe tampering.
+pub fn configure(core: &mut Core, input: Input, config: TamperConfig) {
+    let enable = config.enable as u32;
+    let polarity = (config.polarity == Polarity::ActiveHigh) as u32;
+    match input {
+        Input::External1 => {
+            modify_reg!(lp::core, core, LPTDCR, ET1_EN: enable, ET1P: polarity)
+        }
+        #[cfg(family = "imxrt11xx")]
+        Input::External2 => {
+            modify_reg!(lp::core, core, LPTDCR, ET2_EN: enable, ET2P: polarity)
+        }
+    }
+}
+
+/// Returns the input's current configuration.
+pub fn configuration(core: &Core, input: Input) -> TamperConfig {
+    let (enable, polarity) = match input {
+        Input::External1 => read_reg!(lp::core, core, LPTDCR, ET1_EN, ET1P),
+        #[cfg(family = "imxrt11xx")]
+        Input::External2 => read_reg!(lp::core, core, LPTDCR, ET2_EN, ET2P),
+    };
+    TamperConfig {
+        enable: enable != 0,
+        polarity: if polarity != 0 {
+            Polarity::ActiveHigh
+        } else {
+            Polarity::ActiveLow
+        },
+    }
+}
+
+/// Returns `true` if the input detected tampering.
+pub fn is_detected(core: &Core, input: Input) -> bool {
+    match input {
+        Input::External1 => read_reg!(lp::core, core, LPSR, ET1D == 1),
+        #[cfg(family = "imxrt11xx")]
+        Input::External2 => read_reg!(lp::core, core, LPSR, ET2D == 1),
+    }
+}
+
+/// Clear the input's tamper detection.
+///
+/// The detection won't clear while the input is still at its active
+/// level.
+pub fn clear_detected(core: &mut Core, input: Input) {
+    // All status flags are W1C; only write the flag we want to clear.
+    match input {
+        Input::External1 => write_reg!(lp::core, core, LPSR, ET1D: 1),
+        #[cfg(family = "imxrt11xx")]
+        Input::External2 => write_reg!(lp::core, core, LPSR, ET2D: 1),
+    }
+}
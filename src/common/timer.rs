@@ -1,9 +1,9 @@
 //! General timer APIs.
 //!
 //! `timer` provides adapters for implementing (non-)blocking
-//! timers. It builds upon hardware drivers like [`gpt`](crate::gpt)
-//! and [`pit`](crate::pit), and implements various `embedded-hal`
-//! timer traits.
+//! timers. It builds upon hardware drivers like [`gpt`](crate::gpt),
+//! [`pit`](crate::pit), and [`flexpwm`](crate::flexpwm), and implements
+//! various `embedded-hal` timer traits.
 //!
 //! Use [`Blocking`] to adapt a timer for simple, blocking delays. Blocking
 //! delays use durations, not raw clock ticks; this means that you need to
@@ -13,6 +13,8 @@
 //! tracking. This also uses time duration, not raw clock ticks, for
 //! timing, so it again needs the clock frequency. Use [`RawCountDown`]
 //! if you want to track time in clock ticks.
+//! [`DurationPit`] also implements `CountDown`, using `core::time::Duration`
+//! for its timeout.
 //!
 //! # Raw count representations
 //!
@@ -24,6 +26,10 @@
 //! | PIT channel | 32        |
 //! | PIT chain   | 64        |
 //! | GPT         | 32        |
+//! | PWM         | 16        |
+//!
+//! A PWM submodule counts at most 65536 ticks. Adapters accept `u32` ticks
+//! for PWM submodules, but they saturate larger tick counts.
 //!
 //! # Computing the clock frequency
 //!
@@ -41,11 +47,11 @@
 //! possible drivers. You're encouraged to adapt [`RawCountDown`] to implement a
 //! `CountDown` implementation for your specific driver.
 
-use crate::{gpt, pit};
+use crate::{flexpwm, gpt, pit};
 
 /// An interface for hardware timers.
 ///
-/// This is implemented for various GPT and PIT objects.
+/// This is implemented for various GPT, PIT, and PWM objects.
 /// It is not convered by the semver guarantees of this
 /// crate. Do not use.
 #[doc(hidden)]
@@ -60,6 +66,11 @@ pub trait HardwareTimer {
     fn set_ticks(&mut self, ticks: Self::Ticks);
     /// Enable / disable the timer.
     fn set_enable(&mut self, enable: bool);
+    /// Returns the number of ticks counted since the timer was enabled,
+    /// or since it last elapsed.
+    ///
+    /// Returns zero if the timer is disabled.
+    fn elapsed_ticks(&self) -> Self::Ticks;
 }
 
 impl<const N: u8> HardwareTimer for pit::Pit<N> {
@@ -80,6 +91,15 @@ impl<const N: u8> HardwareTimer for pit::Pit<N> {
             self.disable();
         }
     }
+    fn elapsed_ticks(&self) -> Self::Ticks {
+        // The PIT counts down from the load value.
+        if self.is_enabled() {
+            self.load_timer_value()
+                .saturating_sub(self.current_timer_value())
+        } else {
+            0
+        }
+    }
 }
 
 impl<const L: u8, const R: u8> HardwareTimer for pit::Chained<L, R> {
@@ -100,6 +120,14 @@ impl<const L: u8, const R: u8> HardwareTimer for pit::Chained<L, R> {
             self.disable();
         }
     }
+    fn elapsed_ticks(&self) -> Self::Ticks {
+        if self.is_enabled() {
+            self.load_timer_value()
+                .saturating_sub(self.current_timer_value())
+        } else {
+            0
+        }
+    }
 }
 
 /// The GPT OCR used for timers implementations.
@@ -123,6 +151,57 @@ impl<const N: u8> HardwareTimer for gpt::Gpt<N> {
             self.disable();
         }
     }
+    fn elapsed_ticks(&self) -> Self::Ticks {
+        // The GPT counts up from zero, and it's reset when enabled.
+        if self.is_enabled() {
+            self.count()
+        } else {
+            0
+        }
+    }
+}
+
+impl<const N: u8, const M: u8> HardwareTimer for (flexpwm::Pwm<N>, flexpwm::Submodule<N, M>) {
+    type Ticks = u32;
+    fn is_elapsed(&self) -> bool {
+        self.1.status().contains(flexpwm::Status::COMPARE_VAL1)
+    }
+    fn clear_elapsed(&mut self) {
+        self.1.clear_status(flexpwm::Status::COMPARE_VAL1);
+    }
+    fn set_ticks(&mut self, ticks: Self::Ticks) {
+        let (pwm, sm) = self;
+        sm.clear_load_ok(pwm);
+        sm.set_initial_count(pwm, i16::MIN);
+        sm.set_value(
+            flexpwm::FULL_RELOAD_VALUE_REGISTER,
+            pwm_full_reload_value(ticks),
+        );
+        sm.set_load_ok(pwm);
+        sm.force_initialize();
+    }
+    fn set_enable(&mut self, enable: bool) {
+        let (pwm, sm) = self;
+        sm.set_running(pwm, enable);
+    }
+    fn elapsed_ticks(&self) -> Self::Ticks {
+        // The submodule counts up from the initial count.
+        let (pwm, sm) = self;
+        if sm.is_running(pwm) {
+            u32::from(sm.count().wrapping_sub(sm.initial_count()) as u16)
+        } else {
+            0
+        }
+    }
+}
+
+/// Returns the full reload value that elapses after `ticks`, when
+/// counting from `i16::MIN`.
+///
+/// Saturates `ticks` between 1 and 65536.
+fn pwm_full_reload_value(ticks: u32) -> i16 {
+    let ticks = ticks.clamp(1, 1 << 16);
+    (i32::from(i16::MIN) + ticks as i32 - 1) as i16
 }
 
 /// Extensions for `fugit::TimerDuration`.
@@ -255,6 +334,15 @@ where
         self.timer
     }
 
+    /// Returns the number of ticks counted by the underlying timer.
+    ///
+    /// Since [`block`](Self::block) disables the timer when the delay
+    /// completes, this returns zero between blocking calls. If you'd like
+    /// to measure elapsed time, use a [`Stopwatch`].
+    pub fn elapsed_ticks(&self) -> T::Ticks {
+        self.timer.elapsed_ticks()
+    }
+
     /// Occupy the CPU, blocking execution, for a `duration` represented
     /// by the target clock.
     ///
@@ -347,6 +435,7 @@ where
 /// Prepares a PIT channel to be adapted by blocking / count down
 /// adapters.
 fn prepare_pit<const N: u8>(pit: &mut pit::Pit<N>) {
+    pit.set_mode(pit::Mode::Repeat);
     pit.disable();
     pit.clear_elapsed();
     pit.set_chained(false);
@@ -380,6 +469,19 @@ fn prepare_gpt<const N: u8>(gpt: &mut gpt::Gpt<N>) {
     }
 }
 
+/// Prepares a PWM submodule to be adapted by blocking / count down adapters.
+fn prepare_pwm<const N: u8, const M: u8>(
+    pwm: &mut flexpwm::Pwm<N>,
+    sm: &mut flexpwm::Submodule<N, M>,
+) {
+    sm.set_running(pwm, false);
+    sm.clear_load_ok(pwm);
+    sm.set_interrupts(flexpwm::Interrupts::empty());
+    sm.clear_status(flexpwm::Status::all());
+    // Load buffered values as soon as we set LDOK.
+    sm.set_load_mode(flexpwm::LoadMode::Immediate);
+}
+
 /// A single PIT channel that acts as a blocking timer.
 pub type BlockingPit<const N: u8, const HZ: u32> = Blocking<pit::Pit<N>, HZ>;
 
@@ -431,6 +533,24 @@ impl<const N: u8, const HZ: u32> BlockingGpt<N, HZ> {
     }
 }
 
+/// A PWM submodule that acts as a blocking timer.
+///
+/// The adapter takes the PWM module, since it needs to run the submodule.
+/// `HZ` is the submodule's counting frequency, after the prescaler.
+pub type BlockingPwm<const N: u8, const M: u8, const HZ: u32> =
+    Blocking<(flexpwm::Pwm<N>, flexpwm::Submodule<N, M>), HZ>;
+
+impl<const N: u8, const M: u8, const HZ: u32> BlockingPwm<N, M, HZ> {
+    /// Create a blocking adapter from a PWM submodule.
+    ///
+    /// This overwrites the submodule's initial count, full reload value,
+    /// load mode, and interrupts. Don't use the submodule's outputs.
+    pub fn from_pwm(mut pwm: flexpwm::Pwm<N>, mut submodule: flexpwm::Submodule<N, M>) -> Self {
+        prepare_pwm(&mut pwm, &mut submodule);
+        Self::new((pwm, submodule))
+    }
+}
+
 impl<R, T, const HZ: u32> eh02::blocking::delay::DelayMs<R> for Blocking<T, HZ>
 where
     R: Into<T::Ticks>,
@@ -455,6 +575,257 @@ where
     }
 }
 
+/// A PIT channel that measures elapsed time.
+///
+/// The stopwatch lets the PIT channel free-run, and it reports the
+/// time between [`lap`](Self::lap) calls. Unlike a cycle counter,
+/// the stopwatch doesn't require the debug unit. You're responsible
+/// for specifying the PIT clock frequency `HZ`.
+///
+/// The stopwatch handles a counter wraparound between two laps.
+/// However, if the time between two laps exceeds the counter's
+/// period (`u32::MAX` ticks), the lap duration is incorrect.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// # const PIT_FREQUENCY_HZ: u32 = 1_000_000;
+/// let pit = unsafe { ral::pit::PIT::instance() };
+/// let (pit0, _, _, _) = hal::pit::new(pit);
+///
+/// let mut stopwatch = hal::timer::Stopwatch::<0, PIT_FREQUENCY_HZ>::from_pit(pit0);
+/// stopwatch.start();
+/// // Do some work...
+/// let duration: core::time::Duration = stopwatch.lap();
+/// ```
+pub struct Stopwatch<const N: u8, const HZ: u32> {
+    pit: pit::Pit<N>,
+    previous: u32,
+}
+
+impl<const N: u8, const HZ: u32> Stopwatch<N, HZ> {
+    /// The frequency of the PIT clock, in Hz.
+    pub const FREQUENCY: u32 = HZ;
+
+    /// Create a stopwatch from a PIT channel.
+    ///
+    /// The stopwatch is stopped. Use [`start`](Self::start) to
+    /// start measuring time.
+    pub fn from_pit(mut pit: pit::Pit<N>) -> Self {
+        prepare_pit(&mut pit);
+        Self { pit, previous: 0 }
+    }
+
+    /// Release the underlying PIT channel.
+    ///
+    /// The released timer's state is unspecified.
+    pub fn release(self) -> pit::Pit<N> {
+        self.pit
+    }
+
+    /// Start, or restart, the stopwatch.
+    pub fn start(&mut self) {
+        self.pit.disable();
+        self.pit.clear_elapsed();
+        self.pit.set_load_timer_value(u32::MAX);
+        self.pit.enable();
+        self.previous = 0;
+    }
+
+    /// Stop the stopwatch.
+    ///
+    /// Does nothing if the stopwatch is already stopped.
+    pub fn stop(&mut self) {
+        self.pit.disable();
+    }
+
+    /// Returns the ticks counted since the stopwatch started, or
+    /// since the counter last wrapped around.
+    ///
+    /// Returns zero if the stopwatch is stopped.
+    pub fn elapsed_ticks(&self) -> u32 {
+        HardwareTimer::elapsed_ticks(&self.pit)
+    }
+
+    /// Returns the time since the previous lap.
+    ///
+    /// If there was no previous lap, this returns the time since
+    /// [`start`](Self::start).
+    pub fn lap(&mut self) -> core::time::Duration {
+        let now = self.elapsed_ticks();
+        let period = u64::from(self.pit.load_timer_value()) + 1;
+        let ticks = lap_ticks(self.previous, now, period);
+        self.previous = now;
+        ticks_to_duration(ticks, HZ)
+    }
+}
+
+/// Compute the number of ticks between two counter values, given that
+/// the counter wraps around to zero after `period` ticks.
+const fn lap_ticks(previous: u32, now: u32, period: u64) -> u64 {
+    (now as u64 + period - previous as u64) % period
+}
+
+/// Convert `ticks` of a `hz` clock into a duration.
+fn ticks_to_duration(ticks: u64, hz: u32) -> core::time::Duration {
+    let hz = u64::from(hz);
+    let secs = ticks / hz;
+    let nanos = (ticks % hz) * 1_000_000_000 / hz;
+    core::time::Duration::new(secs, nanos as u32)
+}
+
+/// A PIT channel that's configured with durations.
+///
+/// `DurationPit` converts durations into PIT clock ticks, so you
+/// don't need to compute the ticks yourself. You're responsible for
+/// specifying the PIT clock frequency `HZ`. Durations that are too long
+/// for the PIT channel saturate at the longest possible period.
+///
+/// Use [`pit`](Self::pit) and [`pit_mut`](Self::pit_mut) to access
+/// the PIT channel for everything else, like enabling the timer,
+/// enabling interrupts, and checking the elapsed flag.
+///
+/// # Example
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use core::time::Duration;
+///
+/// # const PIT_FREQUENCY_HZ: u32 = 1_000_000;
+/// let pit = unsafe { ral::pit::PIT::instance() };
+/// let (pit0, _, _, _) = hal::pit::new(pit);
+///
+/// let mut pit0 = hal::timer::DurationPit::<0, PIT_FREQUENCY_HZ>::from_pit(pit0);
+/// pit0.set_period(Duration::from_millis(250));
+/// pit0.pit_mut().enable();
+///
+/// loop {
+///     while !pit0.pit().is_elapsed() {}
+///     pit0.pit().clear_elapsed();
+///     // Runs every 250ms...
+/// }
+/// ```
+pub struct DurationPit<const N: u8, const HZ: u32> {
+    pit: pit::Pit<N>,
+}
+
+impl<const N: u8, const HZ: u32> DurationPit<N, HZ> {
+    /// The frequency of the PIT clock, in Hz.
+    pub const FREQUENCY: u32 = HZ;
+
+    /// Create a duration PIT from a PIT channel.
+    ///
+    /// The channel is disabled, and it's in [`Repeat`](pit::Mode::Repeat)
+    /// mode.
+    pub fn from_pit(mut pit: pit::Pit<N>) -> Self {
+        prepare_pit(&mut pit);
+        Self { pit }
+    }
+
+    /// Release the underlying PIT channel.
+    pub fn release(self) -> pit::Pit<N> {
+        self.pit
+    }
+
+    /// Access the PIT channel.
+    pub fn pit(&self) -> &pit::Pit<N> {
+        &self.pit
+    }
+
+    /// Mutably access the PIT channel.
+    pub fn pit_mut(&mut self) -> &mut pit::Pit<N> {
+        &mut self.pit
+    }
+
+    /// Elapse periodically, every `period`.
+    ///
+    /// This puts the channel into [`Repeat`](pit::Mode::Repeat) mode. The
+    /// period takes effect after the current period elapses.
+    pub fn set_period(&mut self, period: core::time::Duration) {
+        self.pit.set_mode(pit::Mode::Repeat);
+        self.pit.set_load_timer_value(duration_to_ticks(period, HZ));
+    }
+
+    /// Elapse once, after `interval`.
+    ///
+    /// This puts the channel into [`OneShot`](pit::Mode::OneShot) mode.
+    /// The interval takes effect the next time you enable the timer.
+    pub fn set_interval(&mut self, interval: core::time::Duration) {
+        self.pit.set_mode(pit::Mode::OneShot);
+        self.pit
+            .set_load_timer_value(duration_to_ticks(interval, HZ));
+    }
+
+    /// Returns the duration that the timer counts.
+    pub fn duration(&self) -> core::time::Duration {
+        ticks_to_duration(u64::from(self.pit.load_timer_value()), HZ)
+    }
+
+    /// Restart the timer to periodically elapse every `period`.
+    ///
+    /// Unlike [`set_period`](Self::set_period), the period takes effect
+    /// immediately. This clears the elapsed flag, then enables the timer.
+    pub fn start(&mut self, period: core::time::Duration) {
+        self.pit.disable();
+        self.pit.clear_elapsed();
+        self.set_period(period);
+        self.pit.enable();
+    }
+}
+
+/// A `CountDown` that uses `core::time::Duration` for its timeout.
+///
+/// Use this with drivers that need a `CountDown` with `Duration` time.
+/// To use `fugit` durations, or another timer, see [`CountDown`].
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use core::time::Duration;
+/// use eh02::timer::CountDown;
+///
+/// # const PIT_FREQUENCY_HZ: u32 = 1_000_000;
+/// let pit = unsafe { ral::pit::PIT::instance() };
+/// let (pit0, _, _, _) = hal::pit::new(pit);
+///
+/// let mut pit0 = hal::timer::DurationPit::<0, PIT_FREQUENCY_HZ>::from_pit(pit0);
+/// CountDown::start(&mut pit0, Duration::from_millis(10));
+/// nb::block!(CountDown::wait(&mut pit0)).unwrap();
+/// ```
+impl<const N: u8, const HZ: u32> eh02::timer::CountDown for DurationPit<N, HZ> {
+    type Time = core::time::Duration;
+    fn start<C>(&mut self, count: C)
+    where
+        C: Into<Self::Time>,
+    {
+        DurationPit::<N, HZ>::start(self, count.into());
+    }
+    fn wait(&mut self) -> nb::Result<(), void::Void> {
+        if self.pit.is_elapsed() {
+            self.pit.clear_elapsed();
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+impl<const N: u8, const HZ: u32> eh02::timer::Periodic for DurationPit<N, HZ> {}
+
+/// Convert a duration into `ticks` of a `hz` clock.
+///
+/// Saturates at `u32::MAX`.
+fn duration_to_ticks(duration: core::time::Duration, hz: u32) -> u32 {
+    let ticks = duration.as_nanos() * u128::from(hz) / 1_000_000_000;
+    ticks.try_into().unwrap_or(u32::MAX)
+}
+
 /// A count down timer that uses ticks for the timeout.
 ///
 /// This adapter does not require you to know about the
@@ -576,6 +947,26 @@ impl<const N: u8> RawCountDownGpt<N> {
     }
 }
 
+/// A count down timer over a PWM submodule.
+///
+/// The adapter takes the PWM module, since it needs to run the submodule.
+pub type RawCountDownPwm<const N: u8, const M: u8> =
+    RawCountDown<(flexpwm::Pwm<N>, flexpwm::Submodule<N, M>)>;
+
+impl<const N: u8, const M: u8> RawCountDownPwm<N, M> {
+    /// Create a count down timer from a PWM submodule.
+    ///
+    /// This overwrites the submodule's initial count, full reload value,
+    /// load mode, and interrupts. Don't use the submodule's outputs.
+    ///
+    /// The timer elapses when the counter compares to the full reload
+    /// value, VAL1.
+    pub fn from_pwm(mut pwm: flexpwm::Pwm<N>, mut submodule: flexpwm::Submodule<N, M>) -> Self {
+        prepare_pwm(&mut pwm, &mut submodule);
+        Self::new((pwm, submodule))
+    }
+}
+
 impl<T> eh02::timer::CountDown for RawCountDown<T>
 where
     T: HardwareTimer,
@@ -726,3 +1117,64 @@ where
 }
 
 impl<T, const HZ: u32> eh02::timer::Periodic for CountDown<T, HZ> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_to_ticks, lap_ticks, pwm_full_reload_value, ticks_to_duration};
+    use core::time::Duration;
+
+    #[test]
+    fn lap_without_wraparound() {
+        assert_eq!(lap_ticks(0, 0, 100), 0);
+        assert_eq!(lap_ticks(10, 25, 100), 15);
+        assert_eq!(lap_ticks(0, 99, 100), 99);
+    }
+
+    #[test]
+    fn lap_with_wraparound() {
+        assert_eq!(lap_ticks(90, 5, 100), 15);
+        assert_eq!(lap_ticks(99, 0, 100), 1);
+        let period = u64::from(u32::MAX) + 1;
+        assert_eq!(lap_ticks(u32::MAX, 1, period), 2);
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(ticks_to_duration(0, 1_000_000), Duration::ZERO);
+        assert_eq!(ticks_to_duration(1, 1_000_000), Duration::from_micros(1));
+        assert_eq!(
+            ticks_to_duration(3_000_001, 1_000_000),
+            Duration::new(3, 1_000)
+        );
+        assert_eq!(ticks_to_duration(1, 3), Duration::new(0, 333_333_333));
+        assert_eq!(
+            ticks_to_duration(u64::from(u32::MAX), 24_000_000),
+            Duration::new(178, 956_970_625)
+        );
+    }
+
+    #[test]
+    fn ticks() {
+        assert_eq!(duration_to_ticks(Duration::ZERO, 1_000_000), 0);
+        assert_eq!(duration_to_ticks(Duration::from_micros(1), 1_000_000), 1);
+        assert_eq!(
+            duration_to_ticks(Duration::from_millis(250), 24_000_000),
+            6_000_000
+        );
+        assert_eq!(
+            duration_to_ticks(Duration::from_secs(179), 24_000_000),
+            u32::MAX
+        );
+        assert_eq!(duration_to_ticks(Duration::MAX, 1), u32::MAX);
+    }
+
+    #[test]
+    fn pwm_ticks() {
+        assert_eq!(pwm_full_reload_value(0), i16::MIN);
+        assert_eq!(pwm_full_reload_value(1), i16::MIN);
+        assert_eq!(pwm_full_reload_value(2), i16::MIN + 1);
+        assert_eq!(pwm_full_reload_value(1 << 15), -1);
+        assert_eq!(pwm_full_reload_value(1 << 16), i16::MAX);
+        assert_eq!(pwm_full_reload_value(u32::MAX), i16::MAX);
+    }
+}
@@ -0,0 +1,357 @@
+//! USB device helpers.
+
+pub mod dfu;
+mod memory;
+#[cfg(feature = "usbd-serial")]
+mod serial;
+mod stats;
+mod vbus;
+pub use memory::{required_memory, EndpointConfig};
+#[cfg(feature = "usbd-serial")]
+pub use serial::{CdcSerial, LineCodingCallback};
+pub use stats::{EndpointStats, StatsBus};
+pub use vbus::{Vbus, VbusEvent};
+
+use imxrt_usbd::Speed;
+use usb_device::{
+    bus::{UsbBus, UsbBusAllocator},
+    device::{StringDescriptors, UsbDevice, UsbDeviceBuilder, UsbDeviceState, UsbVidPid},
+};
+
+/// An error when building a USB device from a [`DeviceDescriptorConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DescriptorError {
+    /// The control endpoint's max packet size isn't valid for the speed.
+    ///
+    /// High-speed devices must use 64 bytes. Low / full speed devices
+    /// may use 8, 16, 32, or 64 bytes.
+    MaxPacketSize0 {
+        /// The configured max packet size.
+        max_packet_size_0: u8,
+        /// The configured speed.
+        speed: Speed,
+    },
+    /// The device requests more than [`MAX_BUS_POWER_MA`] from the bus.
+    MaxPower {
+        /// The requested current, in milliamps.
+        max_power_ma: u16,
+    },
+}
+
+/// The most current, in milliamps, that a device may draw from a USB 2
+/// bus after the host configures the device.
+pub const MAX_BUS_POWER_MA: u16 = 500;
+
+/// The current, in milliamps, that a device may draw from the bus before
+/// the host configures the device.
+///
+/// This is one unit load.
+pub const UNCONFIGURED_BUS_POWER_MA: u16 = 100;
+
+/// The current, in milliamps, that a suspended device may draw from the bus.
+///
+/// The USB 2 limit is 2.5mA. This rounds down to whole milliamps.
+pub const SUSPENDED_BUS_POWER_MA: u16 = 2;
+
+/// Define a static [`EndpointMemory`](crate::usbd::EndpointMemory) in a
+/// specific linker section.
+///
+/// The USB controller moves every packet through endpoint memory, so the
+/// memory's location affects throughput. Endpoint memory is a zero-initialized
+/// static, so it normally lands in `.bss` with your other statics. Use this
+/// macro to place the endpoint memory in its own section, then use your linker
+/// script to place that section in DTCM or OCRAM.
+///
+/// The section must be one that your runtime initializes. With `imxrt-rt`,
+/// use a `.bss.*` section to place the memory with `RuntimeBuilder::bss`, or a
+/// `.data.*` section to place it with `RuntimeBuilder::data`. Then, select
+/// the memory for that section in your build script. Don't use `.uninit`;
+/// endpoint memory needs its initial value.
+///
+/// ```
+/// imxrt_hal::endpoint_memory! {
+///     /// Endpoint memory for the USB serial port.
+///     static EP_MEMORY: [2048] in ".data.usbd";
+/// }
+/// static EP_STATE: imxrt_hal::usbd::EndpointState = imxrt_hal::usbd::EndpointState::max_endpoints();
+/// ```
+///
+/// With `imxrt-rt`, this places `EP_MEMORY` in OCRAM, and all other statics in DTCM:
+///
+/// ```ignore
+/// RuntimeBuilder::from_flexspi(Family::Imxrt1060, FLASH_SIZE)
+///     .bss(Memory::Dtcm)
+///     .data(Memory::Ocram)
+///     .build()?;
+/// ```
+#[macro_export]
+macro_rules! endpoint_memory {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: [$size:expr] in $section:literal;) => {
+        $(#[$attr])*
+        #[link_section = $section]
+        $vis static $name: $crate::usbd::EndpointMemory<{ $size }> =
+            $crate::usbd::EndpointMemory::new();
+    };
+}
+
+/// A USB device descriptor configuration.
+///
+/// `DeviceDescriptorConfig` collects the settings that most USB devices
+/// specify in a `usb-device` `UsbDeviceBuilder`. It checks the settings
+/// against the bus speed before it builds the device. The default
+/// configuration
+///
+/// - has no strings.
+/// - defers the device class to the interfaces.
+/// - uses a 64 byte control endpoint max packet size.
+/// - has device release 0.1.
+/// - is bus powered, and requests 100mA.
+///
+/// Use the same [`Speed`] that you used to construct your
+/// [`BusAdapter`](crate::usbd::BusAdapter).
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use hal::usbd::{DeviceDescriptorConfig, Speed};
+///
+/// const CONFIG: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, Speed::High)
+///     .product("imxrt-hal-example")
+///     .device_class(0x02);
+///
+/// # fn f<B: usb_device::bus::UsbBus>(bus: &usb_device::bus::UsbBusAllocator<B>) {
+/// let bus = // The UsbBusAllocator...
+///     # bus;
+/// let device = CONFIG.build(bus).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceDescriptorConfig<'a> {
+    vid: u16,
+    pid: u16,
+    speed: Speed,
+    manufacturer: Option<&'a str>,
+    product: Option<&'a str>,
+    serial_number: Option<&'a str>,
+    device_class: u8,
+    device_sub_class: u8,
+    device_protocol: u8,
+    max_packet_size_0: u8,
+    device_release: u16,
+    self_powered: bool,
+    max_power_ma: u16,
+}
+
+impl<'a> DeviceDescriptorConfig<'a> {
+    /// Create a configuration for a device with the vendor and product ID,
+    /// running at `speed`.
+    pub const fn new(vid: u16, pid: u16, speed: Speed) -> Self {
+        Self {
+            vid,
+            pid,
+            speed,
+            manufacturer: None,
+            product: None,
+            serial_number: None,
+            device_class: 0x00,
+            device_sub_class: 0x00,
+            device_protocol: 0x00,
+            max_packet_size_0: 64,
+            device_release: 0x0010,
+            self_powered: false,
+            max_power_ma: UNCONFIGURED_BUS_POWER_MA,
+        }
+    }
+
+    /// Set the manufacturer string.
+    pub const fn manufacturer(mut self, manufacturer: &'a str) -> Self {
+        self.manufacturer = Some(manufacturer);
+        self
+    }
+
+    /// Set the product string.
+    pub const fn product(mut self, product: &'a str) -> Self {
+        self.product = Some(product);
+        self
+    }
+
+    /// Set the serial number string.
+    pub const fn serial_number(mut self, serial_number: &'a str) -> Self {
+        self.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Set the device class code.
+    pub const fn device_class(mut self, device_class: u8) -> Self {
+        self.device_class = device_class;
+        self
+    }
+
+    /// Set the device sub-class code.
+    pub const fn device_sub_class(mut self, device_sub_class: u8) -> Self {
+        self.device_sub_class = device_sub_class;
+        self
+    }
+
+    /// Set the device protocol code.
+    pub const fn device_protocol(mut self, device_protocol: u8) -> Self {
+        self.device_protocol = device_protocol;
+        self
+    }
+
+    /// Set the control endpoint's max packet size, in bytes.
+    pub const fn max_packet_size_0(mut self, max_packet_size_0: u8) -> Self {
+        self.max_packet_size_0 = max_packet_size_0;
+        self
+    }
+
+    /// Set the device release number, in BCD (`bcdDevice`).
+    pub const fn device_release(mut self, device_release: u16) -> Self {
+        self.device_release = device_release;
+        self
+    }
+
+    /// Indicate that the device may have its own power source.
+    ///
+    /// Set this even if the device only sometimes has its own power.
+    /// A self-powered device follows the same limits when it draws current
+    /// from the bus.
+    pub const fn self_powered(mut self, self_powered: bool) -> Self {
+        self.self_powered = self_powered;
+        self
+    }
+
+    /// Set the most current, in milliamps, that the device draws from the bus.
+    ///
+    /// The descriptor expresses the current in 2mA units, so odd values round
+    /// down. The value can't exceed [`MAX_BUS_POWER_MA`]. If the device never
+    /// draws current from the bus, use zero, and indicate that it's
+    /// [self-powered](Self::self_powered).
+    pub const fn max_power_ma(mut self, max_power_ma: u16) -> Self {
+        self.max_power_ma = max_power_ma;
+        self
+    }
+
+    /// Returns the current, in milliamps, that the device requests
+    /// from the host.
+    ///
+    /// This is the [`max_power_ma`](Self::max_power_ma) value that the
+    /// descriptor can express.
+    pub const fn requested_power_ma(&self) -> u16 {
+        self.max_power_ma & !1
+    }
+
+    /// Returns the current, in milliamps, that the device may draw from
+    /// the bus in the device state `state`.
+    ///
+    /// Before the host configures the device, the device may draw one unit
+    /// load. Once configured, the host has granted the
+    /// [requested current](Self::requested_power_ma). A suspended device
+    /// may only draw the suspend current. Use this with `UsbDevice::state()`
+    /// to throttle peripherals until the host grants full power.
+    pub const fn available_power_ma(&self, state: UsbDeviceState) -> u16 {
+        match state {
+            UsbDeviceState::Default | UsbDeviceState::Addressed => UNCONFIGURED_BUS_POWER_MA,
+            UsbDeviceState::Configured => self.requested_power_ma(),
+            UsbDeviceState::Suspend => SUSPENDED_BUS_POWER_MA,
+        }
+    }
+
+    /// Check the configuration.
+    pub const fn validate(&self) -> Result<(), DescriptorError> {
+        if self.max_power_ma > MAX_BUS_POWER_MA {
+            return Err(DescriptorError::MaxPower {
+                max_power_ma: self.max_power_ma,
+            });
+        }
+        let valid = match self.speed {
+            Speed::High => self.max_packet_size_0 == 64,
+            Speed::LowFull => matches!(self.max_packet_size_0, 8 | 16 | 32 | 64),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(DescriptorError::MaxPacketSize0 {
+                max_packet_size_0: self.max_packet_size_0,
+                speed: self.speed,
+            })
+        }
+    }
+
+    /// Validate the configuration, then build a USB device.
+    pub fn build<B: UsbBus>(
+        &self,
+        bus: &'a UsbBusAllocator<B>,
+    ) -> Result<UsbDevice<'a, B>, DescriptorError> {
+        self.validate()?;
+
+        let mut strings = StringDescriptors::default();
+        if let Some(manufacturer) = self.manufacturer {
+            strings = strings.manufacturer(manufacturer);
+        }
+        if let Some(product) = self.product {
+            strings = strings.product(product);
+        }
+        if let Some(serial_number) = self.serial_number {
+            strings = strings.serial_number(serial_number);
+        }
+
+        // Validated above, and one set of strings is always
+        // supported.
+        let builder = UsbDeviceBuilder::new(bus, UsbVidPid(self.vid, self.pid))
+            .strings(&[strings])
+            .unwrap()
+            .max_packet_size_0(self.max_packet_size_0)
+            .unwrap()
+            .max_power(self.max_power_ma as usize)
+            .unwrap();
+
+        Ok(builder
+            .device_class(self.device_class)
+            .device_sub_class(self.device_sub_class)
+            .device_protocol(self.device_protocol)
+            .device_release(self.device_release)
+            .self_powered(self.self_powered)
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DescriptorError, DeviceDescriptorConfig, Speed, UsbDeviceState};
+
+    #[test]
+    fn max_packet_size_0() {
+        let config = DeviceDescriptorConfig::new(0, 0, Speed::High);
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(
+            config.max_packet_size_0(8).validate(),
+            Err(DescriptorError::MaxPacketSize0 {
+                max_packet_size_0: 8,
+                speed: Speed::High
+            })
+        );
+
+        let config = DeviceDescriptorConfig::new(0, 0, Speed::LowFull);
+        assert_eq!(config.max_packet_size_0(8).validate(), Ok(()));
+        assert!(config.max_packet_size_0(12).validate().is_err());
+    }
+
+    #[test]
+    fn max_power() {
+        let config = DeviceDescriptorConfig::new(0, 0, Speed::High);
+        assert_eq!(config.requested_power_ma(), 100);
+        assert_eq!(config.max_power_ma(500).validate(), Ok(()));
+        assert_eq!(
+            config.max_power_ma(501).validate(),
+            Err(DescriptorError::MaxPower { max_power_ma: 501 })
+        );
+
+        let config = config.max_power_ma(251);
+        assert_eq!(config.requested_power_ma(), 250);
+        assert_eq!(config.available_power_ma(UsbDeviceState::Default), 100);
+        assert_eq!(config.available_power_ma(UsbDeviceState::Addressed), 100);
+        assert_eq!(config.available_power_ma(UsbDeviceState::Configured), 250);
+        assert_eq!(config.available_power_ma(UsbDeviceState::Suspend), 2);
+    }
+}
@@ -0,0 +1,743 @@
+//! USB device firmware upgrade (DFU) class.
+//!
+//! [`Dfu`] implements the DFU 1.1 class in DFU mode. It receives firmware
+//! from a host tool, like `dfu-util`, and hands each block to a
+//! [`DfuHandler`]. [`FlexSpiDownload`] is a handler that programs the blocks
+//! into a FlexSPI NOR flash.
+//!
+//! [`DfuRuntime`] implements the DFU class in run-time mode. It adds a DFU
+//! interface to your application, so that a host tool can ask the device to
+//! detach. Your application then resets into its bootloader.
+//!
+//! Neither class supports uploads.
+
+use usb_device::class_prelude::*;
+use usb_device::control::{Recipient, RequestType};
+
+use crate::flexspi::{FlexSpi, FlexSpiError, PAGE_SIZE, SECTOR_SIZE};
+
+/// The largest block of firmware that the host sends per request.
+///
+/// This matches the `usb-device` control buffer size.
+pub const TRANSFER_SIZE: u16 = 128;
+
+/// Application specific interface class.
+const USB_CLASS_APPLICATION_SPECIFIC: u8 = 0xFE;
+/// Device firmware upgrade subclass.
+const DFU_SUBCLASS: u8 = 0x01;
+/// The interface protocol for run-time mode.
+const PROTOCOL_RUNTIME: u8 = 0x01;
+/// The interface protocol for DFU mode.
+const PROTOCOL_DFU_MODE: u8 = 0x02;
+/// DFU functional descriptor type.
+const DFU_FUNCTIONAL: u8 = 0x21;
+/// DFU 1.1, in BCD.
+const DFU_VERSION: u16 = 0x0110;
+
+const DFU_DETACH: u8 = 0;
+const DFU_DNLOAD: u8 = 1;
+const DFU_GETSTATUS: u8 = 3;
+const DFU_CLRSTATUS: u8 = 4;
+const DFU_GETSTATE: u8 = 5;
+const DFU_ABORT: u8 = 6;
+
+/// The device accepts downloads.
+const ATTR_CAN_DNLOAD: u8 = 1 << 0;
+/// The device responds to requests after manifestation.
+const ATTR_MANIFESTATION_TOLERANT: u8 = 1 << 2;
+/// The device detaches itself after a detach request.
+const ATTR_WILL_DETACH: u8 = 1 << 3;
+
+/// A DFU class state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DfuState {
+    /// Running the application. Run-time mode.
+    AppIdle = 0,
+    /// Received a detach request. Run-time mode.
+    AppDetach = 1,
+    /// Waiting for a download.
+    Idle = 2,
+    /// Received a block, waiting for the host to request status.
+    DownloadSync = 3,
+    /// Programming a block.
+    DownloadBusy = 4,
+    /// Waiting for the next block.
+    DownloadIdle = 5,
+    /// Received the end of the download, waiting for the host to request status.
+    ManifestSync = 6,
+    /// Finishing the download.
+    Manifest = 7,
+    /// Waiting for a USB reset after the download.
+    ManifestWaitReset = 8,
+    /// Uploading.
+    UploadIdle = 9,
+    /// An error occurred. Waiting for the host to clear the status.
+    Error = 10,
+}
+
+/// A DFU error status.
+///
+/// The device reports the status to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DfuError {
+    /// The file isn't for this device.
+    Target = 0x01,
+    /// The file is for this device, but it fails a verification test.
+    File = 0x02,
+    /// The device can't write memory.
+    Write = 0x03,
+    /// The memory erase failed.
+    Erase = 0x04,
+    /// The memory erase check failed.
+    CheckErased = 0x05,
+    /// The program memory function failed.
+    Program = 0x06,
+    /// The programmed memory failed verification.
+    Verify = 0x07,
+    /// The received address is out of range.
+    Address = 0x08,
+    /// The download ended, but the firmware is incomplete.
+    NotDone = 0x09,
+    /// The firmware is corrupt.
+    Firmware = 0x0A,
+    /// A vendor-specific error.
+    Vendor = 0x0B,
+    /// Unexpected USB reset.
+    UsbReset = 0x0C,
+    /// Unexpected power on reset.
+    PowerOnReset = 0x0D,
+    /// Something went wrong.
+    Unknown = 0x0E,
+    /// The device stalled an unexpected request.
+    StalledPacket = 0x0F,
+}
+
+/// Handles the firmware that the host downloads.
+///
+/// The [`Dfu`] class calls these methods from `UsbDevice::poll`, while it
+/// handles the host's status request. The host waits for the method to
+/// return, so it's OK to block while erasing or programming memory.
+pub trait DfuHandler {
+    /// Handle a block of firmware.
+    ///
+    /// `offset` is the block's byte offset from the start of the download.
+    /// The first block of every download has offset zero. `data` is at most
+    /// [`TRANSFER_SIZE`] bytes.
+    fn download(&mut self, offset: u32, data: &[u8]) -> Result<(), DfuError>;
+
+    /// The host finished the download.
+    ///
+    /// Use this to validate the firmware, or to mark it bootable.
+    fn manifest(&mut self) -> Result<(), DfuError> {
+        Ok(())
+    }
+
+    /// The host aborted the download.
+    fn abort(&mut self) {}
+}
+
+/// Returns `true` if the request is a class request for `interface`.
+fn is_class_request(request: &control::Request, interface: InterfaceNumber) -> bool {
+    request.request_type == RequestType::Class
+        && request.recipient == Recipient::Interface
+        && request.index == u8::from(interface) as u16
+}
+
+/// Write the DFU functional descriptor.
+fn write_functional(
+    writer: &mut DescriptorWriter,
+    attributes: u8,
+    detach_timeout_ms: u16,
+) -> usb_device::Result<()> {
+    let [timeout_lo, timeout_hi] = detach_timeout_ms.to_le_bytes();
+    let [size_lo, size_hi] = TRANSFER_SIZE.to_le_bytes();
+    let [version_lo, version_hi] = DFU_VERSION.to_le_bytes();
+    writer.write(
+        DFU_FUNCTIONAL,
+        &[
+            attributes, timeout_lo, timeout_hi, size_lo, size_hi, version_lo, version_hi,
+        ],
+    )
+}
+
+/// Build a status response.
+///
+/// The poll timeout is always zero, since the class finishes its work
+/// before it responds.
+const fn status_response(status: Option<DfuError>, state: DfuState) -> [u8; 6] {
+    let status = match status {
+        Some(err) => err as u8,
+        None => 0,
+    };
+    [status, 0, 0, 0, state as u8, 0]
+}
+
+/// A USB DFU class, in DFU mode.
+///
+/// `Dfu` receives firmware from the host, and hands it to a [`DfuHandler`].
+/// Allocate it on the bus before you build the `UsbDevice`, then include it
+/// in every `UsbDevice::poll`. The class has no endpoints; it uses only the
+/// control endpoint.
+///
+/// After the host finishes the download, and the handler accepts the
+/// firmware, [`is_complete`](Self::is_complete) returns `true`. Keep polling
+/// the device for a moment, so that the host sees the final status, then
+/// reset into the new firmware.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+/// use hal::usbd::{dfu, BusAdapter, DeviceDescriptorConfig, Speed};
+/// use usb_device::bus::UsbBusAllocator;
+///
+/// const CONFIG: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, Speed::High)
+///     .product("imxrt-hal-dfu");
+/// // Firmware goes in the second MiB of flash.
+/// const FIRMWARE_OFFSET: u32 = 1024 * 1024;
+/// const FIRMWARE_SIZE: u32 = 1024 * 1024;
+///
+/// # fn f(bus: &'static UsbBusAllocator<BusAdapter>) {
+/// let bus: &UsbBusAllocator<BusAdapter> = // Allocated once...
+///     # bus;
+/// let flexspi = hal::flexspi::FlexSpi::new(unsafe { ral::flexspi::FLEXSPI1::instance() });
+/// let handler = dfu::FlexSpiDownload::new(flexspi, FIRMWARE_OFFSET, FIRMWARE_SIZE);
+///
+/// let mut dfu = dfu::Dfu::new(bus, handler);
+/// let mut device = CONFIG.build(bus).unwrap();
+/// while !dfu.is_complete() {
+///     device.poll(&mut [&mut dfu]);
+/// }
+/// # }
+/// ```
+pub struct Dfu<H> {
+    interface: InterfaceNumber,
+    machine: Machine<H>,
+}
+
+impl<H: DfuHandler> Dfu<H> {
+    /// Allocate a DFU interface on the bus.
+    pub fn new<B: UsbBus>(bus: &UsbBusAllocator<B>, handler: H) -> Self {
+        Self {
+            interface: bus.interface(),
+            machine: Machine::new(handler),
+        }
+    }
+
+    /// Returns the class state.
+    pub fn state(&self) -> DfuState {
+        self.machine.state
+    }
+
+    /// Returns `true` if the handler accepted a complete download.
+    ///
+    /// This resets when the host starts another download.
+    pub fn is_complete(&self) -> bool {
+        self.machine.complete
+    }
+
+    /// Access the handler.
+    pub fn handler(&self) -> &H {
+        &self.machine.handler
+    }
+
+    /// Mutably access the handler.
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.machine.handler
+    }
+
+    /// Release the handler.
+    pub fn release(self) -> H {
+        self.machine.handler
+    }
+}
+
+/// The DFU mode state machine.
+struct Machine<H> {
+    handler: H,
+    state: DfuState,
+    status: Option<DfuError>,
+    buffer: [u8; TRANSFER_SIZE as usize],
+    pending: usize,
+    offset: u32,
+    complete: bool,
+}
+
+impl<H: DfuHandler> Machine<H> {
+    const fn new(handler: H) -> Self {
+        Self {
+            handler,
+            state: DfuState::Idle,
+            status: None,
+            buffer: [0; TRANSFER_SIZE as usize],
+            pending: 0,
+            offset: 0,
+            complete: false,
+        }
+    }
+
+    /// Enter the error state.
+    fn fail(&mut self, err: DfuError) {
+        self.state = DfuState::Error;
+        self.status = Some(err);
+    }
+
+    /// Handle a download request. Returns `true` if the request is accepted.
+    fn download_request(&mut self, data: &[u8]) -> bool {
+        match (self.state, data.len()) {
+            (DfuState::Idle, 0) => {
+                self.fail(DfuError::StalledPacket);
+                false
+            }
+            (DfuState::DownloadIdle, 0) => {
+                self.state = DfuState::ManifestSync;
+                true
+            }
+            (DfuState::Idle | DfuState::DownloadIdle, len) if len <= self.buffer.len() => {
+                if self.state == DfuState::Idle {
+                    self.offset = 0;
+                    self.complete = false;
+                }
+                self.buffer[..len].copy_from_slice(data);
+                self.pending = len;
+                self.state = DfuState::DownloadSync;
+                true
+            }
+            _ => {
+                self.fail(DfuError::StalledPacket);
+                false
+            }
+        }
+    }
+
+    /// Handle a status request, finishing any pending work.
+    fn status_request(&mut self) -> [u8; 6] {
+        match self.state {
+            DfuState::DownloadSync => {
+                match self
+                    .handler
+                    .download(self.offset, &self.buffer[..self.pending])
+                {
+                    Ok(()) => {
+                        self.offset += self.pending as u32;
+                        self.state = DfuState::DownloadIdle;
+                    }
+                    Err(err) => self.fail(err),
+                }
+                self.pending = 0;
+            }
+            DfuState::ManifestSync => match self.handler.manifest() {
+                Ok(()) => {
+                    self.complete = true;
+                    self.state = DfuState::Idle;
+                }
+                Err(err) => self.fail(err),
+            },
+            _ => {}
+        }
+        status_response(self.status, self.state)
+    }
+
+    /// Handle an abort request. Returns `true` if the request is accepted.
+    fn abort_request(&mut self) -> bool {
+        match self.state {
+            DfuState::Idle
+            | DfuState::DownloadSync
+            | DfuState::DownloadIdle
+            | DfuState::ManifestSync => {
+                if self.state != DfuState::Idle {
+                    self.handler.abort();
+                }
+                self.pending = 0;
+                self.state = DfuState::Idle;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Return to idle after a USB reset.
+    fn reset(&mut self) {
+        if self.state != DfuState::Idle {
+            self.handler.abort();
+        }
+        self.state = DfuState::Idle;
+        self.status = None;
+        self.pending = 0;
+    }
+
+    /// Handle a clear status request. Returns `true` if the request is accepted.
+    fn clear_status_request(&mut self) -> bool {
+        if self.state == DfuState::Error {
+            self.state = DfuState::Idle;
+            self.status = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<B: UsbBus, H: DfuHandler> UsbClass<B> for Dfu<H> {
+    fn get_configuration_descriptors(
+        &self,
+        writer: &mut DescriptorWriter,
+    ) -> usb_device::Result<()> {
+        writer.interface(
+            self.interface,
+            USB_CLASS_APPLICATION_SPECIFIC,
+            DFU_SUBCLASS,
+            PROTOCOL_DFU_MODE,
+        )?;
+        write_functional(writer, ATTR_CAN_DNLOAD | ATTR_MANIFESTATION_TOLERANT, 0)
+    }
+
+    fn reset(&mut self) {
+        self.machine.reset();
+    }
+
+    fn control_out(&mut self, xfer: ControlOut<B>) {
+        let request = xfer.request();
+        if !is_class_request(request, self.interface) {
+            return;
+        }
+        let accepted = match request.request {
+            DFU_DNLOAD => self.machine.download_request(xfer.data()),
+            DFU_CLRSTATUS => self.machine.clear_status_request(),
+            DFU_ABORT => self.machine.abort_request(),
+            _ => false,
+        };
+        if accepted {
+            xfer.accept().ok();
+        } else {
+            xfer.reject().ok();
+        }
+    }
+
+    fn control_in(&mut self, xfer: ControlIn<B>) {
+        let request = xfer.request();
+        if !is_class_request(request, self.interface) {
+            return;
+        }
+        match request.request {
+            DFU_GETSTATUS => {
+                let response = self.machine.status_request();
+                xfer.accept_with(&response).ok();
+            }
+            DFU_GETSTATE => {
+                xfer.accept_with(&[self.machine.state as u8]).ok();
+            }
+            _ => {
+                self.machine.fail(DfuError::StalledPacket);
+                xfer.reject().ok();
+            }
+        }
+    }
+}
+
+/// A USB DFU class, in run-time mode.
+///
+/// `DfuRuntime` adds a DFU interface to your application. When a host tool
+/// sends a detach request, [`is_detach_requested`](Self::is_detach_requested)
+/// returns `true`. Your application then resets into a bootloader that
+/// implements DFU mode, like one that uses [`Dfu`].
+///
+/// The ROM bootloader uses the NXP serial download protocol, not DFU.
+/// Still, resetting into the ROM bootloader on detach gives you a way to
+/// reach it without touching the boot mode pins.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use hal::usbd::{dfu::DfuRuntime, BusAdapter, DeviceDescriptorConfig, Speed};
+/// use usb_device::bus::UsbBusAllocator;
+///
+/// const CONFIG: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, Speed::High)
+///     .product("imxrt-hal-example");
+///
+/// # fn f(bus: &'static UsbBusAllocator<BusAdapter>) {
+/// let bus: &UsbBusAllocator<BusAdapter> = // Allocated once...
+///     # bus;
+/// let mut dfu = DfuRuntime::new(bus, 1000);
+/// let mut device = CONFIG.build(bus).unwrap();
+/// loop {
+///     device.poll(&mut [&mut dfu]);
+///     if dfu.is_detach_requested() {
+///         hal::reset_to_bootloader();
+///     }
+/// }
+/// # }
+/// ```
+pub struct DfuRuntime {
+    interface: InterfaceNumber,
+    detach_timeout_ms: u16,
+    detach: bool,
+}
+
+impl DfuRuntime {
+    /// Allocate a run-time DFU interface on the bus.
+    ///
+    /// `detach_timeout_ms` is the time, in milliseconds, that the host
+    /// waits for the device to reset after the detach request.
+    pub fn new<B: UsbBus>(bus: &UsbBusAllocator<B>, detach_timeout_ms: u16) -> Self {
+        Self {
+            interface: bus.interface(),
+            detach_timeout_ms,
+            detach: false,
+        }
+    }
+
+    /// Returns `true` if the host asked the device to detach.
+    pub fn is_detach_requested(&self) -> bool {
+        self.detach
+    }
+
+    fn state(&self) -> DfuState {
+        if self.detach {
+            DfuState::AppDetach
+        } else {
+            DfuState::AppIdle
+        }
+    }
+}
+
+impl<B: UsbBus> UsbClass<B> for DfuRuntime {
+    fn get_configuration_descriptors(
+        &self,
+        writer: &mut DescriptorWriter,
+    ) -> usb_device::Result<()> {
+        writer.interface(
+            self.interface,
+            USB_CLASS_APPLICATION_SPECIFIC,
+            DFU_SUBCLASS,
+            PROTOCOL_RUNTIME,
+        )?;
+        write_functional(
+            writer,
+            ATTR_CAN_DNLOAD | ATTR_WILL_DETACH,
+            self.detach_timeout_ms,
+        )
+    }
+
+    fn control_out(&mut self, xfer: ControlOut<B>) {
+        let request = xfer.request();
+        if !is_class_request(request, self.interface) {
+            return;
+        }
+        if request.request == DFU_DETACH {
+            self.detach = true;
+            xfer.accept().ok();
+        } else {
+            xfer.reject().ok();
+        }
+    }
+
+    fn control_in(&mut self, xfer: ControlIn<B>) {
+        let request = xfer.request();
+        if !is_class_request(request, self.interface) {
+            return;
+        }
+        match request.request {
+            DFU_GETSTATUS => {
+                xfer.accept_with(&status_response(None, self.state())).ok();
+            }
+            DFU_GETSTATE => {
+                xfer.accept_with(&[self.state() as u8]).ok();
+            }
+            _ => {
+                xfer.reject().ok();
+            }
+        }
+    }
+}
+
+/// A DFU handler that programs firmware into a FlexSPI NOR flash.
+///
+/// The handler writes the download into a flash region, starting at
+/// `offset` from the start of the flash. It erases each sector just before
+/// it programs the sector. Downloads that exceed the region fail with
+/// [`DfuError::Address`].
+///
+/// Interrupts must not execute from flash while the handler erases and
+/// programs the flash. See the [`flexspi` documentation](crate::flexspi)
+/// for more information.
+pub struct FlexSpiDownload<const N: u8> {
+    flexspi: FlexSpi<N>,
+    offset: u32,
+    size: u32,
+    erased: u32,
+}
+
+impl<const N: u8> FlexSpiDownload<N> {
+    /// Create a handler that writes up to `size` bytes, starting at `offset`.
+    ///
+    /// The handler erases whole sectors, so the region must start and end
+    /// on a sector boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` isn't aligned to a [`SECTOR_SIZE`], or if `size`
+    /// isn't a multiple of [`SECTOR_SIZE`].
+    pub fn new(flexspi: FlexSpi<N>, offset: u32, size: u32) -> Self {
+        assert!(
+            offset.is_multiple_of(SECTOR_SIZE as u32),
+            "Firmware offset must be sector aligned"
+        );
+        assert!(
+            size.is_multiple_of(SECTOR_SIZE as u32),
+            "Firmware size must be a multiple of the sector size"
+        );
+        Self {
+            flexspi,
+            offset,
+            size,
+            erased: 0,
+        }
+    }
+
+    /// Release the FlexSPI driver.
+    pub fn release(self) -> FlexSpi<N> {
+        self.flexspi
+    }
+}
+
+impl<const N: u8> DfuHandler for FlexSpiDownload<N> {
+    fn download(&mut self, offset: u32, data: &[u8]) -> Result<(), DfuError> {
+        if offset == 0 {
+            self.erased = 0;
+        }
+        let end = offset
+            .checked_add(data.len() as u32)
+            .filter(|&end| end <= self.size)
+            .ok_or(DfuError::Address)?;
+        // A large block number can exceed the flash address space.
+        let mut addr = self.offset.checked_add(offset).ok_or(DfuError::Address)?;
+        self.offset.checked_add(end).ok_or(DfuError::Address)?;
+
+        while self.erased < end {
+            self.flexspi
+                .erase_sector(self.offset + self.erased)
+                .map_err(|_| DfuError::Erase)?;
+            self.erased += SECTOR_SIZE as u32;
+        }
+
+        let mut data = data;
+        while !data.is_empty() {
+            let (page, rest) = data.split_at(page_chunk_len(addr, data.len()));
+            self.flexspi
+                .program_page(addr, page)
+                .map_err(|err: FlexSpiError| match err {
+                    FlexSpiError::PageBoundary | FlexSpiError::Address => DfuError::Address,
+                    _ => DfuError::Program,
+                })?;
+            addr += page.len() as u32;
+            data = rest;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of bytes, up to `len`, that fit in the page at `addr`.
+const fn page_chunk_len(addr: u32, len: usize) -> usize {
+    let remaining = PAGE_SIZE - (addr as usize % PAGE_SIZE);
+    if len < remaining {
+        len
+    } else {
+        remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{page_chunk_len, status_response, DfuError, DfuHandler, DfuState, Machine};
+
+    #[derive(Default)]
+    struct Handler {
+        received: usize,
+        manifested: bool,
+        aborted: bool,
+    }
+
+    impl DfuHandler for Handler {
+        fn download(&mut self, offset: u32, data: &[u8]) -> Result<(), DfuError> {
+            if offset as usize != self.received {
+                return Err(DfuError::Address);
+            }
+            if data[0] == 0xFF {
+                return Err(DfuError::Program);
+            }
+            self.received += data.len();
+            Ok(())
+        }
+        fn manifest(&mut self) -> Result<(), DfuError> {
+            self.manifested = true;
+            Ok(())
+        }
+        fn abort(&mut self) {
+            self.aborted = true;
+        }
+    }
+
+    fn dfu() -> Machine<Handler> {
+        Machine::new(Handler::default())
+    }
+
+    #[test]
+    fn download() {
+        let mut dfu = dfu();
+        assert!(dfu.download_request(&[1; 128]));
+        assert_eq!(dfu.state, DfuState::DownloadSync);
+        assert_eq!(
+            dfu.status_request(),
+            status_response(None, DfuState::DownloadIdle)
+        );
+        assert!(dfu.download_request(&[2; 16]));
+        dfu.status_request();
+        assert_eq!(dfu.handler.received, 144);
+
+        assert!(!dfu.complete);
+        assert!(dfu.download_request(&[]));
+        assert_eq!(dfu.status_request(), status_response(None, DfuState::Idle));
+        assert!(dfu.handler.manifested);
+        assert!(dfu.complete);
+
+        // The next download starts at offset zero.
+        assert!(dfu.download_request(&[3; 8]));
+        assert!(!dfu.complete);
+        dfu.status_request();
+        assert_eq!(dfu.state, DfuState::Error);
+        assert_eq!(dfu.status, Some(DfuError::Address));
+    }
+
+    #[test]
+    fn errors() {
+        let mut dfu = dfu();
+        // Can't manifest without a download.
+        assert!(!dfu.download_request(&[]));
+        assert_eq!(dfu.state, DfuState::Error);
+        assert!(!dfu.download_request(&[1]));
+        assert!(dfu.clear_status_request());
+        assert_eq!(dfu.state, DfuState::Idle);
+
+        assert!(dfu.download_request(&[0xFF]));
+        assert_eq!(
+            dfu.status_request(),
+            status_response(Some(DfuError::Program), DfuState::Error)
+        );
+        assert!(!dfu.abort_request());
+        assert!(dfu.clear_status_request());
+
+        assert!(dfu.download_request(&[1]));
+        assert!(dfu.abort_request());
+        assert!(dfu.handler.aborted);
+        assert_eq!(dfu.state, DfuState::Idle);
+    }
+
+    #[test]
+    fn page_chunks() {
+        assert_eq!(page_chunk_len(0, 128), 128);
+        assert_eq!(page_chunk_len(0, 300), 256);
+        assert_eq!(page_chunk_len(200, 128), 56);
+        assert_eq!(page_chunk_len(256, 128), 128);
+    }
+}
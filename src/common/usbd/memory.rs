@@ -0,0 +1,87 @@
+//! Endpoint memory sizing.
+
+/// An endpoint that needs endpoint memory.
+///
+/// The USB driver allocates one buffer for each endpoint direction. Each
+/// buffer is as large as the endpoint's max packet size. Use endpoint
+/// configurations with [`required_memory`] to size your
+/// [`EndpointMemory`](crate::usbd::EndpointMemory).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointConfig {
+    max_packet_size: u16,
+    directions: u16,
+}
+
+impl EndpointConfig {
+    /// An IN or OUT endpoint with a max packet size, in bytes.
+    pub const fn new(max_packet_size: u16) -> Self {
+        Self {
+            max_packet_size,
+            directions: 1,
+        }
+    }
+
+    /// The control endpoint, with its max packet size, in bytes.
+    ///
+    /// The control endpoint has both an IN and an OUT buffer. Use the
+    /// same size as your device's `max_packet_size_0`.
+    pub const fn control(max_packet_size_0: u8) -> Self {
+        Self {
+            max_packet_size: max_packet_size_0 as u16,
+            directions: 2,
+        }
+    }
+
+    /// Returns the endpoint memory, in bytes, that this endpoint needs.
+    pub const fn required_memory(&self) -> usize {
+        self.max_packet_size as usize * self.directions as usize
+    }
+}
+
+/// Returns the endpoint memory, in bytes, that all `endpoints` need.
+///
+/// Include the control endpoint, and every endpoint that your classes
+/// allocate. If your endpoint memory is smaller than this value, allocating
+/// endpoints panics.
+///
+/// ```
+/// use imxrt_hal as hal;
+/// use hal::usbd::{required_memory, EndpointConfig, EndpointMemory};
+///
+/// const ENDPOINTS: [EndpointConfig; 4] = [
+///     EndpointConfig::control(64),
+///     EndpointConfig::new(16),  // Interrupt IN
+///     EndpointConfig::new(512), // Bulk IN
+///     EndpointConfig::new(512), // Bulk OUT
+/// ];
+/// static EP_MEMORY: EndpointMemory<{ required_memory(&ENDPOINTS) }> = EndpointMemory::new();
+/// ```
+pub const fn required_memory(endpoints: &[EndpointConfig]) -> usize {
+    let mut total = 0;
+    let mut idx = 0;
+    while idx < endpoints.len() {
+        total += endpoints[idx].required_memory();
+        idx += 1;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{required_memory, EndpointConfig};
+
+    #[test]
+    fn sizes() {
+        assert_eq!(required_memory(&[]), 0);
+        assert_eq!(required_memory(&[EndpointConfig::control(64)]), 128);
+        assert_eq!(
+            required_memory(&[
+                EndpointConfig::control(64),
+                EndpointConfig::new(16),
+                EndpointConfig::new(512),
+                EndpointConfig::new(512),
+            ]),
+            1168
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! USB serial (CDC-ACM) device.
+
+use imxrt_usbd::BusAdapter;
+use usb_device::{
+    bus::{UsbBus, UsbBusAllocator},
+    device::UsbDevice,
+    UsbError,
+};
+use usbd_serial::{LineCoding, ParityType, SerialPort, StopBits, USB_CLASS_CDC};
+
+use super::{DescriptorError, DeviceDescriptorConfig};
+
+/// A line coding callback.
+///
+/// See [`CdcSerial::set_line_coding_callback`].
+pub type LineCodingCallback = fn(&LineCoding);
+
+/// The line coding fields that the host can change.
+type Coding = (u32, StopBits, ParityType, u8);
+
+fn coding(line_coding: &LineCoding) -> Coding {
+    (
+        line_coding.data_rate(),
+        line_coding.stop_bits(),
+        line_coding.parity_type(),
+        line_coding.data_bits(),
+    )
+}
+
+/// A USB serial port.
+///
+/// `CdcSerial` combines a USB device with a `usbd-serial` [`SerialPort`].
+/// It's a device with a single CDC-ACM function, which is the most common
+/// USB device. Construct it with a bus allocator, usually wrapping a
+/// [`BusAdapter`], and a [`DeviceDescriptorConfig`].
+///
+/// Call [`poll`](Self::poll) often, either in a loop or in the USB interrupt
+/// handler. The non-blocking [`read`](Self::read) and [`write`](Self::write)
+/// return [`UsbError::WouldBlock`] when there's no data, or no buffer space.
+/// The blocking [`read_blocking`](Self::read_blocking) and
+/// [`write_all`](Self::write_all) poll the device until they finish.
+///
+/// `CdcSerial` also implements the `embedded-io` `Read` and `Write` traits.
+/// Like the blocking methods, these implementations poll the device.
+///
+/// Requires the `"usbd-serial"` feature.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use hal::usbd::{BusAdapter, CdcSerial, DeviceDescriptorConfig, Speed};
+/// use usb_device::bus::UsbBusAllocator;
+///
+/// const CONFIG: DeviceDescriptorConfig = DeviceDescriptorConfig::new(0x5824, 0x27dd, Speed::High)
+///     .product("imxrt-hal-example");
+///
+/// # fn f(bus: &'static UsbBusAllocator<BusAdapter>) {
+/// let bus: &UsbBusAllocator<BusAdapter> = // Allocated once...
+///     # bus;
+/// let mut serial = CdcSerial::new(bus, &CONFIG).unwrap();
+/// loop {
+///     serial.poll();
+///     let mut buffer = [0; 64];
+///     if let Ok(count) = serial.read(&mut buffer) {
+///         // Echo the data back to the host.
+///         serial.write_all(&buffer[..count]).ok();
+///     }
+/// }
+/// # }
+/// ```
+pub struct CdcSerial<'a, B: UsbBus = BusAdapter> {
+    device: UsbDevice<'a, B>,
+    serial: SerialPort<'a, B>,
+    coding: Coding,
+    line_coding_callback: Option<LineCodingCallback>,
+}
+
+impl<'a, B: UsbBus> CdcSerial<'a, B> {
+    /// Create a serial port on the bus.
+    ///
+    /// This uses `config` to build the USB device. The device class is
+    /// always the CDC class, even if `config` specifies another class.
+    /// Returns an error if `config` isn't valid.
+    pub fn new(
+        bus: &'a UsbBusAllocator<B>,
+        config: &DeviceDescriptorConfig<'a>,
+    ) -> Result<Self, DescriptorError> {
+        let config = config.device_class(USB_CLASS_CDC);
+        config.validate()?;
+        // Allocate the class before building the device.
+        let serial = SerialPort::new(bus);
+        let device = config.build(bus)?;
+        Ok(Self {
+            coding: coding(serial.line_coding()),
+            device,
+            serial,
+            line_coding_callback: None,
+        })
+    }
+
+    /// Poll the USB device.
+    ///
+    /// Returns `true` if the serial port may have data to read, or may have
+    /// space to write. Poll at least every 10ms, or whenever the USB
+    /// interrupt fires.
+    ///
+    /// If the host changed the line coding, this calls the line coding
+    /// callback.
+    pub fn poll(&mut self) -> bool {
+        let ready = self.device.poll(&mut [&mut self.serial]);
+        let coding = coding(self.serial.line_coding());
+        if coding != self.coding {
+            self.coding = coding;
+            if let Some(callback) = self.line_coding_callback {
+                callback(self.serial.line_coding());
+            }
+        }
+        ready
+    }
+
+    /// Set a function that's called when the host changes the line coding.
+    ///
+    /// The host sets the line coding when it opens the port, or when it
+    /// changes the baud rate, stop bits, parity, or data bits. Use this to
+    /// configure a UART that bridges to the USB serial port. The callback
+    /// runs in [`poll`](Self::poll).
+    ///
+    /// Pass `None` to remove the callback.
+    pub fn set_line_coding_callback(&mut self, callback: Option<LineCodingCallback>) {
+        self.line_coding_callback = callback;
+    }
+
+    /// Returns the line coding that the host last set.
+    pub fn line_coding(&self) -> &LineCoding {
+        self.serial.line_coding()
+    }
+
+    /// Returns the host's DTR (data terminal ready) state.
+    ///
+    /// Most hosts set DTR when they open the port.
+    pub fn dtr(&self) -> bool {
+        self.serial.dtr()
+    }
+
+    /// Returns the host's RTS (request to send) state.
+    pub fn rts(&self) -> bool {
+        self.serial.rts()
+    }
+
+    /// Read data that the host sent, without blocking.
+    ///
+    /// Returns the number of bytes read into `buffer`. Returns
+    /// [`UsbError::WouldBlock`] if there's no data.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<usize, UsbError> {
+        self.serial.read(buffer)
+    }
+
+    /// Write data for the host, without blocking.
+    ///
+    /// Returns the number of bytes buffered from `buffer`. Returns
+    /// [`UsbError::WouldBlock`] if there's no buffer space.
+    pub fn write(&mut self, buffer: &[u8]) -> Result<usize, UsbError> {
+        self.serial.write(buffer)
+    }
+
+    /// Send buffered data to the host, without blocking.
+    ///
+    /// Returns [`UsbError::WouldBlock`] if there's still data to send.
+    pub fn flush(&mut self) -> Result<(), UsbError> {
+        self.serial.flush()
+    }
+
+    /// Poll the device until there's data to read, then read it.
+    ///
+    /// Returns the number of bytes read into `buffer`, which is at
+    /// least one if `buffer` isn't empty. This blocks until the host
+    /// sends data.
+    pub fn read_blocking(&mut self, buffer: &mut [u8]) -> Result<usize, UsbError> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            self.poll();
+            match self.read(buffer) {
+                Ok(0) | Err(UsbError::WouldBlock) => {}
+                result => return result,
+            }
+        }
+    }
+
+    /// Poll the device until all of `buffer` is sent to the host.
+    ///
+    /// This blocks until the host reads the data. If no host reads
+    /// the port, this blocks forever.
+    pub fn write_all(&mut self, mut buffer: &[u8]) -> Result<(), UsbError> {
+        while !buffer.is_empty() {
+            self.poll();
+            match self.write(buffer) {
+                Ok(count) => buffer = &buffer[count..],
+                Err(UsbError::WouldBlock) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.flush_blocking()
+    }
+
+    /// Poll the device until all buffered data is sent to the host.
+    pub fn flush_blocking(&mut self) -> Result<(), UsbError> {
+        loop {
+            self.poll();
+            match self.flush() {
+                Err(UsbError::WouldBlock) => {}
+                result => return result,
+            }
+        }
+    }
+
+    /// Access the USB device.
+    pub fn device(&self) -> &UsbDevice<'a, B> {
+        &self.device
+    }
+
+    /// Mutably access the USB device.
+    pub fn device_mut(&mut self) -> &mut UsbDevice<'a, B> {
+        &mut self.device
+    }
+
+    /// Mutably access the serial port class.
+    pub fn serial_mut(&mut self) -> &mut SerialPort<'a, B> {
+        &mut self.serial
+    }
+
+    /// Release the USB device and the serial port class.
+    pub fn release(self) -> (UsbDevice<'a, B>, SerialPort<'a, B>) {
+        (self.device, self.serial)
+    }
+}
+
+fn error_kind(err: UsbError) -> eio06::ErrorKind {
+    match err {
+        UsbError::Unsupported => eio06::ErrorKind::Unsupported,
+        UsbError::InvalidState => eio06::ErrorKind::NotConnected,
+        UsbError::BufferOverflow
+        | UsbError::EndpointOverflow
+        | UsbError::EndpointMemoryOverflow => eio06::ErrorKind::OutOfMemory,
+        _ => eio06::ErrorKind::Other,
+    }
+}
+
+impl<B: UsbBus> eio06::ErrorType for CdcSerial<'_, B> {
+    type Error = eio06::ErrorKind;
+}
+
+impl<B: UsbBus> eio06::Read for CdcSerial<'_, B> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.read_blocking(buf).map_err(error_kind)
+    }
+}
+
+impl<B: UsbBus> eio06::Write for CdcSerial<'_, B> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            self.poll();
+            match CdcSerial::write(self, buf) {
+                Ok(0) | Err(UsbError::WouldBlock) => {}
+                result => return result.map_err(error_kind),
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flush_blocking().map_err(error_kind)
+    }
+}
@@ -0,0 +1,240 @@
+//! USB transfer statistics.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use usb_device::{
+    bus::{PollResult, UsbBus},
+    endpoint::{EndpointAddress, EndpointType},
+    UsbDirection, UsbError,
+};
+
+/// The number of endpoint numbers tracked by a [`StatsBus`].
+const ENDPOINTS: usize = 16;
+
+/// Transfer statistics for one endpoint.
+///
+/// Counters wrap on overflow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointStats {
+    /// The number of bytes transferred.
+    pub bytes: u32,
+    /// The number of packets transferred.
+    pub packets: u32,
+    /// The number of reads or writes that would block.
+    ///
+    /// A write would block when the endpoint still holds a packet
+    /// that the host hasn't taken. A read would block when the host
+    /// hasn't sent a packet. Either way, the controller NAKs the host,
+    /// so this approximates the endpoint's NAK count.
+    pub would_block: u32,
+}
+
+#[derive(Default)]
+struct Counters {
+    bytes: AtomicU32,
+    packets: AtomicU32,
+    would_block: AtomicU32,
+}
+
+impl Counters {
+    fn record(&self, result: &Result<usize, UsbError>) {
+        match result {
+            Ok(bytes) => {
+                self.bytes.fetch_add(*bytes as u32, Ordering::Relaxed);
+                self.packets.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(UsbError::WouldBlock) => {
+                self.would_block.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {}
+        }
+    }
+
+    fn take(&self) -> EndpointStats {
+        EndpointStats {
+            bytes: self.bytes.swap(0, Ordering::Relaxed),
+            packets: self.packets.swap(0, Ordering::Relaxed),
+            would_block: self.would_block.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+/// A USB bus that counts endpoint transfers.
+///
+/// `StatsBus` wraps another `usb-device` bus, like a
+/// [`BusAdapter`](crate::usbd::BusAdapter). It counts the bytes and
+/// packets that each endpoint transfers, so that you can measure
+/// throughput. Use [`take_stats`](Self::take_stats) to read, then reset,
+/// an endpoint's statistics.
+///
+/// Once you create a `UsbDevice`, access the `StatsBus` through the
+/// device's `bus()` method.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use hal::usbd::{StatsBus, EndpointStats};
+/// use usb_device::{bus::{UsbBus, UsbBusAllocator}, endpoint::EndpointAddress, UsbDirection};
+///
+/// # fn f<B: UsbBus>(bus: B) {
+/// let bus = UsbBusAllocator::new(StatsBus::new(bus));
+/// // Allocate classes, then build the device...
+/// # let device = usb_device::device::UsbDeviceBuilder::new(&bus, usb_device::device::UsbVidPid(0, 0)).build();
+///
+/// let ep1_in = EndpointAddress::from_parts(1, UsbDirection::In);
+/// let EndpointStats { bytes, .. } = device.bus().take_stats(ep1_in);
+/// # }
+/// ```
+pub struct StatsBus<B> {
+    bus: B,
+    counters: [[Counters; 2]; ENDPOINTS],
+    allocated_memory: usize,
+}
+
+impl<B> StatsBus<B> {
+    /// Count transfers on `bus`.
+    pub fn new(bus: B) -> Self {
+        Self {
+            bus,
+            counters: Default::default(),
+            allocated_memory: 0,
+        }
+    }
+
+    /// Access the wrapped bus.
+    pub fn bus(&self) -> &B {
+        &self.bus
+    }
+
+    /// Release the wrapped bus.
+    pub fn release(self) -> B {
+        self.bus
+    }
+
+    /// Returns the endpoint memory, in bytes, that the bus allocated.
+    ///
+    /// This sums the max packet sizes of all endpoints that were successfully
+    /// allocated. To size your endpoint memory, start with a large
+    /// [`EndpointMemory`](crate::usbd::EndpointMemory), then check this value
+    /// after you build your device. See [`required_memory`](crate::usbd::required_memory)
+    /// to compute the size ahead of time.
+    pub fn allocated_memory(&self) -> usize {
+        self.allocated_memory
+    }
+
+    fn counters(&self, ep_addr: EndpointAddress) -> Option<&Counters> {
+        let pair = self.counters.get(ep_addr.index())?;
+        Some(&pair[ep_addr.is_in() as usize])
+    }
+
+    /// Returns the statistics for an endpoint, then resets them.
+    ///
+    /// Returns zeroed statistics for an endpoint that hasn't
+    /// transferred any data.
+    pub fn take_stats(&self, ep_addr: EndpointAddress) -> EndpointStats {
+        self.counters(ep_addr)
+            .map(Counters::take)
+            .unwrap_or_default()
+    }
+
+    /// Reset all endpoint statistics.
+    pub fn reset_stats(&self) {
+        self.counters.iter().flatten().for_each(|counters| {
+            counters.take();
+        });
+    }
+}
+
+impl<B: UsbBus> UsbBus for StatsBus<B> {
+    fn alloc_ep(
+        &mut self,
+        ep_dir: UsbDirection,
+        ep_addr: Option<EndpointAddress>,
+        ep_type: EndpointType,
+        max_packet_size: u16,
+        interval: u8,
+    ) -> usb_device::Result<EndpointAddress> {
+        let ep_addr = self
+            .bus
+            .alloc_ep(ep_dir, ep_addr, ep_type, max_packet_size, interval)?;
+        self.allocated_memory += max_packet_size as usize;
+        Ok(ep_addr)
+    }
+
+    fn enable(&mut self) {
+        self.bus.enable();
+    }
+
+    fn reset(&self) {
+        self.bus.reset();
+    }
+
+    fn set_device_address(&self, addr: u8) {
+        self.bus.set_device_address(addr);
+    }
+
+    fn write(&self, ep_addr: EndpointAddress, buf: &[u8]) -> usb_device::Result<usize> {
+        let result = self.bus.write(ep_addr, buf);
+        if let Some(counters) = self.counters(ep_addr) {
+            counters.record(&result);
+        }
+        result
+    }
+
+    fn read(&self, ep_addr: EndpointAddress, buf: &mut [u8]) -> usb_device::Result<usize> {
+        let result = self.bus.read(ep_addr, buf);
+        if let Some(counters) = self.counters(ep_addr) {
+            counters.record(&result);
+        }
+        result
+    }
+
+    fn set_stalled(&self, ep_addr: EndpointAddress, stalled: bool) {
+        self.bus.set_stalled(ep_addr, stalled);
+    }
+
+    fn is_stalled(&self, ep_addr: EndpointAddress) -> bool {
+        self.bus.is_stalled(ep_addr)
+    }
+
+    fn suspend(&self) {
+        self.bus.suspend();
+    }
+
+    fn resume(&self) {
+        self.bus.resume();
+    }
+
+    fn poll(&self) -> PollResult {
+        self.bus.poll()
+    }
+
+    fn force_reset(&self) -> usb_device::Result<()> {
+        self.bus.force_reset()
+    }
+
+    const QUIRK_SET_ADDRESS_BEFORE_STATUS: bool = B::QUIRK_SET_ADDRESS_BEFORE_STATUS;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counters, EndpointStats};
+    use usb_device::UsbError;
+
+    #[test]
+    fn record_and_take() {
+        let counters = Counters::default();
+        counters.record(&Ok(64));
+        counters.record(&Ok(12));
+        counters.record(&Err(UsbError::WouldBlock));
+        counters.record(&Err(UsbError::BufferOverflow));
+        assert_eq!(
+            counters.take(),
+            EndpointStats {
+                bytes: 76,
+                packets: 2,
+                would_block: 1,
+            }
+        );
+        assert_eq!(counters.take(), EndpointStats::default());
+    }
+}
@@ -0,0 +1,153 @@
+//! VBUS detection.
+
+use crate::{ral, usbd::Instances};
+
+/// The OTGSC interrupt status bits.
+///
+/// These bits are write 1 to clear.
+const OTGSC_STATUS: u32 = ral::usb::OTGSC::IDIS::mask
+    | ral::usb::OTGSC::AVVIS::mask
+    | ral::usb::OTGSC::ASVIS::mask
+    | ral::usb::OTGSC::BSVIS::mask
+    | ral::usb::OTGSC::BSEIS::mask
+    | ral::usb::OTGSC::STATUS_1MS::mask
+    | ral::usb::OTGSC::DPIS::mask;
+
+/// A change in VBUS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbusEvent {
+    /// VBUS appeared. The device is plugged into a host.
+    Connected,
+    /// VBUS disappeared. The device is unplugged.
+    Disconnected,
+}
+
+/// Detects VBUS on a USB peripheral.
+///
+/// `Vbus` reads the USB controller's B session valid status, which
+/// indicates that a host powers VBUS. Use it on a self-powered device to
+/// hold the USB stack down until VBUS appears, and to tear down the stack
+/// when VBUS disappears.
+///
+/// Create a `Vbus` before you move the [`Instances`] into a
+/// [`BusAdapter`](crate::usbd::BusAdapter). `Vbus` only uses the
+/// controller's OTG status and control register, which the bus doesn't use.
+/// The USB clock gate must be enabled to detect VBUS.
+///
+/// ```no_run
+/// use imxrt_hal as hal;
+/// use imxrt_ral as ral;
+///
+/// use hal::usbd::{self, Vbus, VbusEvent};
+///
+/// # || -> Option<()> {
+/// let usb_instances = usbd::Instances {
+///     usb: unsafe { ral::usb::USB1::instance() },
+///     usbnc: unsafe { ral::usbnc::USBNC1::instance() },
+///     usbphy: unsafe { ral::usbphy::USBPHY1::instance() },
+/// };
+/// let mut vbus = Vbus::new(&usb_instances);
+///
+/// static ENDPOINT_MEMORY: usbd::EndpointMemory<2048> = usbd::EndpointMemory::new();
+/// static ENDPOINT_STATE: usbd::EndpointState = usbd::EndpointState::max_endpoints();
+/// let usbd = usbd::BusAdapter::new(usb_instances, &ENDPOINT_MEMORY, &ENDPOINT_STATE);
+///
+/// // Later...
+/// match vbus.poll() {
+///     Some(VbusEvent::Connected) => { /* Build and poll the USB device. */ }
+///     Some(VbusEvent::Disconnected) => { /* Tear down the USB device. */ }
+///     None => {}
+/// }
+/// # Some(()) }().unwrap();
+/// ```
+pub struct Vbus<const N: u8> {
+    usb: ral::usb::Instance<N>,
+    present: bool,
+}
+
+impl<const N: u8> Vbus<N> {
+    /// Create a VBUS detector for the USB peripheral.
+    ///
+    /// The detector considers VBUS absent, so the first [`poll`](Self::poll)
+    /// reports [`VbusEvent::Connected`] if the device is already plugged in.
+    pub fn new(instances: &Instances<N>) -> Self {
+        // Safety: the pointer is a valid USB register block. We only
+        // access OTGSC, which the USB driver doesn't use.
+        let usb = unsafe { ral::usb::Instance::<N>::new(&*instances.usb) };
+        Self {
+            usb,
+            present: false,
+        }
+    }
+
+    /// Returns `true` if VBUS is present.
+    pub fn is_present(&self) -> bool {
+        ral::read_reg!(ral::usb, self.usb, OTGSC, BSV == 1)
+    }
+
+    /// Check for a change in VBUS.
+    ///
+    /// Returns the change since the previous call, if any. This also clears
+    /// the VBUS interrupt flag. Call this from your USB interrupt handler when
+    /// you've enabled the VBUS interrupt, or call it periodically.
+    pub fn poll(&mut self) -> Option<VbusEvent> {
+        self.clear_status();
+        let present = self.is_present();
+        let event = event(self.present, present);
+        self.present = present;
+        event
+    }
+
+    /// Enable or disable the VBUS interrupt.
+    ///
+    /// When enabled, the USB peripheral's interrupt activates when VBUS
+    /// appears or disappears. Use [`poll`](Self::poll) to handle the interrupt.
+    pub fn set_interrupt_enable(&mut self, enable: bool) {
+        let otgsc = ral::read_reg!(ral::usb, self.usb, OTGSC) & !OTGSC_STATUS;
+        let otgsc = if enable {
+            otgsc | ral::usb::OTGSC::BSVIE::mask
+        } else {
+            otgsc & !ral::usb::OTGSC::BSVIE::mask
+        };
+        ral::write_reg!(ral::usb, self.usb, OTGSC, otgsc);
+    }
+
+    /// Returns `true` if the VBUS interrupt is enabled.
+    pub fn is_interrupt_enabled(&self) -> bool {
+        ral::read_reg!(ral::usb, self.usb, OTGSC, BSVIE == 1)
+    }
+
+    /// Clear the B session valid interrupt flag, without
+    /// clearing any other OTG interrupt flag.
+    fn clear_status(&mut self) {
+        let otgsc = ral::read_reg!(ral::usb, self.usb, OTGSC) & !OTGSC_STATUS;
+        ral::write_reg!(
+            ral::usb,
+            self.usb,
+            OTGSC,
+            otgsc | ral::usb::OTGSC::BSVIS::mask
+        );
+    }
+}
+
+/// Compute the VBUS event from the previous and current state.
+const fn event(previous: bool, present: bool) -> Option<VbusEvent> {
+    match (previous, present) {
+        (false, true) => Some(VbusEvent::Connected),
+        (true, false) => Some(VbusEvent::Disconnected),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{event, VbusEvent};
+
+    #[test]
+    fn vbus_event() {
+        assert_eq!(event(false, true), Some(VbusEvent::Connected));
+        assert_eq!(event(true, false), Some(VbusEvent::Disconnected));
+        assert_eq!(event(true, true), None);
+        assert_eq!(event(false, false), None);
+    }
+}
@@ -52,6 +52,9 @@
 //! | `"imxrt1170"`     | Enable features for the 1170 chips.                              |
 //! | `"eh02-unproven"` | Enable implementations for embedded-hal 0.2 `"unproven"` traits. |
 //! | `"rand_core"`     | Allows the TRNG to be used with the `rand` package.              |
+//! | `"usbd-serial"`   | Enable the USB serial port, `usbd::CdcSerial`.                   |
+//! | `"reg-trace"`     | Report register accesses to a callback. See `reg_trace`.         |
+//! | `"defmt"`         | Implement `defmt::Format` for DMA debugging types.               |
 //!
 //! The APIs exposed by the various `"imxrt[...]"` features are chip specific.
 //! The HAL does not support building with more than one of these features at a
@@ -83,31 +86,41 @@
 #![no_std]
 #![warn(missing_docs, unsafe_op_in_unsafe_fn)]
 
-use imxrt_ral as ral;
+mod ral;
 
 mod chip;
+#[cfg(feature = "reg-trace")]
+pub mod reg_trace;
 
 /// Modules that need no HAL conditional compilation.
 ///
 /// These modules only depend on a RAL feature.
 mod common {
-    pub use imxrt_dma as dma;
-
     pub mod ccm;
+    pub mod delay;
+    pub mod dma;
     pub mod flexpwm;
+    pub mod flexspi;
     pub mod gpio;
     pub mod gpt;
+    #[cfg(not(family = "none"))]
+    pub mod interrupt;
+    pub mod kpp;
     pub mod lpi2c;
     pub mod lpspi;
     pub mod lpuart;
     pub mod pit;
     pub mod snvs;
     pub mod timer;
+    #[cfg(feature = "imxrt-usbd")]
+    pub mod usbd;
 }
 
 // These common drivers have no associated chip APIs, so
 // export them directly.
-pub use common::{flexpwm, gpio, gpt, lpi2c, lpspi, lpuart, pit, snvs, timer};
+#[cfg(not(family = "none"))]
+pub use common::interrupt;
+pub use common::{delay, flexpwm, flexspi, gpio, gpt, kpp, lpi2c, lpspi, lpuart, pit, snvs, timer};
 
 /// Clock control module.
 ///
@@ -119,7 +132,8 @@ pub use common::{flexpwm, gpio, gpt, lpi2c, lpspi, lpuart, pit, snvs, timer};
 ///
 /// Use [`clock_gate`](crate::ccm::clock_gate) APIs to enable or disable the clock gates for
 /// various peripherals. You'll need to enable clock gates before you
-/// start using peripherals.
+/// start using peripherals. The LPUART, LPSPI, and LPI2C drivers
+/// also have `new_with_ccm` constructors that enable their own clock gates.
 ///
 /// The remaining modules provide lower-level APIs for the CCM clock
 /// tree. These APIs may not be portable across chip families.
@@ -154,6 +168,10 @@ pub mod ccm {
 /// family feature, this number may change. See [`CHANNEL_COUNT`](crate::dma::CHANNEL_COUNT)
 /// for more information.
 ///
+/// Dropping a channel doesn't stop it. If you release channels at runtime, wrap them
+/// in a [`ScopedChannel`](crate::dma::ScopedChannel), which stops and resets the channel
+/// when it drops.
+///
 /// # Visibility
 ///
 /// Select items become visible when a chip family feature is enabled.
@@ -196,6 +214,8 @@ pub mod ccm {
 /// # Some(()) }
 /// ```
 ///
+/// Use [`fill`](crate::dma::fill) to set every element of a buffer to one value.
+///
 /// For examples of using DMA with a peripheral, see the peripheral's documentation.
 pub mod dma {
     #[cfg_attr(family = "none", allow(unused_imports))] // Nothing to export in this build.
@@ -209,7 +229,13 @@ pub mod dma {
 ///  with the [`usb-device`](https://docs.rs/usb-device/latest/usb_device/) ecosystem.
 ///
 /// It also provides [`Instances`](crate::usbd::Instances), an implementation of `imxrt_usbd::Peripherals` over
-/// `imxrt-ral` USB instances.
+/// `imxrt-ral` USB instances. Use [`DeviceDescriptorConfig`](crate::usbd::DeviceDescriptorConfig)
+/// to build a `usb-device` device that matches your bus speed. Wrap a bus in a
+/// [`StatsBus`](crate::usbd::StatsBus) to measure endpoint throughput. Use [`Vbus`](crate::usbd::Vbus)
+/// to detect when the device is plugged in. Use
+/// [`required_memory`](crate::usbd::required_memory) to size your endpoint memory, and
+/// [`endpoint_memory!`](crate::endpoint_memory) to place it in a specific memory.
+/// The [`dfu`](crate::usbd::dfu) classes update firmware over USB. With the `"usbd-serial"` feature, `CdcSerial` provides a ready-to-use USB serial port.
 ///
 /// # Example
 ///
@@ -242,6 +268,7 @@ pub mod dma {
 /// ```
 #[cfg(feature = "imxrt-usbd")]
 pub mod usbd {
+    pub use crate::common::usbd::*;
     pub use imxrt_usbd::*;
 
     use crate::ral;
@@ -296,6 +323,14 @@ pub mod iomuxc {
 #[cfg_attr(family = "none", allow(unused_imports))] // Nothing to export in this build.
 pub use crate::chip::reexports::*;
 
+/// Reset the system.
+///
+/// This requests a system reset through the Cortex-M system control block.
+/// See [`src`] to learn about the reset cause after the reset.
+pub fn reset() -> ! {
+    cortex_m::peripheral::SCB::sys_reset()
+}
+
 /// Simply spin on the future.
 fn spin_on<F: core::future::Future>(future: F) -> F::Output {
     use core::task::{Context, Poll};
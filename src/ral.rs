@@ -0,0 +1,212 @@
+//! The HAL's view of `imxrt-ral`.
+//!
+//! This re-exports the RAL, replacing its `read_reg!`, `write_reg!`, and
+//! `modify_reg!` macros. The replacements behave exactly like the RAL's
+//! macros. When the `"reg-trace"` feature is enabled, they also report
+//! every register access to [`reg_trace`](crate::reg_trace). Without
+//! the feature, the helpers in this module inline to plain volatile
+//! reads and writes.
+
+pub use imxrt_ral::*;
+
+/// A register value that can be traced.
+pub trait Value: Copy {
+    /// Returns the value's bits, zero extended.
+    #[cfg_attr(not(feature = "reg-trace"), allow(dead_code))]
+    fn bits(self) -> u32;
+}
+
+impl Value for u8 {
+    #[inline(always)]
+    fn bits(self) -> u32 {
+        self.into()
+    }
+}
+
+impl Value for u16 {
+    #[inline(always)]
+    fn bits(self) -> u32 {
+        self.into()
+    }
+}
+
+impl Value for u32 {
+    #[inline(always)]
+    fn bits(self) -> u32 {
+        self
+    }
+}
+
+impl Value for i16 {
+    #[inline(always)]
+    fn bits(self) -> u32 {
+        (self as u16).into()
+    }
+}
+
+/// A register that the macros can read.
+pub trait Readable<T> {
+    /// Read the register.
+    fn read(&self) -> T;
+}
+
+/// A register that the macros can write.
+pub trait Writable<T> {
+    /// Write the register.
+    fn write(&self, value: T);
+}
+
+impl<T: Copy> Readable<T> for RWRegister<T> {
+    #[inline(always)]
+    fn read(&self) -> T {
+        RWRegister::read(self)
+    }
+}
+
+impl<T: Copy> Readable<T> for RORegister<T> {
+    #[inline(always)]
+    fn read(&self) -> T {
+        RORegister::read(self)
+    }
+}
+
+impl<T: Copy> Writable<T> for RWRegister<T> {
+    #[inline(always)]
+    fn write(&self, value: T) {
+        RWRegister::write(self, value)
+    }
+}
+
+impl<T: Copy> Writable<T> for WORegister<T> {
+    #[inline(always)]
+    fn write(&self, value: T) {
+        WORegister::write(self, value)
+    }
+}
+
+/// Read a register, tracing the access.
+#[inline(always)]
+#[cfg_attr(not(feature = "reg-trace"), allow(unused_variables))]
+pub fn read<R, T>(register: &R, peripheral: &'static str, name: &'static str) -> T
+where
+    R: Readable<T>,
+    T: Value,
+{
+    let value = register.read();
+    #[cfg(feature = "reg-trace")]
+    crate::reg_trace::emit(
+        crate::reg_trace::Kind::Read,
+        peripheral,
+        name,
+        register as *const R as usize,
+        value.bits(),
+    );
+    value
+}
+
+/// Write a register, tracing the access.
+#[inline(always)]
+#[cfg_attr(not(feature = "reg-trace"), allow(unused_variables))]
+pub fn write<R, T>(register: &R, peripheral: &'static str, name: &'static str, value: T)
+where
+    R: Writable<T>,
+    T: Value,
+{
+    #[cfg(feature = "reg-trace")]
+    crate::reg_trace::emit(
+        crate::reg_trace::Kind::Write,
+        peripheral,
+        name,
+        register as *const R as usize,
+        value.bits(),
+    );
+    register.write(value);
+}
+
+// The macros below mirror the arms of the RAL's macros. Only the
+// register accesses change.
+
+macro_rules! write_reg {
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $( $field:ident : $value:expr ),+ $(,)? ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        $crate::ral::write(
+            &(*$instance).$reg $([$offset])*,
+            stringify!($periph),
+            stringify!($reg),
+            $({
+                use $periph::{$reg::$field::{W::*, RW::*}};
+                ($value << { use $periph::{$reg::$field::offset}; offset })
+                    & { use $periph::{$reg::$field::mask}; mask }
+            }) | *
+        );
+    }};
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $value:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        $crate::ral::write(
+            &(*$instance).$reg $([$offset])*,
+            stringify!($periph),
+            stringify!($reg),
+            $value,
+        );
+    }};
+}
+
+macro_rules! modify_reg {
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $( $field:ident : $value:expr ),+ $(,)? ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        $crate::ral::write(
+            &(*$instance).$reg $([$offset])*,
+            stringify!($periph),
+            stringify!($reg),
+            ($crate::ral::read(&(*$instance).$reg $([$offset])*, stringify!($periph), stringify!($reg))
+                & !( $({ use $periph::{$reg::$field::mask}; mask }) | * ))
+            | $({
+                use $periph::{$reg::$field::{W::*, RW::*}};
+                ($value << { use $periph::{$reg::$field::offset}; offset })
+                    & { use $periph::{$reg::$field::mask}; mask }
+            }) | *
+        );
+    }};
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $fn:expr ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        $crate::ral::write(
+            &(*$instance).$reg $([$offset])*,
+            stringify!($periph),
+            stringify!($reg),
+            $fn($crate::ral::read(&(*$instance).$reg $([$offset])*, stringify!($periph), stringify!($reg))),
+        );
+    }};
+}
+
+macro_rules! read_reg {
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $( $field:ident ),+ $(,)? ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        let val = $crate::ral::read(&(*$instance).$reg $([$offset])*, stringify!($periph), stringify!($reg));
+        ( $({
+            #[allow(unused_imports)]
+            use $periph::{$reg::$field::{mask, offset, R::*, RW::*}};
+            (val & mask) >> offset
+        }) , *)
+    }};
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])*, $field:ident $($cmp:tt)* ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        #[allow(unused_imports)]
+        use $periph::{$reg::$field::{mask, offset, R::*, RW::*}};
+        (($crate::ral::read(&(*$instance).$reg $([$offset])*, stringify!($periph), stringify!($reg)) & mask) >> offset) $($cmp)*
+    }};
+    ( $periph:path, $instance:expr, $reg:ident $([$offset:expr])* ) => {{
+        #[allow(unused_imports)]
+        use $periph::{*};
+        ($crate::ral::read(&(*$instance).$reg $([$offset])*, stringify!($periph), stringify!($reg)))
+    }};
+}
+
+pub(crate) use {modify_reg, read_reg, write_reg};
@@ -0,0 +1,127 @@
+//! Register access tracing.
+//!
+//! When the `"reg-trace"` feature is enabled, the HAL reports its register
+//! accesses to a callback that you provide. Use this to see what a driver
+//! does to the hardware, without a debugger.
+//!
+//! The HAL traces every access that it makes through the RAL's
+//! `read_reg!`, `write_reg!`, and `modify_reg!` macros. A `modify_reg!`
+//! produces a read, then a write. Accesses made through other means,
+//! like direct register reads, or accesses made by your own code, are
+//! not traced.
+//!
+//! The callback runs in the context of the access, which might be an
+//! interrupt handler. Keep it short. The callback must not use HAL
+//! drivers; if it does, it traces itself.
+//!
+//! When the feature is disabled, this module doesn't exist, and tracing
+//! costs nothing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imxrt_hal as hal;
+//! use hal::reg_trace::{self, Access};
+//!
+//! fn log_access(access: &Access) {
+//!     // Send the access to your logger...
+//!     # let _ = access;
+//! }
+//!
+//! reg_trace::set_callback(Some(log_access));
+//! ```
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The kind of register access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// The HAL read the register.
+    Read,
+    /// The HAL wrote the register.
+    Write,
+}
+
+/// A traced register access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Access {
+    /// Read or write.
+    pub kind: Kind,
+    /// The RAL module path of the peripheral, like `"ral::lpuart"`.
+    pub peripheral: &'static str,
+    /// The register name, like `"CTRL"`.
+    pub register: &'static str,
+    /// The register's address.
+    pub address: usize,
+    /// The value read from, or written to, the register.
+    ///
+    /// Values of narrower registers are zero extended.
+    pub value: u32,
+}
+
+/// A register access callback.
+pub type Callback = fn(&Access);
+
+static CALLBACK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Set the callback that receives register accesses.
+///
+/// Pass `None` to stop tracing. Tracing is off until you set a callback.
+pub fn set_callback(callback: Option<Callback>) {
+    let ptr = callback.map_or(core::ptr::null_mut(), |callback| callback as *mut ());
+    CALLBACK.store(ptr, Ordering::Release);
+}
+
+pub(crate) fn emit(
+    kind: Kind,
+    peripheral: &'static str,
+    register: &'static str,
+    address: usize,
+    value: u32,
+) {
+    let ptr = CALLBACK.load(Ordering::Acquire);
+    if ptr.is_null() {
+        return;
+    }
+    // Safety: the only non-null values come from a `Callback` in `set_callback`.
+    let callback: Callback = unsafe { core::mem::transmute::<*mut (), Callback>(ptr) };
+    callback(&Access {
+        kind,
+        peripheral,
+        register,
+        address,
+        value,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_callback, Access, Kind};
+    use crate::ral::{self, RWRegister};
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static SEEN: AtomicU32 = AtomicU32::new(0);
+
+    fn record(access: &Access) {
+        let bit = match access.kind {
+            Kind::Read => 1,
+            Kind::Write => 1 << 16,
+        };
+        assert_eq!(access.register, "REG");
+        SEEN.fetch_add(bit, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn modify_reads_then_writes() {
+        let memory = core::cell::UnsafeCell::new(0u32);
+        // Safety: RWRegister is a transparent UnsafeCell.
+        let register = unsafe { &*memory.get().cast::<RWRegister<u32>>() };
+        set_callback(Some(record));
+        let value = ral::read(register, "test", "REG");
+        ral::write(register, "test", "REG", value | 1);
+        set_callback(None);
+        ral::write(register, "test", "REG", 0);
+        assert_eq!(SEEN.load(Ordering::Relaxed), 1 | 1 << 16);
+    }
+}